@@ -1,76 +1,222 @@
 use serde_json::json;
+use state::DaoState;
 use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Instant;
-use storage::dynamodb_dao::DynamoDbDao;
-use storage::memory_dao::MemoryDao;
-// use storage::sheets_dao::SheetsDao;
-use storage::{Balance, StorageDao, Transaction};
-use tauri_plugin_store::StoreExt;
+use storage::config::{self, BackendConfig};
+use storage::correction::Amendment;
+use storage::geometry::{self, SplitGeometry};
+use storage::settlement;
+use storage::stats::{self, SplitStats};
+use storage::{
+    Activity, ActivityFilter, AwardTable, Balance, Correction, GameId, GameSummary, NewGame,
+    Payment, Player, PlayerId, PotPolicy, Replay, Roster, SettlementPlan, Split, StakePolicy,
+    StorageError, Transaction, TransactionId, TransactionPage, Void, DEFAULT_GAME_ID,
+};
+use tauri::{Manager, State, Wry};
+use tauri_plugin_store::{Store, StoreExt};
 
 pub mod secrets;
+mod state;
 mod storage;
 
 const DEMO_SHEET_ID: &str = "1SIvYTqRcno-BxMWZAWNcw208N3WREZRRcPzjn_ftUYo";
-const USE_MEMORY_STORAGE: bool = false; // Set to true to use in-memory storage for testing
-const USE_DYNAMODB_STORAGE: bool = true; // Set to true to use DynamoDB storage
-
-// Factory function to create the appropriate DAO
-async fn create_dao(_app: &tauri::AppHandle) -> Arc<dyn StorageDao> {
-    if USE_MEMORY_STORAGE {
-        Arc::new(MemoryDao::new())
-    } else if USE_DYNAMODB_STORAGE {
-        Arc::new(DynamoDbDao::new().await)
-    } else {
-        // let sheet_id = get_sheet_id_from_store(app.clone());
-        // Arc::new(SheetsDao::new(sheet_id).await)
-        Arc::new(DynamoDbDao::new().await)
+const BACKEND_KEY: &str = "backend";
+const CURRENT_GAME_KEY: &str = "current-game";
+const DEVICE_NAME_KEY: &str = "device-name";
+const SHEET_ID_KEY: &str = "sheet-id";
+const DEFAULT_DEVICE_NAME: &str = "Unnamed device";
+
+fn open_store(app: &tauri::AppHandle) -> Result<Arc<Store<Wry>>, StorageError> {
+    app.store("store.json").map_err(|e| {
+        StorageError::BackendUnavailable(format!("Failed to open the settings store: {}", e))
+    })
+}
+
+/// Saves `value` under `key`, reporting a failed write rather than crashing.
+fn save_to_store(
+    app: &tauri::AppHandle,
+    key: &str,
+    value: serde_json::Value,
+) -> Result<(), StorageError> {
+    let store = open_store(app)?;
+    store.set(key, value);
+    let saved = store.save().map_err(|e| {
+        StorageError::BackendUnavailable(format!("Failed to save the settings store: {}", e))
+    });
+    store.close_resource();
+    saved
+}
+
+fn get_backend_config_from_store(app: &tauri::AppHandle) -> Result<BackendConfig, StorageError> {
+    let store = open_store(app)?;
+    match store.get(BACKEND_KEY) {
+        // Falling back to the default would quietly switch to the real league ledger
+        Some(value) => serde_json::from_value(value).map_err(|e| {
+            StorageError::InvalidConfig(format!("The saved backend config can't be read: {}", e))
+        }),
+        None => Ok(BackendConfig::default()),
     }
 }
 
-fn get_sheet_id_from_store(app: tauri::AppHandle) -> String {
-    let store = app.store("store.json").expect("Failed to open store");
-    let sheet_id = store.get("sheet-id").unwrap_or_default();
-    let sheet_id = sheet_id.get("value").expect("Failed to get sheet id");
-    let sheet_id = sheet_id
-        .as_str()
-        .expect("Failed to get sheet id as string")
-        .to_string();
-    sheet_id
+fn get_current_game_from_store(app: &tauri::AppHandle) -> Result<GameId, StorageError> {
+    let store = open_store(app)?;
+    Ok(match store.get(CURRENT_GAME_KEY) {
+        Some(value) => serde_json::from_value(value).unwrap_or_else(|e| {
+            log::error!("Ignoring unreadable current game: {:?}", e);
+            GameId(DEFAULT_GAME_ID.to_string())
+        }),
+        None => GameId(DEFAULT_GAME_ID.to_string()),
+    })
+}
+
+/// How this device signs the changes it makes, e.g. undos.
+fn get_device_name_from_store(app: &tauri::AppHandle) -> Result<String, StorageError> {
+    Ok(open_store(app)?
+        .get(DEVICE_NAME_KEY)
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_else(|| DEFAULT_DEVICE_NAME.to_string()))
+}
+
+/// A void signed with this device's name. A blank reason is no reason.
+fn void_from_this_device(
+    app: &tauri::AppHandle,
+    reason: Option<String>,
+) -> Result<Void, StorageError> {
+    let reason = reason.filter(|reason| !reason.trim().is_empty());
+    Ok(Void::now(get_device_name_from_store(app)?, reason))
+}
+
+fn get_sheet_id_from_store(app: &tauri::AppHandle) -> Result<String, StorageError> {
+    let saved = open_store(app)?.get(SHEET_ID_KEY);
+    saved
+        .as_ref()
+        .and_then(|value| value.get("value"))
+        .and_then(|value| value.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| {
+            StorageError::InvalidConfig(format!("No readable sheet id is saved: {:?}", saved))
+        })
+}
+
+#[tauri::command]
+async fn list_games(state: State<'_, DaoState>) -> Result<Vec<GameSummary>, StorageError> {
+    let dao = state.get()?;
+    let start = Instant::now();
+    let result = dao.list_games().await?;
+    log::info!("list_games operation took {:?}", start.elapsed());
+    Ok(result)
+}
+
+#[tauri::command]
+async fn get_current_game(state: State<'_, DaoState>) -> Result<GameSummary, StorageError> {
+    let dao = state.get()?;
+    let start = Instant::now();
+    let result = dao.get_game(&state.game()).await?;
+    log::info!("get_game operation took {:?}", start.elapsed());
+    Ok(result)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+async fn select_game(
+    app: tauri::AppHandle,
+    state: State<'_, DaoState>,
+    game_id: GameId,
+) -> Result<GameSummary, StorageError> {
+    let dao = state.get()?;
+    let start = Instant::now();
+    // Only remember games that exist so a typo can't strand the app on an empty ledger
+    let game = dao.get_game(&game_id).await?;
+    save_to_store(&app, CURRENT_GAME_KEY, json!(game_id))?;
+    state.select_game(game_id);
+    log::info!("select_game operation took {:?}", start.elapsed());
+    Ok(game)
 }
 
 #[tauri::command]
-async fn balances(app: tauri::AppHandle) -> Result<Vec<Balance>, ()> {
+async fn create_game(
+    app: tauri::AppHandle,
+    state: State<'_, DaoState>,
+    game: NewGame,
+) -> Result<GameSummary, StorageError> {
+    let actor = get_device_name_from_store(&app)?;
+    let dao = state.get()?;
     let start = Instant::now();
-    let dao = create_dao(&app).await;
-    log::info!("DAO initialization took {:?}", start.elapsed());
+    let result = dao.create_game(game, &actor).await?;
+    log::info!("create_game operation took {:?}", start.elapsed());
+    Ok(result)
+}
 
+#[tauri::command]
+async fn balances(state: State<'_, DaoState>) -> Result<Vec<Balance>, StorageError> {
+    let dao = state.get()?;
     let start = Instant::now();
-    let result = dao.get_balances().await;
+    let result = dao.get_balances(&state.game()).await?;
     log::info!("get_balances operation took {:?}", start.elapsed());
     Ok(result)
 }
 
 #[tauri::command]
-async fn names(app: tauri::AppHandle) -> Result<Vec<String>, ()> {
+async fn get_players(state: State<'_, DaoState>) -> Result<Vec<Player>, StorageError> {
+    let dao = state.get()?;
     let start = Instant::now();
-    let dao = create_dao(&app).await;
-    log::info!("DAO initialization took {:?}", start.elapsed());
+    let result = dao.get_players(&state.game()).await?;
+    log::info!("get_players operation took {:?}", start.elapsed());
+    Ok(result)
+}
 
+#[tauri::command]
+async fn add_player(
+    app: tauri::AppHandle,
+    state: State<'_, DaoState>,
+    name: String,
+) -> Result<PlayerId, StorageError> {
+    let actor = get_device_name_from_store(&app)?;
+    let dao = state.get()?;
     let start = Instant::now();
-    let result = dao.get_names().await;
-    log::info!("get_names operation took {:?}", start.elapsed());
+    let result = dao.add_player(&state.game(), name, &actor).await?;
+    log::info!("add_player operation took {:?}", start.elapsed());
     Ok(result)
 }
 
+#[tauri::command(rename_all = "snake_case")]
+async fn rename_player(
+    app: tauri::AppHandle,
+    state: State<'_, DaoState>,
+    player: PlayerId,
+    new_name: String,
+) -> Result<(), StorageError> {
+    let actor = get_device_name_from_store(&app)?;
+    let dao = state.get()?;
+    let start = Instant::now();
+    dao.rename_player(&state.game(), &player, new_name, &actor)
+        .await?;
+    log::info!("rename_player operation took {:?}", start.elapsed());
+    Ok(())
+}
+
 #[tauri::command]
-async fn transactions(app: tauri::AppHandle, count: usize) -> Result<Vec<Transaction>, ()> {
+async fn retire_player(
+    app: tauri::AppHandle,
+    state: State<'_, DaoState>,
+    player: PlayerId,
+) -> Result<(), StorageError> {
+    let actor = get_device_name_from_store(&app)?;
+    let dao = state.get()?;
     let start = Instant::now();
-    let dao = create_dao(&app).await;
-    log::info!("DAO initialization took {:?}", start.elapsed());
+    dao.retire_player(&state.game(), &player, &actor).await?;
+    log::info!("retire_player operation took {:?}", start.elapsed());
+    Ok(())
+}
 
+#[tauri::command]
+async fn transactions(
+    state: State<'_, DaoState>,
+    count: usize,
+) -> Result<Vec<Transaction>, StorageError> {
+    let dao = state.get()?;
     let start = Instant::now();
-    let in_order = dao.get_last_n_transactions(count).await;
+    let in_order = dao.get_last_n_transactions(&state.game(), count).await?;
     log::info!(
         "get_last_n_transactions operation took {:?}",
         start.elapsed()
@@ -78,91 +224,365 @@ async fn transactions(app: tauri::AppHandle, count: usize) -> Result<Vec<Transac
     Ok(in_order.into_iter().rev().collect())
 }
 
+// Unlike the storage API, pages are returned newest first to match `transactions`.
 #[tauri::command]
-async fn remove_last_transaction(app: tauri::AppHandle) -> Result<(), ()> {
+async fn transactions_page(
+    state: State<'_, DaoState>,
+    before: Option<TransactionId>,
+    limit: usize,
+) -> Result<TransactionPage, StorageError> {
+    let dao = state.get()?;
     let start = Instant::now();
-    let dao = create_dao(&app).await;
-    log::info!("DAO initialization took {:?}", start.elapsed());
+    let mut page = dao
+        .get_transactions_page(&state.game(), before, limit)
+        .await?;
+    log::info!("get_transactions_page operation took {:?}", start.elapsed());
+    page.transactions.reverse();
+    Ok(page)
+}
 
+#[tauri::command]
+async fn remove_last_transaction(
+    app: tauri::AppHandle,
+    state: State<'_, DaoState>,
+    reason: Option<String>,
+) -> Result<Transaction, StorageError> {
+    let dao = state.get()?;
     let start = Instant::now();
-    dao.remove_last_transaction().await;
+    let void = void_from_this_device(&app, reason)?;
+    let removed = dao.remove_last_transaction(&state.game(), void).await?;
     log::info!(
         "remove_last_transaction operation took {:?}",
         start.elapsed()
     );
-    Ok(())
+    Ok(removed)
 }
 
-#[tauri::command(rename_all = "snake_case")]
-async fn create_split(app: tauri::AppHandle, name: &str, split_string: &str) -> Result<(), ()> {
+#[tauri::command]
+async fn redo_last_undo(
+    app: tauri::AppHandle,
+    state: State<'_, DaoState>,
+) -> Result<Transaction, StorageError> {
+    let actor = get_device_name_from_store(&app)?;
+    let dao = state.get()?;
     let start = Instant::now();
-    let dao = create_dao(&app).await;
-    log::info!("DAO initialization took {:?}", start.elapsed());
+    let restored = dao.redo_last_undo(&state.game(), &actor).await?;
+    log::info!("redo_last_undo operation took {:?}", start.elapsed());
+    Ok(restored)
+}
 
+// With `preview` set, nothing is written and the result shows what would change.
+#[tauri::command]
+async fn void_transaction(
+    app: tauri::AppHandle,
+    state: State<'_, DaoState>,
+    id: TransactionId,
+    reason: Option<String>,
+    preview: bool,
+) -> Result<Replay, StorageError> {
+    let actor = get_device_name_from_store(&app)?;
+    let dao = state.get()?;
     let start = Instant::now();
-    dao.add_split(name.to_string(), split_string.to_string())
-        .await;
-    log::info!("add_split operation took {:?}", start.elapsed());
-    Ok(())
+    let game = state.game();
+    let correction = Correction::Void(void_from_this_device(&app, reason)?);
+    let result = if preview {
+        dao.preview_correction(&game, id, &correction).await?
+    } else {
+        dao.correct_transaction(&game, id, correction, &actor)
+            .await?
+    };
+    log::info!("void_transaction operation took {:?}", start.elapsed());
+    Ok(result)
+}
+
+#[tauri::command]
+async fn amend_transaction(
+    app: tauri::AppHandle,
+    state: State<'_, DaoState>,
+    id: TransactionId,
+    player: PlayerId,
+    pins: Vec<u8>,
+    preview: bool,
+) -> Result<Replay, StorageError> {
+    let split = Split::from_pins(pins)?;
+    let actor = get_device_name_from_store(&app)?;
+    let dao = state.get()?;
+    let start = Instant::now();
+    let game = state.game();
+    let correction = Correction::Amend(Amendment { player, split });
+    let result = if preview {
+        dao.preview_correction(&game, id, &correction).await?
+    } else {
+        dao.correct_transaction(&game, id, correction, &actor)
+            .await?
+    };
+    log::info!("amend_transaction operation took {:?}", start.elapsed());
+    Ok(result)
 }
 
 #[tauri::command(rename_all = "snake_case")]
-async fn convert_split(app: tauri::AppHandle, name: &str, split_string: &str) -> Result<(), ()> {
+async fn create_split(
+    app: tauri::AppHandle,
+    state: State<'_, DaoState>,
+    player: PlayerId,
+    pins: Vec<u8>,
+) -> Result<(), StorageError> {
+    let split = Split::from_pins(pins)?;
+    let actor = get_device_name_from_store(&app)?;
+    let dao = state.get()?;
     let start = Instant::now();
-    let dao = create_dao(&app).await;
-    log::info!("DAO initialization took {:?}", start.elapsed());
+    dao.add_split(&state.game(), &player, split, &actor).await?;
+    log::info!("add_split operation took {:?}", start.elapsed());
+    Ok(())
+}
 
+#[tauri::command(rename_all = "snake_case")]
+async fn convert_split(
+    app: tauri::AppHandle,
+    state: State<'_, DaoState>,
+    player: PlayerId,
+    pins: Vec<u8>,
+) -> Result<(), StorageError> {
+    let split = Split::from_pins(pins)?;
+    let actor = get_device_name_from_store(&app)?;
+    let dao = state.get()?;
     let start = Instant::now();
-    dao.add_conversion(name.to_string(), split_string.to_string())
-        .await;
+    dao.add_conversion(&state.game(), &player, split, &actor)
+        .await?;
     log::info!("add_conversion operation took {:?}", start.elapsed());
     Ok(())
 }
 
 #[tauri::command]
-async fn get_valid_splits(app: tauri::AppHandle) -> Result<HashSet<String>, ()> {
-    let start = Instant::now();
-    let dao = create_dao(&app).await;
-    log::info!("DAO initialization took {:?}", start.elapsed());
-
+async fn get_valid_splits(state: State<'_, DaoState>) -> Result<HashSet<Split>, StorageError> {
+    let dao = state.get()?;
     let start = Instant::now();
     let result = dao
-        .get_split_awards()
-        .await
-        .iter()
-        .map(|(k, _v)| k.clone())
+        .get_split_awards(&state.game())
+        .await?
+        .into_keys()
         .collect();
     log::info!("get_split_awards operation took {:?}", start.elapsed());
     Ok(result)
 }
 
+#[tauri::command]
+async fn split_stats(state: State<'_, DaoState>) -> Result<SplitStats, StorageError> {
+    let dao = state.get()?;
+    let start = Instant::now();
+    let game = state.game();
+    let transactions = dao.get_all_transactions(&game).await?;
+    let roster = Roster {
+        players: dao.get_players(&game).await?,
+        ..Roster::default()
+    };
+    let result = stats::split_stats(&transactions, &roster);
+    log::info!("split_stats operation took {:?}", start.elapsed());
+    Ok(result)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+async fn plan_settlement(
+    state: State<'_, DaoState>,
+    keep_pot: bool,
+) -> Result<SettlementPlan, StorageError> {
+    let dao = state.get()?;
+    let start = Instant::now();
+    let balances = dao.get_balances(&state.game()).await?;
+    let result = settlement::plan(&balances, keep_pot);
+    log::info!("plan_settlement operation took {:?}", start.elapsed());
+    Ok(result)
+}
+
+#[tauri::command]
+async fn record_settlement(
+    app: tauri::AppHandle,
+    state: State<'_, DaoState>,
+    payments: Vec<Payment>,
+) -> Result<Vec<Transaction>, StorageError> {
+    let actor = get_device_name_from_store(&app)?;
+    let dao = state.get()?;
+    let start = Instant::now();
+    let result = dao
+        .record_settlement(&state.game(), payments, &actor)
+        .await?;
+    log::info!("record_settlement operation took {:?}", start.elapsed());
+    Ok(result)
+}
+
+#[tauri::command]
+async fn get_activity_log(
+    state: State<'_, DaoState>,
+    filter: ActivityFilter,
+) -> Result<Vec<Activity>, StorageError> {
+    let dao = state.get()?;
+    let start = Instant::now();
+    let result = dao.get_activity_log(&state.game(), &filter).await?;
+    log::info!("get_activity_log operation took {:?}", start.elapsed());
+    Ok(result)
+}
+
+#[tauri::command]
+async fn classify_split(pins: Vec<u8>) -> Result<SplitGeometry, StorageError> {
+    geometry::classify(&pins)
+}
+
+#[tauri::command]
+async fn get_suggested_award_table() -> Result<AwardTable, StorageError> {
+    Ok(geometry::suggested_award_table())
+}
+
+#[tauri::command]
+async fn get_award_table(state: State<'_, DaoState>) -> Result<AwardTable, StorageError> {
+    let dao = state.get()?;
+    let start = Instant::now();
+    let result = dao.get_award_table(&state.game()).await?;
+    log::info!("get_award_table operation took {:?}", start.elapsed());
+    Ok(result)
+}
+
+#[tauri::command]
+async fn set_award_table(
+    app: tauri::AppHandle,
+    state: State<'_, DaoState>,
+    table: AwardTable,
+) -> Result<(), StorageError> {
+    let actor = get_device_name_from_store(&app)?;
+    let dao = state.get()?;
+    let start = Instant::now();
+    dao.set_award_table(&state.game(), table, &actor).await?;
+    log::info!("set_award_table operation took {:?}", start.elapsed());
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_stake_policy(state: State<'_, DaoState>) -> Result<StakePolicy, StorageError> {
+    let dao = state.get()?;
+    let start = Instant::now();
+    let result = dao.get_stake_policy(&state.game()).await?;
+    log::info!("get_stake_policy operation took {:?}", start.elapsed());
+    Ok(result)
+}
+
+#[tauri::command]
+async fn set_stake_policy(
+    app: tauri::AppHandle,
+    state: State<'_, DaoState>,
+    policy: StakePolicy,
+) -> Result<(), StorageError> {
+    let actor = get_device_name_from_store(&app)?;
+    let dao = state.get()?;
+    let start = Instant::now();
+    dao.set_stake_policy(&state.game(), policy, &actor).await?;
+    log::info!("set_stake_policy operation took {:?}", start.elapsed());
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_pot_policy(state: State<'_, DaoState>) -> Result<PotPolicy, StorageError> {
+    let dao = state.get()?;
+    let start = Instant::now();
+    let result = dao.get_pot_policy(&state.game()).await?;
+    log::info!("get_pot_policy operation took {:?}", start.elapsed());
+    Ok(result)
+}
+
+#[tauri::command]
+async fn set_pot_policy(
+    app: tauri::AppHandle,
+    state: State<'_, DaoState>,
+    policy: PotPolicy,
+) -> Result<(), StorageError> {
+    let actor = get_device_name_from_store(&app)?;
+    let dao = state.get()?;
+    let start = Instant::now();
+    dao.set_pot_policy(&state.game(), policy, &actor).await?;
+    log::info!("set_pot_policy operation took {:?}", start.elapsed());
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_display_timezone(state: State<'_, DaoState>) -> Result<String, StorageError> {
+    let dao = state.get()?;
+    let start = Instant::now();
+    let result = dao.get_display_timezone(&state.game()).await?;
+    log::info!("get_display_timezone operation took {:?}", start.elapsed());
+    Ok(result)
+}
+
+#[tauri::command]
+async fn set_display_timezone(
+    app: tauri::AppHandle,
+    state: State<'_, DaoState>,
+    timezone: String,
+) -> Result<(), StorageError> {
+    let actor = get_device_name_from_store(&app)?;
+    let dao = state.get()?;
+    let start = Instant::now();
+    dao.set_display_timezone(&state.game(), timezone, &actor)
+        .await?;
+    log::info!("set_display_timezone operation took {:?}", start.elapsed());
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_device_name(app: tauri::AppHandle) -> Result<String, StorageError> {
+    get_device_name_from_store(&app)
+}
+
+#[tauri::command]
+async fn set_device_name(app: tauri::AppHandle, name: String) -> Result<(), StorageError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(StorageError::InvalidConfig(
+            "The device name can't be blank".to_string(),
+        ));
+    }
+    save_to_store(&app, DEVICE_NAME_KEY, json!(name))
+}
+
+#[tauri::command]
+async fn get_backend_config(app: tauri::AppHandle) -> Result<BackendConfig, StorageError> {
+    get_backend_config_from_store(&app)
+}
+
+#[tauri::command]
+async fn set_backend_config(
+    app: tauri::AppHandle,
+    state: State<'_, DaoState>,
+    config: BackendConfig,
+) -> Result<(), StorageError> {
+    let start = Instant::now();
+    // Build the new DAO before touching the store so a bad config leaves the current
+    // backend in place.
+    let dao = config::create_dao(&config).await?;
+    save_to_store(&app, BACKEND_KEY, json!(config))?;
+    state.replace(dao);
+    log::info!("set_backend_config operation took {:?}", start.elapsed());
+    Ok(())
+}
+
 #[tauri::command(rename_all = "snake_case")]
-async fn set_sheet_id(app: tauri::AppHandle, sheet_id: &str) -> Result<(), ()> {
+async fn set_sheet_id(app: tauri::AppHandle, sheet_id: &str) -> Result<(), StorageError> {
     let start: Instant = Instant::now();
-    let store = app.store("store.json").expect("Failed to open store");
-    store.set("sheet-id", json!({ "value": sheet_id }));
-    store.save().expect("Failed to save store");
+    save_to_store(&app, SHEET_ID_KEY, json!({ "value": sheet_id }))?;
     log::info!("set_sheet_id operation took {:?}", start.elapsed());
-    store.close_resource();
     Ok(())
 }
 
 #[tauri::command]
-async fn get_sheet_id(app: tauri::AppHandle) -> Result<String, ()> {
+async fn get_sheet_id(app: tauri::AppHandle) -> Result<String, StorageError> {
     let start = Instant::now();
-    let sheet_id = get_sheet_id_from_store(app);
+    let sheet_id = get_sheet_id_from_store(&app)?;
     log::info!("get_sheet_id operation took {:?}", start.elapsed());
     Ok(sheet_id)
 }
 
 #[tauri::command]
-async fn set_demo_sheet_id(app: tauri::AppHandle) -> Result<(), ()> {
+async fn set_demo_sheet_id(app: tauri::AppHandle) -> Result<(), StorageError> {
     let start = Instant::now();
-    let store = app.store("store.json").expect("Failed to open store");
-    store.set("sheet-id", json!({ "value": DEMO_SHEET_ID }));
-    store.save().expect("Failed to save store");
+    save_to_store(&app, SHEET_ID_KEY, json!({ "value": DEMO_SHEET_ID }))?;
     log::info!("set_demo_sheet_id operation took {:?}", start.elapsed());
-    store.close_resource();
     Ok(())
 }
 
@@ -181,37 +601,71 @@ pub fn run() {
         )
         .plugin(tauri_plugin_opener::init())
         .invoke_handler(tauri::generate_handler![
+            list_games,
+            get_current_game,
+            select_game,
+            create_game,
             balances,
-            names,
+            get_players,
+            add_player,
+            rename_player,
+            retire_player,
             transactions,
+            transactions_page,
             remove_last_transaction,
+            redo_last_undo,
+            void_transaction,
+            amend_transaction,
             create_split,
             convert_split,
             get_valid_splits,
+            split_stats,
+            plan_settlement,
+            record_settlement,
+            get_activity_log,
+            classify_split,
+            get_suggested_award_table,
+            get_award_table,
+            set_award_table,
+            get_stake_policy,
+            set_stake_policy,
+            get_pot_policy,
+            set_pot_policy,
+            get_display_timezone,
+            set_display_timezone,
+            get_device_name,
+            set_device_name,
             set_sheet_id,
             get_sheet_id,
-            set_demo_sheet_id
+            set_demo_sheet_id,
+            get_backend_config,
+            set_backend_config
         ])
         .setup(|app| {
             let store = app.store("store.json")?;
             log::info!("Current store: {:?}", store.entries());
-            if store.get("sheet-id").is_none() {
-                log::info!("Setting sheet id to {}", DEMO_SHEET_ID);
-                store.set("sheet-id", json!({ "value": DEMO_SHEET_ID }));
-            } else {
-                log::info!(
-                    "Sheet id already set to {}",
-                    store
-                        .get("sheet-id")
-                        .unwrap()
-                        .get("value")
-                        .unwrap()
-                        .as_str()
-                        .unwrap()
-                );
+            match store.get(SHEET_ID_KEY) {
+                None => {
+                    log::info!("Setting sheet id to {}", DEMO_SHEET_ID);
+                    store.set(SHEET_ID_KEY, json!({ "value": DEMO_SHEET_ID }));
+                }
+                // Logged as saved, since a malformed entry mustn't stop the app starting
+                Some(sheet_id) => log::info!("Sheet id already set to {}", sheet_id),
             }
             store.save()?;
             store.close_resource();
+
+            let start = Instant::now();
+            let dao = get_backend_config_from_store(app.handle())
+                .and_then(|backend_config| {
+                    log::info!("Using backend {:?}", backend_config);
+                    tauri::async_runtime::block_on(config::create_dao(&backend_config))
+                })
+                .inspect_err(|e| log::error!("Failed to initialize backend: {}", e));
+            log::info!("DAO initialization took {:?}", start.elapsed());
+            let game = get_current_game_from_store(app.handle())?;
+            log::info!("Using game {}", game);
+            app.manage(DaoState::new(dao, game));
             Ok(())
         })
         .run(tauri::generate_context!())
@@ -0,0 +1,42 @@
+use crate::storage::{GameId, StorageDao, StorageError};
+use std::sync::{Arc, RwLock};
+
+/// The active storage backend and selected game, built once during setup and kept in
+/// Tauri managed state.
+///
+/// Commands clone the `Arc` out and release the lock before awaiting, so swapping the
+/// backend never blocks behind (or cancels) an in-flight request; that request simply
+/// finishes against the DAO it started with. The same goes for the selected game.
+///
+/// If the configured backend couldn't be started, the error is kept instead and every
+/// command fails with it until a working backend is configured, rather than quietly
+/// running on a ledger whose writes would go nowhere.
+pub struct DaoState {
+    dao: RwLock<Result<Arc<dyn StorageDao>, StorageError>>,
+    game: RwLock<GameId>,
+}
+
+impl DaoState {
+    pub fn new(dao: Result<Arc<dyn StorageDao>, StorageError>, game: GameId) -> Self {
+        Self {
+            dao: RwLock::new(dao),
+            game: RwLock::new(game),
+        }
+    }
+
+    pub fn get(&self) -> Result<Arc<dyn StorageDao>, StorageError> {
+        self.dao.read().unwrap().clone()
+    }
+
+    pub fn replace(&self, dao: Arc<dyn StorageDao>) {
+        *self.dao.write().unwrap() = Ok(dao);
+    }
+
+    pub fn game(&self) -> GameId {
+        self.game.read().unwrap().clone()
+    }
+
+    pub fn select_game(&self, game: GameId) {
+        *self.game.write().unwrap() = game;
+    }
+}
@@ -0,0 +1,64 @@
+//! Who money moves between. Transactions refer to accounts by id rather than by name,
+//! so players can be renamed and can't be mistaken for the pot whatever they're called.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// A player's permanent id within their game, such as `p3`. Ids are never reused.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(transparent)]
+pub struct PlayerId(pub String);
+
+impl fmt::Display for PlayerId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// One side of a transaction: the game's pot or one of its players. Serialized as
+/// `"pot"` or the player's id.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AccountId {
+    Pot,
+    Player(PlayerId),
+}
+
+const POT_ACCOUNT: &str = "pot";
+
+impl AccountId {
+    pub fn parse(value: &str) -> Self {
+        if value == POT_ACCOUNT {
+            AccountId::Pot
+        } else {
+            AccountId::Player(PlayerId(value.to_string()))
+        }
+    }
+
+    pub fn player(&self) -> Option<&PlayerId> {
+        match self {
+            AccountId::Pot => None,
+            AccountId::Player(player) => Some(player),
+        }
+    }
+}
+
+impl fmt::Display for AccountId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccountId::Pot => f.write_str(POT_ACCOUNT),
+            AccountId::Player(player) => f.write_str(&player.0),
+        }
+    }
+}
+
+impl Serialize for AccountId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for AccountId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(AccountId::parse(&String::deserialize(deserializer)?))
+    }
+}
@@ -0,0 +1,134 @@
+//! The activity log: an append-only record, kept with each game, of every change made to
+//! it, who made it and what it did to balances. It answers questions the ledger can't,
+//! such as who undid a conversion or when the stake was raised.
+
+use super::ledger::BalanceChange;
+use super::{AccountId, PlayerId, Roster, Split, TransactionId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// What a logged change did.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    CreateGame,
+    AddPlayer,
+    RenamePlayer,
+    RetirePlayer,
+    Split,
+    Conversion,
+    Settlement,
+    Undo,
+    Redo,
+    /// A past transaction taken back; see [`super::Correction::Void`].
+    Void,
+    /// A past transaction moved to another player or split; see
+    /// [`super::Correction::Amend`].
+    Amend,
+    SetAwardTable,
+    SetStakePolicy,
+    SetPotPolicy,
+    SetDisplayTimezone,
+}
+
+/// One entry in a game's activity log.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Activity {
+    /// Position in the game's log, counting from 1; assigned when the entry is logged.
+    pub id: u64,
+    pub at: DateTime<Utc>,
+    /// The name of the device that made the change, as set in its settings.
+    pub actor: String,
+    pub action: Action,
+    /// The transactions recorded, voided, restored or rewritten.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transactions: Vec<TransactionId>,
+    /// A short description in the game's terms at the time, e.g. `Ann 7-10` or the reason
+    /// given for an undo.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Every balance the change moved; empty for changes to players and settings.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub balances: Vec<BalanceChange>,
+}
+
+impl Activity {
+    /// An entry for `action` taken now by `actor`. Its id, transactions and balance
+    /// changes are filled in as it is logged.
+    pub fn new(actor: &str, action: Action, detail: Option<String>) -> Self {
+        Self {
+            id: 0,
+            at: Utc::now(),
+            actor: actor.to_string(),
+            action,
+            transactions: Vec::new(),
+            detail,
+            balances: Vec::new(),
+        }
+    }
+}
+
+/// Describes a split or conversion, e.g. `Ann 7-10`.
+pub fn describe_play(roster: &Roster, player: &PlayerId, split: Split) -> String {
+    format!(
+        "{} {}",
+        roster.name_of(&AccountId::Player(player.clone())),
+        split
+    )
+}
+
+/// Describes renaming `player`, e.g. `Ann → Annie`, from the roster before the rename.
+pub fn describe_rename(roster: &Roster, player: &PlayerId, new_name: &str) -> String {
+    format!(
+        "{} → {}",
+        roster.name_of(&AccountId::Player(player.clone())),
+        new_name
+    )
+}
+
+/// Which entries [`super::StorageDao::get_activity_log`] returns. Every condition that is
+/// set must hold; the default matches everything.
+#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ActivityFilter {
+    /// Only these actions, or any action when empty.
+    #[serde(default)]
+    pub actions: Vec<Action>,
+    #[serde(default)]
+    pub actor: Option<String>,
+    /// Only entries about this transaction, e.g. to see who undid it.
+    #[serde(default)]
+    pub transaction: Option<TransactionId>,
+    /// Only entries logged at or after this time.
+    #[serde(default)]
+    pub since: Option<DateTime<Utc>>,
+    /// Only entries logged before this time.
+    #[serde(default)]
+    pub until: Option<DateTime<Utc>>,
+    /// At most this many entries, the most recent.
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+impl ActivityFilter {
+    pub fn matches(&self, activity: &Activity) -> bool {
+        (self.actions.is_empty() || self.actions.contains(&activity.action))
+            && self
+                .actor
+                .as_ref()
+                .is_none_or(|actor| actor == &activity.actor)
+            && self
+                .transaction
+                .is_none_or(|id| activity.transactions.contains(&id))
+            && self.since.is_none_or(|since| activity.at >= since)
+            && self.until.is_none_or(|until| activity.at < until)
+    }
+
+    /// The matching entries of `newest_first`, up to the limit.
+    pub fn select(&self, newest_first: impl IntoIterator<Item = Activity>) -> Vec<Activity> {
+        newest_first
+            .into_iter()
+            .filter(|activity| self.matches(activity))
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
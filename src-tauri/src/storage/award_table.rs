@@ -0,0 +1,122 @@
+use super::split_rule::SplitRule;
+use super::{Split, StorageError, StorageResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// The table every game starts with until it is given its own.
+const STANDARD_PRESET: &str = include_str!("presets/standard_awards.json");
+
+/// Splits that pay the same share of the pot when converted: those listed explicitly
+/// plus any matching `rule`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AwardTier {
+    /// Percentage of the pot awarded, e.g. `30.0`.
+    pub percent: f64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub splits: Vec<Split>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule: Option<SplitRule>,
+}
+
+impl AwardTier {
+    pub fn matches(&self, split: Split) -> bool {
+        self.splits.contains(&split) || self.rule.as_ref().is_some_and(|rule| rule.matches(split))
+    }
+}
+
+/// A game's payout schedule for conversions. Stored with the game so it can change
+/// between seasons without a release. Tiers are checked in order and the first match
+/// wins; splits matching no tier can't be converted.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AwardTable {
+    pub name: String,
+    pub tiers: Vec<AwardTier>,
+}
+
+impl AwardTable {
+    /// The bundled preset: 10% for baby splits, 50% for the hardest leaves, 30% for any
+    /// other leave that isn't a cluster.
+    pub fn standard() -> Self {
+        serde_json::from_str(STANDARD_PRESET).expect("bundled award preset is valid")
+    }
+
+    /// Checks that every tier pays a share of the pot between 0 and 100%, has a valid
+    /// rule or at least one split, and that no split is listed twice.
+    pub fn validate(&self) -> StorageResult<()> {
+        if self.tiers.is_empty() {
+            return Err(StorageError::InvalidConfig(
+                "Award table has no tiers".to_string(),
+            ));
+        }
+        let mut seen = HashSet::new();
+        for tier in &self.tiers {
+            if !tier.percent.is_finite() || tier.percent <= 0.0 || tier.percent > 100.0 {
+                return Err(StorageError::InvalidConfig(format!(
+                    "Award of {}% is not between 0 and 100",
+                    tier.percent
+                )));
+            }
+            if tier.splits.is_empty() && tier.rule.is_none() {
+                return Err(StorageError::InvalidConfig(format!(
+                    "The {}% tier has no splits or rule",
+                    tier.percent
+                )));
+            }
+            if let Some(rule) = &tier.rule {
+                rule.validate()?;
+            }
+            if let Some(split) = tier.splits.iter().find(|split| !seen.insert(**split)) {
+                return Err(StorageError::InvalidConfig(format!(
+                    "{} is listed more than once",
+                    split
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn percent_for(&self, split: Split) -> Option<f64> {
+        self.tiers
+            .iter()
+            .find(|tier| tier.matches(split))
+            .map(|tier| tier.percent)
+    }
+
+    /// The award for every split that pays anything.
+    pub fn to_map(&self) -> HashMap<Split, f64> {
+        Split::all()
+            .filter_map(|split| Some((split, self.percent_for(split)?)))
+            .collect()
+    }
+}
+
+impl Default for AwardTable {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The hand-enumerated table the DynamoDB backend used to hardcode.
+    const LEGACY_TABLE: &str = include_str!("testdata/legacy_awards.json");
+
+    #[test]
+    fn standard_preset_reproduces_legacy_table() {
+        let legacy: AwardTable = serde_json::from_str(LEGACY_TABLE).unwrap();
+        let standard = AwardTable::standard();
+        standard.validate().unwrap();
+
+        for split in Split::all() {
+            assert_eq!(
+                standard.percent_for(split),
+                legacy.percent_for(split),
+                "award for {}",
+                split
+            );
+        }
+        assert_eq!(standard.to_map().len(), 459);
+    }
+}
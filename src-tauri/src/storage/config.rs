@@ -0,0 +1,152 @@
+use super::dynamodb_dao::DynamoDbDao;
+use super::memory_dao::MemoryDao;
+use super::{StorageDao, StorageError, StorageResult};
+use crate::secrets::AWS_REGION;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Which storage backend to use and how to reach it. Persisted in `store.json` under
+/// the `backend` key so devices can switch ledgers without a rebuild.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BackendConfig {
+    /// In-memory ledger seeded with demo data. When `file_path` is set the ledger is
+    /// loaded from and saved to that JSON file so it survives restarts.
+    Memory {
+        #[serde(default)]
+        file_path: Option<String>,
+    },
+    DynamoDb(DynamoDbConfig),
+    Sheets {
+        sheet_id: String,
+    },
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct DynamoDbConfig {
+    pub region: String,
+    pub games_table: String,
+    pub transactions_table: String,
+    /// Holds every game's activity log. Configs saved before the log existed use the
+    /// default table.
+    #[serde(default = "default_activity_table")]
+    pub activity_table: String,
+    /// Overrides the AWS endpoint, e.g. `http://localhost:8000` for DynamoDB Local.
+    #[serde(default)]
+    pub endpoint: Option<String>,
+}
+
+impl Default for DynamoDbConfig {
+    fn default() -> Self {
+        Self {
+            region: AWS_REGION.to_string(),
+            games_table: "split-happens-games".to_string(),
+            transactions_table: "split-happens-transactions".to_string(),
+            activity_table: default_activity_table(),
+            endpoint: None,
+        }
+    }
+}
+
+fn default_activity_table() -> String {
+    "split-happens-activity".to_string()
+}
+
+impl Default for BackendConfig {
+    fn default() -> Self {
+        BackendConfig::DynamoDb(DynamoDbConfig::default())
+    }
+}
+
+impl BackendConfig {
+    pub fn validate(&self) -> StorageResult<()> {
+        match self {
+            BackendConfig::Memory { file_path } => {
+                if let Some(path) = file_path {
+                    require_non_empty("file_path", path)?;
+                }
+            }
+            BackendConfig::DynamoDb(config) => {
+                require_non_empty("region", &config.region)?;
+                require_non_empty("games_table", &config.games_table)?;
+                require_non_empty("transactions_table", &config.transactions_table)?;
+                require_non_empty("activity_table", &config.activity_table)?;
+                if config.games_table == config.transactions_table
+                    || config.activity_table == config.games_table
+                    || config.activity_table == config.transactions_table
+                {
+                    return Err(StorageError::InvalidConfig(
+                        "games_table, transactions_table and activity_table must be different \
+                         tables"
+                            .to_string(),
+                    ));
+                }
+                if let Some(endpoint) = &config.endpoint {
+                    if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+                        return Err(StorageError::InvalidConfig(format!(
+                            "endpoint must be an http(s) URL, got {}",
+                            endpoint
+                        )));
+                    }
+                }
+            }
+            BackendConfig::Sheets { sheet_id } => {
+                require_non_empty("sheet_id", sheet_id)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn require_non_empty(field: &str, value: &str) -> StorageResult<()> {
+    if value.trim().is_empty() {
+        Err(StorageError::InvalidConfig(format!(
+            "{} must not be empty",
+            field
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/// Validates `config` and builds the DAO it describes.
+pub async fn create_dao(config: &BackendConfig) -> StorageResult<Arc<dyn StorageDao>> {
+    config.validate()?;
+    match config {
+        BackendConfig::Memory { file_path: None } => Ok(Arc::new(MemoryDao::new())),
+        BackendConfig::Memory {
+            file_path: Some(path),
+        } => Ok(Arc::new(MemoryDao::with_file(path.into())?)),
+        BackendConfig::DynamoDb(config) => {
+            let dao = DynamoDbDao::new(config).await;
+            dao.check_activity_table().await?;
+            Ok(Arc::new(dao))
+        }
+        // The sheets module is not compiled in (see storage/mod.rs), so refuse rather than
+        // quietly writing to a different ledger.
+        BackendConfig::Sheets { .. } => Err(StorageError::BackendUnavailable(
+            "Google Sheets storage is not available in this build".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    #[ignore = "needs DYNAMODB_TEST_ENDPOINT"]
+    async fn dynamodb_without_an_activity_table_is_unavailable() {
+        let endpoint = std::env::var("DYNAMODB_TEST_ENDPOINT")
+            .expect("DYNAMODB_TEST_ENDPOINT must point at DynamoDB to run this check");
+        let config = BackendConfig::DynamoDb(DynamoDbConfig {
+            activity_table: "no-such-activity-table".to_string(),
+            endpoint: Some(endpoint),
+            ..DynamoDbConfig::default()
+        });
+        assert!(matches!(
+            create_dao(&config).await,
+            Err(StorageError::BackendUnavailable(message)) if message.contains("no-such-activity-table")
+        ));
+    }
+}
@@ -0,0 +1,693 @@
+//! Behaviour every `StorageDao` must share, checked against each backend. `MemoryDao` is
+//! always tested. The DynamoDB checks are ignored by default; run them with
+//! `cargo test -- --ignored` and `DYNAMODB_TEST_ENDPOINT` pointing at DynamoDB Local or
+//! another stand-in, e.g. `http://localhost:8000`. Each check gets a fresh DAO from the
+//! backend's constructor and works in a game of its own.
+
+use super::activity::Action;
+use super::config::DynamoDbConfig;
+use super::correction::{Amendment, Correction};
+use super::dynamodb_dao::DynamoDbDao;
+use super::memory_dao::MemoryDao;
+use super::settlement;
+use super::{
+    AccountId, ActivityFilter, AwardTable, GameId, Money, NewGame, PlayerId, PotPolicy, Split,
+    StakePolicy, StorageDao, StorageError, TransactionId, TransactionKind, Void,
+};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+const STAKE: Money = Money::from_dollars(1);
+
+/// The device every check's changes are made from.
+const ACTOR: &str = "Lane 3";
+
+fn split(pins: &str) -> Split {
+    pins.parse().expect("test split is valid")
+}
+
+/// A new game with players Ann, Ben and Cat paying a flat stake.
+async fn new_game(dao: &dyn StorageDao, name: &str) -> (GameId, Vec<PlayerId>) {
+    let game = dao
+        .create_game(
+            NewGame {
+                name: name.to_string(),
+                players: vec!["Ann".to_string(), "Ben".to_string(), "Cat".to_string()],
+                stake: StakePolicy::flat(STAKE),
+                pot_policy: PotPolicy::default(),
+                award_table: AwardTable::default(),
+            },
+            ACTOR,
+        )
+        .await
+        .expect("create game");
+    let players = dao
+        .get_players(&game.id)
+        .await
+        .expect("get players")
+        .into_iter()
+        .map(|player| player.id)
+        .collect();
+    (game.id, players)
+}
+
+fn undo() -> Void {
+    Void::now(ACTOR.to_string(), Some("Wrong player".to_string()))
+}
+
+async fn balances(dao: &dyn StorageDao, game: &GameId) -> HashMap<AccountId, Money> {
+    dao.get_balances(game)
+        .await
+        .expect("get balances")
+        .into_iter()
+        .map(|balance| (balance.account, balance.amount))
+        .collect()
+}
+
+fn assert_sums_to_zero(balances: &HashMap<AccountId, Money>) {
+    assert_eq!(
+        balances.values().copied().sum::<Money>(),
+        Money::ZERO,
+        "balances {:?}",
+        balances
+    );
+}
+
+async fn new_game_starts_at_zero(dao: &dyn StorageDao) {
+    let (game, players) = new_game(dao, "Starts at zero").await;
+    let balances = balances(dao, &game).await;
+    assert_eq!(balances.len(), players.len() + 1);
+    assert!(balances.values().all(|amount| amount.is_zero()));
+    assert_eq!(
+        dao.get_award_table(&game).await.expect("get award table"),
+        AwardTable::standard()
+    );
+}
+
+async fn split_moves_stake_into_pot(dao: &dyn StorageDao) {
+    let (game, players) = new_game(dao, "Split moves stake").await;
+    let ann = AccountId::Player(players[0].clone());
+    dao.add_split(&game, &players[0], split("7-10"), ACTOR)
+        .await
+        .expect("add split");
+
+    let balances = balances(dao, &game).await;
+    assert_eq!(balances[&AccountId::Pot], STAKE);
+    assert_eq!(balances[&ann], -STAKE);
+    assert_sums_to_zero(&balances);
+
+    let recorded = dao
+        .get_last_n_transactions(&game, 1)
+        .await
+        .expect("read back");
+    let transaction = &recorded[0];
+    assert_eq!(transaction.kind, TransactionKind::Split);
+    assert_eq!(transaction.creditor, AccountId::Pot);
+    assert_eq!(transaction.debtor, ann);
+    assert_eq!(transaction.amount, STAKE);
+    assert_eq!(transaction.split, Some(split("7-10")));
+    assert_eq!(transaction.pot_amount, Money::ZERO);
+}
+
+async fn conversion_pays_award_percentage(dao: &dyn StorageDao) {
+    let (game, players) = new_game(dao, "Conversion pays").await;
+    for _ in 0..3 {
+        for player in &players {
+            dao.add_split(&game, player, split("4-6-7-10"), ACTOR)
+                .await
+                .expect("add split");
+        }
+    }
+    let before = balances(dao, &game).await;
+    let pot = before[&AccountId::Pot];
+    let percent = dao
+        .get_award_table(&game)
+        .await
+        .expect("get award table")
+        .percent_for(split("7-10"))
+        .expect("7-10 pays out");
+
+    dao.add_conversion(&game, &players[1], split("7-10"), ACTOR)
+        .await
+        .expect("add conversion");
+
+    let award = pot.percent(percent);
+    let after = balances(dao, &game).await;
+    let ben = AccountId::Player(players[1].clone());
+    assert_eq!(after[&AccountId::Pot], pot - award);
+    assert_eq!(after[&ben], before[&ben] + award);
+    assert_sums_to_zero(&after);
+
+    let recorded = dao
+        .get_last_n_transactions(&game, 1)
+        .await
+        .expect("read back");
+    let transaction = &recorded[0];
+    assert_eq!(transaction.kind, TransactionKind::Conversion);
+    assert_eq!(transaction.creditor, ben);
+    assert_eq!(transaction.debtor, AccountId::Pot);
+    assert_eq!(transaction.amount, award);
+    assert_eq!(transaction.pot_amount, pot);
+}
+
+async fn conversion_rejects_unpaid_split(dao: &dyn StorageDao) {
+    let (game, players) = new_game(dao, "Unpaid split").await;
+    // A cluster pays nothing in the standard table
+    let result = dao
+        .add_conversion(&game, &players[0], split("2-4-5-8"), ACTOR)
+        .await;
+    assert!(
+        matches!(result, Err(StorageError::InvalidSplit(_))),
+        "{:?}",
+        result
+    );
+}
+
+async fn undo_is_exact_inverse(dao: &dyn StorageDao) {
+    let (game, players) = new_game(dao, "Undo inverse").await;
+    dao.add_split(&game, &players[0], split("7-10"), ACTOR)
+        .await
+        .expect("add split");
+    dao.add_split(&game, &players[2], split("2-7"), ACTOR)
+        .await
+        .expect("add split");
+    let before = balances(dao, &game).await;
+
+    dao.add_conversion(&game, &players[0], split("7-10"), ACTOR)
+        .await
+        .expect("add conversion");
+    let undone = dao
+        .remove_last_transaction(&game, undo())
+        .await
+        .expect("undo conversion");
+    assert_eq!(undone.kind, TransactionKind::Conversion);
+    let void = undone.voided.expect("undone transaction is voided");
+    assert_eq!(void.by, "Lane 3");
+    assert_eq!(void.reason.as_deref(), Some("Wrong player"));
+    assert_eq!(balances(dao, &game).await, before);
+
+    dao.remove_last_transaction(&game, undo())
+        .await
+        .expect("undo split");
+    dao.remove_last_transaction(&game, undo())
+        .await
+        .expect("undo split");
+    assert!(balances(dao, &game)
+        .await
+        .values()
+        .all(|amount| amount.is_zero()));
+    let result = dao.remove_last_transaction(&game, undo()).await;
+    assert!(
+        matches!(result, Err(StorageError::NotFound(_))),
+        "{:?}",
+        result
+    );
+}
+
+async fn undone_transactions_stay_in_history(dao: &dyn StorageDao) {
+    let (game, players) = new_game(dao, "Undo keeps history").await;
+    dao.add_split(&game, &players[0], split("7-10"), ACTOR)
+        .await
+        .expect("add split");
+    dao.add_split(&game, &players[1], split("4-6"), ACTOR)
+        .await
+        .expect("add split");
+    dao.remove_last_transaction(&game, undo())
+        .await
+        .expect("undo split");
+
+    let history = dao.get_all_transactions(&game).await.expect("all");
+    assert_eq!(history.len(), 2);
+    assert!(!history[0].is_voided());
+    assert!(history[1].is_voided());
+
+    // The next undo skips the voided split, and new ids carry on after it
+    let undone = dao
+        .remove_last_transaction(&game, undo())
+        .await
+        .expect("undo split");
+    assert_eq!(undone.id, TransactionId(1));
+    dao.add_split(&game, &players[2], split("2-7"), ACTOR)
+        .await
+        .expect("add split");
+    let latest = dao.get_last_n_transactions(&game, 1).await.expect("last");
+    assert_eq!(latest[0].id, TransactionId(3));
+    assert_eq!(latest[0].pot_amount, Money::ZERO);
+}
+
+async fn redo_restores_last_undo(dao: &dyn StorageDao) {
+    let (game, players) = new_game(dao, "Redo").await;
+    dao.add_split(&game, &players[0], split("7-10"), ACTOR)
+        .await
+        .expect("add split");
+    dao.add_conversion(&game, &players[0], split("7-10"), ACTOR)
+        .await
+        .expect("add conversion");
+    let before = balances(dao, &game).await;
+
+    dao.remove_last_transaction(&game, undo())
+        .await
+        .expect("undo conversion");
+    dao.remove_last_transaction(&game, undo())
+        .await
+        .expect("undo split");
+    let redone = dao.redo_last_undo(&game, ACTOR).await.expect("redo split");
+    assert_eq!(redone.kind, TransactionKind::Split);
+    assert!(!redone.is_voided());
+    let redone = dao
+        .redo_last_undo(&game, ACTOR)
+        .await
+        .expect("redo conversion");
+    assert_eq!(redone.kind, TransactionKind::Conversion);
+    assert_eq!(balances(dao, &game).await, before);
+    assert!(dao
+        .get_all_transactions(&game)
+        .await
+        .expect("all")
+        .iter()
+        .all(|t| !t.is_voided()));
+    let result = dao.redo_last_undo(&game, ACTOR).await;
+    assert!(
+        matches!(result, Err(StorageError::NotFound(_))),
+        "{:?}",
+        result
+    );
+
+    // Recording something after an undo leaves nothing to redo
+    dao.remove_last_transaction(&game, undo())
+        .await
+        .expect("undo conversion");
+    dao.add_split(&game, &players[1], split("4-6"), ACTOR)
+        .await
+        .expect("add split");
+    let result = dao.redo_last_undo(&game, ACTOR).await;
+    assert!(
+        matches!(result, Err(StorageError::NotFound(_))),
+        "{:?}",
+        result
+    );
+}
+
+async fn concurrent_undos_each_void_a_transaction(dao: &dyn StorageDao) {
+    let (game, players) = new_game(dao, "Concurrent undo").await;
+    for pins in ["7-10", "4-6"] {
+        dao.add_split(&game, &players[0], split(pins), ACTOR)
+            .await
+            .expect("add split");
+    }
+
+    // Two lanes tapping undo together: the loser re-reads and undoes the one before
+    let (first, second) = tokio::join!(
+        dao.remove_last_transaction(&game, undo()),
+        dao.remove_last_transaction(&game, undo()),
+    );
+    let mut undone = [
+        first.expect("first undo").id.0,
+        second.expect("second undo").id.0,
+    ];
+    undone.sort_unstable();
+    assert_eq!(undone, [1, 2]);
+    assert!(balances(dao, &game)
+        .await
+        .values()
+        .all(|amount| amount.is_zero()));
+}
+
+async fn void_replays_later_conversions(dao: &dyn StorageDao) {
+    let (game, players) = new_game(dao, "Void replays").await;
+    for player in &players {
+        dao.add_split(&game, player, split("7-10"), ACTOR)
+            .await
+            .expect("add split");
+    }
+    dao.add_conversion(&game, &players[0], split("7-10"), ACTOR)
+        .await
+        .expect("add conversion");
+    let percent = dao
+        .get_award_table(&game)
+        .await
+        .expect("get award table")
+        .percent_for(split("7-10"))
+        .expect("7-10 pays out");
+
+    let correction = Correction::Void(undo());
+    let preview = dao
+        .preview_correction(&game, TransactionId(2), &correction)
+        .await
+        .expect("preview void");
+    let ids: Vec<u64> = preview.changed.iter().map(|t| t.id.0).collect();
+    // Cat's split saw a smaller pot, and Ann's conversion is paid from it
+    assert_eq!(ids, vec![2, 3, 4]);
+    assert_eq!(preview.changed[1].pot_amount, STAKE);
+    let award = (STAKE + STAKE).percent(percent);
+    assert_eq!(preview.changed[2].pot_amount, STAKE + STAKE);
+    assert_eq!(preview.changed[2].amount, award);
+    // Previewing writes nothing
+    let latest = dao.get_last_n_transactions(&game, 1).await.expect("last");
+    assert_eq!(latest[0].pot_amount, STAKE + STAKE + STAKE);
+
+    let committed = dao
+        .correct_transaction(&game, TransactionId(2), correction, ACTOR)
+        .await
+        .expect("void");
+    assert_eq!(committed.balances, preview.balances);
+    let after = balances(dao, &game).await;
+    for change in &committed.balances {
+        assert_eq!(after[&change.account], change.after);
+    }
+    assert_eq!(after[&AccountId::Pot], STAKE + STAKE - award);
+    assert_eq!(after[&AccountId::Player(players[1].clone())], Money::ZERO);
+    assert_sums_to_zero(&after);
+
+    let history = dao.get_all_transactions(&game).await.expect("all");
+    assert!(history[1].is_voided());
+    assert_eq!(history[3].amount, award);
+    let result = dao
+        .correct_transaction(&game, TransactionId(2), Correction::Void(undo()), ACTOR)
+        .await;
+    assert!(
+        matches!(result, Err(StorageError::InvalidConfig(_))),
+        "{:?}",
+        result
+    );
+}
+
+async fn amend_moves_transaction(dao: &dyn StorageDao) {
+    let (game, players) = new_game(dao, "Amend").await;
+    dao.add_split(&game, &players[0], split("7-10"), ACTOR)
+        .await
+        .expect("add split");
+    dao.add_split(&game, &players[1], split("4-6"), ACTOR)
+        .await
+        .expect("add split");
+    dao.add_conversion(&game, &players[0], split("7-10"), ACTOR)
+        .await
+        .expect("add conversion");
+    let before = balances(dao, &game).await;
+
+    // The conversion was really Cat's
+    let amendment = Correction::Amend(Amendment {
+        player: players[2].clone(),
+        split: split("7-10"),
+    });
+    let replay = dao
+        .correct_transaction(&game, TransactionId(3), amendment, ACTOR)
+        .await
+        .expect("amend conversion");
+    assert_eq!(replay.changed.len(), 1);
+    let amended = &replay.changed[0];
+    assert_eq!(amended.creditor, AccountId::Player(players[2].clone()));
+    let after = balances(dao, &game).await;
+    let award = amended.amount;
+    let ann = AccountId::Player(players[0].clone());
+    let cat = AccountId::Player(players[2].clone());
+    assert_eq!(after[&ann], before[&ann] - award);
+    assert_eq!(after[&cat], before[&cat] + award);
+    assert_eq!(after[&AccountId::Pot], before[&AccountId::Pot]);
+
+    let result = dao
+        .correct_transaction(
+            &game,
+            TransactionId(3),
+            Correction::Amend(Amendment {
+                player: players[2].clone(),
+                split: split("2-4-5-8"),
+            }),
+            ACTOR,
+        )
+        .await;
+    assert!(
+        matches!(result, Err(StorageError::InvalidSplit(_))),
+        "{:?}",
+        result
+    );
+    let result = dao
+        .correct_transaction(&game, TransactionId(99), Correction::Void(undo()), ACTOR)
+        .await;
+    assert!(
+        matches!(result, Err(StorageError::NotFound(_))),
+        "{:?}",
+        result
+    );
+}
+
+async fn balances_sum_to_zero(dao: &dyn StorageDao) {
+    let (game, players) = new_game(dao, "Sums to zero").await;
+    for (index, pins) in ["7-10", "4-6", "2-7", "3-10", "4-7-10"].iter().enumerate() {
+        dao.add_split(&game, &players[index % players.len()], split(pins), ACTOR)
+            .await
+            .expect("add split");
+        assert_sums_to_zero(&balances(dao, &game).await);
+    }
+    dao.add_conversion(&game, &players[2], split("4-7-10"), ACTOR)
+        .await
+        .expect("add conversion");
+    assert_sums_to_zero(&balances(dao, &game).await);
+
+    let plan = settlement::plan(&dao.get_balances(&game).await.expect("get balances"), false);
+    dao.record_settlement(&game, plan.payments, ACTOR)
+        .await
+        .expect("record settlement");
+    let settled = balances(dao, &game).await;
+    assert!(
+        settled.values().all(|amount| amount.is_zero()),
+        "{:?}",
+        settled
+    );
+}
+
+async fn recent_transactions_are_oldest_first(dao: &dyn StorageDao) {
+    let (game, players) = new_game(dao, "Ordering").await;
+    for player in players.iter().chain(&players) {
+        dao.add_split(&game, player, split("7-10"), ACTOR)
+            .await
+            .expect("add split");
+    }
+    let ids = |transactions: &[super::Transaction]| -> Vec<u64> {
+        transactions.iter().map(|t| t.id.0).collect()
+    };
+
+    let recent = dao.get_last_n_transactions(&game, 3).await.expect("last 3");
+    assert_eq!(ids(&recent), vec![4, 5, 6]);
+    assert!(recent.windows(2).all(|pair| pair[0].time <= pair[1].time));
+
+    let newest = dao
+        .get_transactions_page(&game, None, 4)
+        .await
+        .expect("newest page");
+    assert_eq!(ids(&newest.transactions), vec![3, 4, 5, 6]);
+    assert_eq!(newest.next_before, Some(TransactionId(3)));
+    let oldest = dao
+        .get_transactions_page(&game, newest.next_before, 4)
+        .await
+        .expect("oldest page");
+    assert_eq!(ids(&oldest.transactions), vec![1, 2]);
+    assert_eq!(oldest.next_before, None);
+
+    let all = dao.get_all_transactions(&game).await.expect("all");
+    assert_eq!(ids(&all), vec![1, 2, 3, 4, 5, 6]);
+
+    // Out-of-range limits are clamped rather than failing or returning nothing
+    let smallest = dao
+        .get_transactions_page(&game, None, 0)
+        .await
+        .expect("smallest page");
+    assert_eq!(ids(&smallest.transactions), vec![6]);
+    assert_eq!(smallest.next_before, Some(TransactionId(6)));
+    let largest = dao
+        .get_transactions_page(&game, None, usize::MAX)
+        .await
+        .expect("largest page");
+    assert_eq!(ids(&largest.transactions), vec![1, 2, 3, 4, 5, 6]);
+    assert_eq!(largest.next_before, None);
+    let none = dao.get_last_n_transactions(&game, 0).await.expect("last 0");
+    assert!(none.is_empty());
+}
+
+async fn activity_log_records_every_change(dao: &dyn StorageDao) {
+    let (game, players) = new_game(dao, "Activity").await;
+    dao.add_split(&game, &players[0], split("7-10"), ACTOR)
+        .await
+        .expect("add split");
+    dao.add_conversion(&game, &players[0], split("7-10"), ACTOR)
+        .await
+        .expect("add conversion");
+    let converted = balances(dao, &game).await;
+    dao.remove_last_transaction(&game, undo())
+        .await
+        .expect("undo conversion");
+    dao.redo_last_undo(&game, "Lane 5")
+        .await
+        .expect("redo conversion");
+    dao.set_display_timezone(&game, "Europe/London".to_string(), ACTOR)
+        .await
+        .expect("set time zone");
+
+    let log = dao
+        .get_activity_log(&game, &ActivityFilter::default())
+        .await
+        .expect("get activity log");
+    let actions: Vec<Action> = log.iter().map(|activity| activity.action).collect();
+    assert_eq!(
+        actions,
+        vec![
+            Action::SetDisplayTimezone,
+            Action::Redo,
+            Action::Undo,
+            Action::Conversion,
+            Action::Split,
+            Action::CreateGame,
+        ]
+    );
+    let ids: Vec<u64> = log.iter().map(|activity| activity.id).collect();
+    assert_eq!(ids, vec![6, 5, 4, 3, 2, 1]);
+    assert!(log[0].balances.is_empty());
+
+    // Who undid the conversion, and what it did to balances
+    let undos = dao
+        .get_activity_log(
+            &game,
+            &ActivityFilter {
+                actions: vec![Action::Undo],
+                transaction: Some(TransactionId(2)),
+                ..ActivityFilter::default()
+            },
+        )
+        .await
+        .expect("get undos");
+    assert_eq!(undos.len(), 1);
+    assert_eq!(undos[0].actor, ACTOR);
+    assert_eq!(undos[0].detail.as_deref(), Some("Wrong player"));
+    let ann = AccountId::Player(players[0].clone());
+    let ann_change = undos[0]
+        .balances
+        .iter()
+        .find(|change| change.account == ann)
+        .expect("undo moved Ann's balance");
+    assert_eq!(ann_change.before, converted[&ann]);
+    assert_eq!(
+        undos[0]
+            .balances
+            .iter()
+            .map(|change| change.after - change.before)
+            .sum::<Money>(),
+        Money::ZERO
+    );
+
+    let redos = dao
+        .get_activity_log(
+            &game,
+            &ActivityFilter {
+                actor: Some("Lane 5".to_string()),
+                ..ActivityFilter::default()
+            },
+        )
+        .await
+        .expect("get redos");
+    assert_eq!(redos.len(), 1);
+    assert_eq!(redos[0].action, Action::Redo);
+    let latest = dao
+        .get_activity_log(
+            &game,
+            &ActivityFilter {
+                limit: Some(2),
+                ..ActivityFilter::default()
+            },
+        )
+        .await
+        .expect("get latest");
+    assert_eq!(latest, log[..2]);
+}
+
+async fn unknown_game_is_not_found(dao: &dyn StorageDao) {
+    let game = GameId("no-such-game".to_string());
+    let result = dao.get_balances(&game).await;
+    assert!(
+        matches!(result, Err(StorageError::NotFound(_))),
+        "{:?}",
+        result
+    );
+    let result = dao
+        .get_activity_log(&game, &ActivityFilter::default())
+        .await;
+    assert!(
+        matches!(result, Err(StorageError::NotFound(_))),
+        "{:?}",
+        result
+    );
+}
+
+async fn memory() -> MemoryDao {
+    MemoryDao::new()
+}
+
+/// A DAO on fresh tables at `DYNAMODB_TEST_ENDPOINT`.
+async fn dynamodb() -> DynamoDbDao {
+    static NEXT_TABLES: AtomicU64 = AtomicU64::new(0);
+    let endpoint = std::env::var("DYNAMODB_TEST_ENDPOINT")
+        .expect("DYNAMODB_TEST_ENDPOINT must point at DynamoDB to run these checks");
+    let suffix = format!(
+        "{}-{}",
+        std::process::id(),
+        NEXT_TABLES.fetch_add(1, Ordering::Relaxed)
+    );
+    let dao = DynamoDbDao::new(&DynamoDbConfig {
+        games_table: format!("conformance-games-{}", suffix),
+        transactions_table: format!("conformance-transactions-{}", suffix),
+        activity_table: format!("conformance-activity-{}", suffix),
+        endpoint: Some(endpoint),
+        ..DynamoDbConfig::default()
+    })
+    .await;
+    dao.create_tables().await.expect("create tables");
+    dao
+}
+
+/// A test running `$check` against a DAO from `$make`, with the attributes in braces.
+macro_rules! conformance_test {
+    ({ $(#[$attr:meta])* }, $make:path, $check:ident) => {
+        #[tokio::test]
+        $(#[$attr])*
+        async fn $check() {
+            super::$check(&$make().await).await;
+        }
+    };
+}
+
+/// One test per check for the backend built by `$make`.
+macro_rules! conformance_tests {
+    ($backend:ident, $make:path, $attrs:tt, [$($check:ident),* $(,)?]) => {
+        mod $backend {
+            $(conformance_test!($attrs, $make, $check);)*
+        }
+    };
+}
+
+macro_rules! all_backends {
+    ($($check:ident),* $(,)?) => {
+        conformance_tests!(memory, super::memory, {}, [$($check),*]);
+        conformance_tests!(
+            dynamodb,
+            super::dynamodb,
+            { #[ignore = "needs DYNAMODB_TEST_ENDPOINT"] },
+            [$($check),*]
+        );
+    };
+}
+
+all_backends!(
+    new_game_starts_at_zero,
+    split_moves_stake_into_pot,
+    conversion_pays_award_percentage,
+    conversion_rejects_unpaid_split,
+    undo_is_exact_inverse,
+    undone_transactions_stay_in_history,
+    redo_restores_last_undo,
+    concurrent_undos_each_void_a_transaction,
+    void_replays_later_conversions,
+    amend_moves_transaction,
+    balances_sum_to_zero,
+    recent_transactions_are_oldest_first,
+    activity_log_records_every_change,
+    unknown_game_is_not_found,
+);
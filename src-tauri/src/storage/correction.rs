@@ -0,0 +1,213 @@
+//! Fixing a transaction further back than the last one. Conversion awards depend on the
+//! pot at the time, so the ledger is replayed from the corrected transaction and every
+//! later award is worked out again from the corrected pot.
+
+use super::activity::{self, Action, Activity};
+use super::ledger::{self, BalanceChange};
+use super::{
+    AccountId, AwardTable, Money, PlayerId, PotPolicy, Roster, Split, StakePolicy, StorageError,
+    StorageResult, Transaction, TransactionId, TransactionKind, Void,
+};
+use serde::Serialize;
+
+#[derive(Debug, Clone)]
+pub enum Correction {
+    /// Takes the transaction back as if it had been undone.
+    Void(Void),
+    /// Records a split or conversion against a different player or split.
+    Amend(Amendment),
+}
+
+#[derive(Debug, Clone)]
+pub struct Amendment {
+    pub player: PlayerId,
+    pub split: Split,
+}
+
+impl Correction {
+    /// The activity log entry for `actor` making this correction.
+    pub fn activity(&self, actor: &str, roster: &Roster) -> Activity {
+        match self {
+            Correction::Void(void) => Activity::new(actor, Action::Void, void.reason.clone()),
+            Correction::Amend(amendment) => Activity::new(
+                actor,
+                Action::Amend,
+                Some(activity::describe_play(
+                    roster,
+                    &amendment.player,
+                    amendment.split,
+                )),
+            ),
+        }
+    }
+}
+
+/// The game settings a replay prices transactions with.
+pub struct Rules<'a> {
+    pub roster: &'a Roster,
+    pub stake: &'a StakePolicy,
+    pub pot_policy: &'a PotPolicy,
+    pub award_table: &'a AwardTable,
+}
+
+/// The outcome of a correction, as previewed or as committed.
+#[derive(Debug, Clone, Serialize)]
+pub struct Replay {
+    /// The corrected transaction, then every later one whose pot or award changed, as
+    /// they are to be stored.
+    pub changed: Vec<Transaction>,
+    /// Every account whose balance changes, ordered by name.
+    pub balances: Vec<BalanceChange>,
+}
+
+impl Replay {
+    /// How much each account's balance moves.
+    pub fn deltas(&self) -> impl Iterator<Item = (&AccountId, Money)> {
+        self.balances
+            .iter()
+            .map(|change| (&change.account, change.after - change.before))
+    }
+}
+
+/// Applies `correction` to transaction `id` of `history` (the whole game, oldest first)
+/// and replays everything after it.
+///
+/// Later conversions that now see a different pot are paid from it at the percentage they
+/// recorded (the award table's percentage if they predate recorded awards) under the
+/// game's current pot policy. A conversion amended to another split is paid that split's
+/// percentage. Stakes are kept as recorded, except that a split moved to another player
+/// is charged that player's stake.
+pub fn replay(
+    history: &[Transaction],
+    id: TransactionId,
+    correction: &Correction,
+    rules: &Rules,
+) -> StorageResult<Replay> {
+    let index = history
+        .iter()
+        .position(|t| t.id == id)
+        .ok_or_else(|| StorageError::NotFound(format!("No transaction #{}", id.0)))?;
+    let (earlier, later) = history.split_at(index);
+    let corrected = correct(&later[0], correction, earlier, rules)?;
+
+    let accounts = rules.roster.active_accounts();
+    let mut balances = ledger::fold(accounts.clone(), earlier);
+    let mut changed = Vec::new();
+    let mut replayed = Vec::with_capacity(later.len());
+    for (position, original) in later.iter().enumerate() {
+        let mut transaction = if position == 0 {
+            corrected.clone()
+        } else {
+            original.clone()
+        };
+        let pot = ledger::pot_balance(&balances);
+        if !transaction.is_voided() {
+            // Untouched transactions that saw the same pot stand as recorded
+            if position == 0 || pot != original.pot_amount {
+                reprice(&mut transaction, pot, rules);
+            }
+            ledger::apply(&mut balances, &transaction);
+        }
+        if position == 0
+            || transaction.pot_amount != original.pot_amount
+            || transaction.amount != original.amount
+        {
+            changed.push(transaction.clone());
+        }
+        replayed.push(transaction);
+    }
+
+    let before = ledger::fold(accounts.clone(), history);
+    let after = ledger::fold(accounts, earlier.iter().chain(&replayed));
+    Ok(Replay {
+        changed,
+        balances: ledger::changes(&before, &after, rules.roster),
+    })
+}
+
+/// The transaction with `correction` applied, before it is repriced.
+fn correct(
+    transaction: &Transaction,
+    correction: &Correction,
+    earlier: &[Transaction],
+    rules: &Rules,
+) -> StorageResult<Transaction> {
+    let mut corrected = transaction.clone();
+    match correction {
+        Correction::Void(void) => {
+            if transaction.is_voided() {
+                return Err(StorageError::InvalidConfig(format!(
+                    "Transaction #{} is already voided",
+                    transaction.id.0
+                )));
+            }
+            corrected.voided = Some(void.clone());
+        }
+        Correction::Amend(amendment) => {
+            if transaction.is_voided() {
+                return Err(StorageError::InvalidConfig(format!(
+                    "Transaction #{} is voided; redo it before amending it",
+                    transaction.id.0
+                )));
+            }
+            // Players who have since retired can still be corrected into the past
+            rules.roster.player(&amendment.player)?;
+            let player = AccountId::Player(amendment.player.clone());
+            match transaction.kind {
+                TransactionKind::Split => {
+                    if transaction.player() != Some(&amendment.player) {
+                        let (amount, stake) = rules.stake.stake_for(
+                            &amendment.player,
+                            transaction.time,
+                            earlier.iter().rev(),
+                        );
+                        corrected.amount = amount;
+                        corrected.stake = Some(stake);
+                    }
+                    corrected.debtor = player;
+                }
+                TransactionKind::Conversion => {
+                    if transaction.split != Some(amendment.split) {
+                        if rules.award_table.percent_for(amendment.split).is_none() {
+                            return Err(StorageError::InvalidSplit(amendment.split.to_string()));
+                        }
+                        // Without a recorded award, the replay pays the new split's percentage
+                        corrected.award = None;
+                    }
+                    corrected.creditor = player;
+                }
+                _ => {
+                    return Err(StorageError::InvalidConfig(
+                        "Only splits and conversions can be amended".to_string(),
+                    ));
+                }
+            }
+            corrected.split = Some(amendment.split);
+        }
+    }
+    Ok(corrected)
+}
+
+/// Records the pot `transaction` was made against and, for a conversion, pays its award
+/// from that pot.
+fn reprice(transaction: &mut Transaction, pot: Money, rules: &Rules) {
+    transaction.pot_amount = pot;
+    if transaction.kind != TransactionKind::Conversion {
+        return;
+    }
+    let percent = transaction
+        .award
+        .as_ref()
+        .map(|award| award.percent)
+        .or_else(|| {
+            transaction
+                .split
+                .and_then(|split| rules.award_table.percent_for(split))
+        });
+    // A conversion whose split the table no longer pays keeps its amount
+    if let Some(percent) = percent {
+        let (amount, award) = rules.pot_policy.award(pot, percent);
+        transaction.amount = amount;
+        transaction.award = Some(award);
+    }
+}
@@ -1,37 +1,427 @@
-use super::{Balance, StorageDao, Transaction};
-use crate::secrets::{AWS_ACCESS_KEY_ID, AWS_REGION, AWS_SECRET_ACCESS_KEY};
+use super::activity::{self, Action};
+use super::config::DynamoDbConfig;
+use super::correction;
+use super::ledger;
+use super::settlement;
+use super::stake;
+use super::timestamp;
+use super::{
+    page_limit, AccountId, Activity, ActivityFilter, AwardTable, Balance, Correction, GameId,
+    GameSummary, Money, NewGame, Payment, Player, PlayerId, PotPolicy, Replay, Roster, Split,
+    StakePolicy, StorageDao, StorageError, StorageResult, StoredTransaction, Transaction,
+    TransactionId, TransactionKind, TransactionPage, Void,
+};
+use crate::secrets::{AWS_ACCESS_KEY_ID, AWS_SECRET_ACCESS_KEY};
 use async_trait::async_trait;
 use aws_config::{BehaviorVersion, Region};
-use aws_sdk_dynamodb::{types::AttributeValue, Client};
+use aws_sdk_dynamodb::{
+    error::ProvideErrorMetadata,
+    types::{builders::UpdateBuilder, AttributeValue, Put, TransactWriteItem, Update},
+    Client,
+};
 use aws_smithy_runtime::client::http::hyper_014::HyperClientBuilder;
 use chrono::{DateTime, Utc};
 use hyper_rustls::HttpsConnectorBuilder;
-use std::collections::HashMap;
-use std::time::SystemTime;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// How many times a write is attempted before giving up on a conflicting writer.
+const MAX_WRITE_ATTEMPTS: u32 = 5;
+
+/// DynamoDB's limit on the writes in one `TransactWriteItems` call.
+const MAX_TRANSACT_ITEMS: usize = 100;
+
+/// Page size when reading back through tonight's transactions to price a stake.
+const RECENT_PAGE_SIZE: usize = 50;
+
+/// Page size when reading back through a game's activity log.
+const ACTIVITY_PAGE_SIZE: i32 = 100;
+
+/// The parts of the game record a write depends on.
+struct GameState {
+    balances: HashMap<AccountId, Money>,
+    /// `None` until the game records its first transaction.
+    next_transaction_num: Option<u64>,
+    stake: StakePolicy,
+    pot_policy: PotPolicy,
+    roster: Roster,
+    /// Bumped by every roster write, which is conditioned on it; 0 before the first.
+    roster_version: u64,
+    /// Bumped by every undo, redo and correction; like the counter, every write to the
+    /// balances is conditioned on it. 0 before the first.
+    ledger_version: u64,
+    /// How many entries the activity log holds; each write logs the next one.
+    activity_count: u64,
+}
+
+impl GameState {
+    fn next_id(&self) -> TransactionId {
+        TransactionId(self.next_transaction_num.unwrap_or(1))
+    }
+
+    fn pot_balance(&self) -> Money {
+        ledger::pot_balance(&self.balances)
+    }
+}
+
+/// Reads epoch millis, or upgrades a legacy string timestamp.
+fn parse_time(value: &AttributeValue) -> Option<DateTime<Utc>> {
+    match value {
+        AttributeValue::N(millis) => DateTime::from_timestamp_millis(millis.parse().ok()?),
+        AttributeValue::S(text) => timestamp::parse(text),
+        _ => None,
+    }
+}
+
+fn balances_to_attribute(balances: HashMap<AccountId, Money>) -> AttributeValue {
+    AttributeValue::M(
+        balances
+            .into_iter()
+            .map(|(account, amount)| (account.to_string(), AttributeValue::N(amount.to_string())))
+            .collect(),
+    )
+}
+
+/// Reads the `balances` map, keyed by account id (or by name before migration).
+fn parse_balances(
+    game: &GameId,
+    item: &HashMap<String, AttributeValue>,
+) -> StorageResult<HashMap<String, Money>> {
+    match item.get("balances") {
+        Some(AttributeValue::M(balances_map)) => balances_map
+            .iter()
+            .map(|(key, value)| {
+                value
+                    .as_n()
+                    .ok()
+                    .and_then(|amount_str| Money::parse(amount_str))
+                    .map(|amount| (key.clone(), amount))
+                    .ok_or_else(|| {
+                        StorageError::CorruptRecord(format!("Unreadable balance for {}", key))
+                    })
+            })
+            .collect(),
+        // A freshly created game may not have any balances yet
+        None => Ok(HashMap::new()),
+        Some(_) => Err(StorageError::CorruptRecord(format!(
+            "Balances of game {} are not a map",
+            game
+        ))),
+    }
+}
+
+/// Stores a JSON document as native DynamoDB maps, lists, strings and numbers so that
+/// nested settings like the award table stay readable in the console.
+fn json_to_attribute(value: serde_json::Value) -> AttributeValue {
+    use serde_json::Value;
+    match value {
+        Value::Null => AttributeValue::Null(true),
+        Value::Bool(b) => AttributeValue::Bool(b),
+        Value::Number(n) => AttributeValue::N(n.to_string()),
+        Value::String(s) => AttributeValue::S(s),
+        Value::Array(items) => {
+            AttributeValue::L(items.into_iter().map(json_to_attribute).collect())
+        }
+        Value::Object(fields) => AttributeValue::M(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key, json_to_attribute(value)))
+                .collect(),
+        ),
+    }
+}
+
+fn attribute_to_json(value: &AttributeValue) -> Option<serde_json::Value> {
+    use serde_json::Value;
+    Some(match value {
+        AttributeValue::Null(_) => Value::Null,
+        AttributeValue::Bool(b) => Value::Bool(*b),
+        AttributeValue::N(n) => serde_json::from_str(n).ok()?,
+        AttributeValue::S(s) => Value::String(s.clone()),
+        AttributeValue::L(items) => {
+            Value::Array(items.iter().map(attribute_to_json).collect::<Option<_>>()?)
+        }
+        AttributeValue::M(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, value)| Some((key.clone(), attribute_to_json(value)?)))
+                .collect::<Option<_>>()?,
+        ),
+        _ => return None,
+    })
+}
+
+fn string_set(item: &HashMap<String, AttributeValue>, key: &str) -> Option<Option<Vec<String>>> {
+    match item.get(key) {
+        Some(AttributeValue::Ss(values)) => Some(Some(values.clone())),
+        Some(_) => None,
+        None => Some(None),
+    }
+}
+
+/// The name-based roster of a game written before players had ids: the `players`,
+/// `retired` and `aliases` attributes, any of which may be missing.
+struct LegacyRoster {
+    players: Vec<String>,
+    retired: BTreeSet<String>,
+    aliases: BTreeMap<String, String>,
+}
+
+fn parse_legacy_roster(
+    game: &GameId,
+    item: &HashMap<String, AttributeValue>,
+) -> StorageResult<LegacyRoster> {
+    let corrupt =
+        |what: &str| StorageError::CorruptRecord(format!("Unreadable {} in game {}", what, game));
+    let players = string_set(item, "players")
+        .ok_or_else(|| corrupt("players"))?
+        .unwrap_or_default();
+    let retired = string_set(item, "retired")
+        .ok_or_else(|| corrupt("retired players"))?
+        .unwrap_or_default();
+    let aliases = match item.get("aliases") {
+        Some(AttributeValue::M(aliases)) => aliases
+            .iter()
+            .map(|(former, current)| Some((former.clone(), current.as_s().ok()?.clone())))
+            .collect::<Option<_>>()
+            .ok_or_else(|| corrupt("aliases"))?,
+        Some(_) => return Err(corrupt("aliases")),
+        None => Default::default(),
+    };
+    Ok(LegacyRoster {
+        players,
+        retired: retired.into_iter().collect(),
+        aliases,
+    })
+}
+
+fn roster_to_attribute(roster: &Roster) -> AttributeValue {
+    json_to_attribute(serde_json::to_value(roster).expect("roster serializes"))
+}
+
+fn parse_roster(game: &GameId, item: &HashMap<String, AttributeValue>) -> StorageResult<Roster> {
+    item.get("roster")
+        .and_then(attribute_to_json)
+        .and_then(|json| serde_json::from_value(json).ok())
+        .ok_or_else(|| StorageError::CorruptRecord(format!("Unreadable roster in game {}", game)))
+}
+
+/// Reads a counter such as `roster_version`, which is 0 until first written.
+fn parse_version(
+    game: &GameId,
+    item: &HashMap<String, AttributeValue>,
+    attribute: &str,
+) -> StorageResult<u64> {
+    match item.get(attribute) {
+        Some(value) => value
+            .as_n()
+            .ok()
+            .and_then(|version| version.parse().ok())
+            .ok_or_else(|| {
+                StorageError::CorruptRecord(format!("Unreadable {} in game {}", attribute, game))
+            }),
+        None => Ok(0),
+    }
+}
+
+fn award_table_to_attribute(table: &AwardTable) -> AttributeValue {
+    json_to_attribute(serde_json::to_value(table).expect("award table serializes"))
+}
+
+fn parse_award_table(value: &AttributeValue) -> Option<AwardTable> {
+    serde_json::from_value(attribute_to_json(value)?).ok()
+}
+
+fn stake_policy_to_attribute(policy: &StakePolicy) -> AttributeValue {
+    json_to_attribute(serde_json::to_value(policy).expect("stake policy serializes"))
+}
+
+fn pot_policy_to_attribute(policy: &PotPolicy) -> AttributeValue {
+    json_to_attribute(serde_json::to_value(policy).expect("pot policy serializes"))
+}
+
+/// Reads the `pot_policy` attribute; games without one have no limits.
+fn parse_pot_policy(
+    game: &GameId,
+    item: &HashMap<String, AttributeValue>,
+) -> StorageResult<PotPolicy> {
+    match item.get("pot_policy") {
+        Some(value) => attribute_to_json(value)
+            .and_then(|json| serde_json::from_value(json).ok())
+            .ok_or_else(|| {
+                StorageError::CorruptRecord(format!("Unreadable pot_policy in game {}", game))
+            }),
+        None => Ok(PotPolicy::default()),
+    }
+}
+
+/// Reads the `stake` attribute: a policy map, or a bare amount on games created before
+/// stakes could vary. Games older still pay the default.
+fn parse_stake_policy(
+    game: &GameId,
+    item: &HashMap<String, AttributeValue>,
+) -> StorageResult<StakePolicy> {
+    let policy = match item.get("stake") {
+        Some(AttributeValue::N(amount)) => Money::parse(amount).map(StakePolicy::flat),
+        Some(value) => attribute_to_json(value).and_then(|json| serde_json::from_value(json).ok()),
+        None => Some(StakePolicy::default()),
+    };
+    policy.ok_or_else(|| StorageError::CorruptRecord(format!("Unreadable stake in game {}", game)))
+}
+
+/// Conditions an update of the game's balances on nothing having been recorded, undone,
+/// redone or corrected since `game` was read.
+fn ledger_unchanged(update: UpdateBuilder, game: &GameState) -> UpdateBuilder {
+    let (counter, update) = match game.next_transaction_num {
+        Some(expected) => (
+            "next_transaction_num = :expected",
+            update
+                .expression_attribute_values(":expected", AttributeValue::N(expected.to_string())),
+        ),
+        None => (
+            "attribute_exists(game_id) AND attribute_not_exists(next_transaction_num)",
+            update,
+        ),
+    };
+    let (version, update) = match game.ledger_version {
+        0 => ("attribute_not_exists(ledger_version)", update),
+        expected => (
+            "ledger_version = :expected_ledger_version",
+            update.expression_attribute_values(
+                ":expected_ledger_version",
+                AttributeValue::N(expected.to_string()),
+            ),
+        ),
+    };
+    update.condition_expression(format!("{} AND {}", counter, version))
+}
+
+/// Sets `:activity_count` for an update that logs the entry after the `logged` ones.
+fn count_activity(update: UpdateBuilder, logged: u64) -> UpdateBuilder {
+    update.expression_attribute_values(
+        ":activity_count",
+        AttributeValue::N((logged + 1).to_string()),
+    )
+}
+
+fn activity_to_item(game: &GameId, activity: &Activity) -> HashMap<String, AttributeValue> {
+    let mut item = HashMap::new();
+    item.insert("game_id".to_string(), AttributeValue::S(game.0.clone()));
+    item.insert(
+        "activity_num".to_string(),
+        AttributeValue::N(activity.id.to_string()),
+    );
+    item.insert(
+        "at".to_string(),
+        AttributeValue::N(activity.at.timestamp_millis().to_string()),
+    );
+    item.insert(
+        "actor".to_string(),
+        AttributeValue::S(activity.actor.clone()),
+    );
+    item.insert(
+        "action".to_string(),
+        json_to_attribute(serde_json::to_value(activity.action).expect("action serializes")),
+    );
+    if !activity.transactions.is_empty() {
+        item.insert(
+            "transactions".to_string(),
+            AttributeValue::L(
+                activity
+                    .transactions
+                    .iter()
+                    .map(|id| AttributeValue::N(id.0.to_string()))
+                    .collect(),
+            ),
+        );
+    }
+    if let Some(detail) = &activity.detail {
+        item.insert("detail".to_string(), AttributeValue::S(detail.clone()));
+    }
+    if !activity.balances.is_empty() {
+        item.insert(
+            "balances".to_string(),
+            json_to_attribute(
+                serde_json::to_value(&activity.balances).expect("balance changes serialize"),
+            ),
+        );
+    }
+    item
+}
+
+fn parse_activity(item: &HashMap<String, AttributeValue>) -> Option<Activity> {
+    Some(Activity {
+        id: item.get("activity_num")?.as_n().ok()?.parse().ok()?,
+        at: parse_time(item.get("at")?)?,
+        actor: item.get("actor")?.as_s().ok()?.clone(),
+        action: serde_json::from_value(attribute_to_json(item.get("action")?)?).ok()?,
+        transactions: match item.get("transactions") {
+            Some(ids) => ids
+                .as_l()
+                .ok()?
+                .iter()
+                .map(|id| Some(TransactionId(id.as_n().ok()?.parse().ok()?)))
+                .collect::<Option<_>>()?,
+            None => Vec::new(),
+        },
+        detail: match item.get("detail") {
+            Some(detail) => Some(detail.as_s().ok()?.clone()),
+            None => None,
+        },
+        balances: match item.get("balances") {
+            Some(balances) => serde_json::from_value(attribute_to_json(balances)?).ok()?,
+            None => Vec::new(),
+        },
+    })
+}
+
+impl From<aws_sdk_dynamodb::Error> for StorageError {
+    fn from(e: aws_sdk_dynamodb::Error) -> Self {
+        use aws_sdk_dynamodb::Error;
+        let message = e.message().unwrap_or("no details").to_string();
+        match e {
+            Error::ConditionalCheckFailedException(_)
+            | Error::TransactionCanceledException(_)
+            | Error::TransactionConflictException(_) => StorageError::Conflict(message),
+            // A missing table, not a missing game; those are found by reading the item
+            Error::ResourceNotFoundException(_) => {
+                StorageError::BackendUnavailable(format!("DynamoDB table not found: {}", message))
+            }
+            _ => match e.code() {
+                Some("AccessDeniedException")
+                | Some("UnrecognizedClientException")
+                | Some("InvalidSignatureException")
+                | Some("ExpiredTokenException") => StorageError::Unauthorized(message),
+                _ => StorageError::BackendUnavailable(format!("{:?}", e)),
+            },
+        }
+    }
+}
 
 pub struct DynamoDbDao {
     client: Client,
     games_table: String,
     transactions_table: String,
-    game_id: String,
+    activity_table: String,
 }
 
 impl DynamoDbDao {
-    pub async fn new() -> Self {
-        // Create HTTPS connector with webpki-roots (Mozilla root certificates)
-        let rustls_connector = HttpsConnectorBuilder::new()
-            .with_webpki_roots()
-            .https_only()
-            .enable_http1()
-            .enable_http2()
-            .build();
+    pub async fn new(settings: &DynamoDbConfig) -> Self {
+        // Create HTTPS connector with webpki-roots (Mozilla root certificates). A custom
+        // endpoint may be plain http (DynamoDB Local), otherwise insist on https.
+        let connector_builder = HttpsConnectorBuilder::new().with_webpki_roots();
+        let connector_builder = if settings.endpoint.is_some() {
+            connector_builder.https_or_http()
+        } else {
+            connector_builder.https_only()
+        };
+        let rustls_connector = connector_builder.enable_http1().enable_http2().build();
 
         // Create HTTP client with the connector
         let http_client = HyperClientBuilder::new().build(rustls_connector);
 
         // Create AWS config with credentials from secrets and custom HTTP client
-        let config = aws_config::defaults(BehaviorVersion::latest())
-            .region(Region::new(AWS_REGION))
+        let mut loader = aws_config::defaults(BehaviorVersion::latest())
+            .region(Region::new(settings.region.clone()))
             .credentials_provider(aws_sdk_dynamodb::config::Credentials::new(
                 AWS_ACCESS_KEY_ID,
                 AWS_SECRET_ACCESS_KEY,
@@ -39,958 +429,1355 @@ impl DynamoDbDao {
                 None, // expiration
                 "split-happens-static-credentials",
             ))
-            .http_client(http_client)
-            .load()
-            .await;
+            .http_client(http_client);
+        if let Some(endpoint) = &settings.endpoint {
+            loader = loader.endpoint_url(endpoint);
+        }
+        let config = loader.load().await;
 
         let client = Client::new(&config);
 
         Self {
             client,
-            games_table: "split-happens-games".to_string(),
-            transactions_table: "split-happens-transactions".to_string(),
-            game_id: "sample_game_id".to_string(),
+            games_table: settings.games_table.clone(),
+            transactions_table: settings.transactions_table.clone(),
+            activity_table: settings.activity_table.clone(),
         }
     }
 
-    async fn get_game(
+    /// Fails with `BackendUnavailable` if the activity table doesn't exist, since every
+    /// change is logged in the same write and none could be recorded. Other failures only
+    /// log a warning, so starting without a network still works once it's back.
+    pub async fn check_activity_table(&self) -> StorageResult<()> {
+        let result = self
+            .client
+            .query()
+            .table_name(&self.activity_table)
+            .key_condition_expression("game_id = :game_id")
+            .expression_attribute_values(":game_id", AttributeValue::S("-".to_string()))
+            .limit(1)
+            .send()
+            .await
+            .map_err(aws_sdk_dynamodb::Error::from);
+        match result {
+            Ok(_) => Ok(()),
+            Err(aws_sdk_dynamodb::Error::ResourceNotFoundException(_)) => {
+                Err(StorageError::BackendUnavailable(format!(
+                    "The activity table {} doesn't exist; create it with a game_id (S) hash \
+                     key and an activity_num (N) range key",
+                    self.activity_table
+                )))
+            }
+            Err(e) => {
+                log::warn!(
+                    "Couldn't check the activity table {}: {:?}",
+                    self.activity_table,
+                    e
+                );
+                Ok(())
+            }
+        }
+    }
+
+    async fn get_game_item(
         &self,
+        game: &GameId,
     ) -> Result<Option<HashMap<String, AttributeValue>>, aws_sdk_dynamodb::Error> {
         let response = self
             .client
             .get_item()
             .table_name(&self.games_table)
-            .key("game_id", AttributeValue::S(self.game_id.clone()))
+            .key("game_id", AttributeValue::S(game.0.clone()))
             .send()
             .await?;
 
         Ok(response.item)
     }
 
-    async fn get_next_transaction_number(&self) -> Result<i32, aws_sdk_dynamodb::Error> {
-        match self.get_game().await? {
-            Some(game_item) => {
-                if let Some(AttributeValue::N(next_num_str)) = game_item.get("next_transaction_num")
-                {
-                    if let Ok(next_num) = next_num_str.parse::<i32>() {
-                        return Ok(next_num);
-                    }
-                }
-                // If no next_transaction_num field, default to 1
-                Ok(1)
+    async fn get_game_state(&self, game: &GameId) -> StorageResult<GameState> {
+        let game_item = self.get_migrated_game(game).await?;
+        let balances = parse_balances(game, &game_item)?
+            .into_iter()
+            .map(|(account, amount)| (AccountId::parse(&account), amount))
+            .collect();
+        let next_transaction_num = match game_item.get("next_transaction_num") {
+            Some(value) => Some(
+                value
+                    .as_n()
+                    .ok()
+                    .and_then(|num| num.parse::<u64>().ok())
+                    .ok_or_else(|| {
+                        StorageError::CorruptRecord(format!(
+                            "Unreadable next_transaction_num in game {}",
+                            game
+                        ))
+                    })?,
+            ),
+            None => None,
+        };
+        Ok(GameState {
+            balances,
+            next_transaction_num,
+            stake: parse_stake_policy(game, &game_item)?,
+            pot_policy: parse_pot_policy(game, &game_item)?,
+            roster: parse_roster(game, &game_item)?,
+            roster_version: parse_version(game, &game_item, "roster_version")?,
+            ledger_version: parse_version(game, &game_item, "ledger_version")?,
+            activity_count: parse_version(game, &game_item, "activity_count")?,
+        })
+    }
+
+    /// Moves a game written before players had ids onto account ids: builds a roster from
+    /// its name-based attributes and re-keys its balances. Transaction rows keep their
+    /// names and are resolved through [`Roster::legacy_names`] when read. The write is
+    /// conditioned on the game still having no roster and an unchanged counter, so it
+    /// fails with `StorageError::Conflict` rather than losing a concurrent write.
+    async fn migrate_game(
+        &self,
+        game_id: &GameId,
+        item: &HashMap<String, AttributeValue>,
+    ) -> StorageResult<()> {
+        let legacy = parse_legacy_roster(game_id, item)?;
+        let named_balances = parse_balances(game_id, item)?;
+        let roster = Roster::from_legacy(
+            legacy.players,
+            legacy.retired,
+            legacy.aliases,
+            named_balances.keys().cloned(),
+        );
+        let mut balances: HashMap<AccountId, Money> = HashMap::new();
+        for (name, amount) in named_balances {
+            *balances.entry(roster.legacy_account(&name)).or_default() += amount;
+        }
+        log::info!("Migrating game {} to account ids", game_id);
+
+        let update = self
+            .client
+            .update_item()
+            .table_name(&self.games_table)
+            .key("game_id", AttributeValue::S(game_id.0.clone()))
+            .update_expression(
+                "SET roster = :roster, balances = :balances REMOVE players, retired, aliases",
+            )
+            .expression_attribute_values(":roster", roster_to_attribute(&roster))
+            .expression_attribute_values(":balances", balances_to_attribute(balances));
+        let update = match item.get("next_transaction_num") {
+            Some(expected) => update
+                .condition_expression(
+                    "attribute_not_exists(roster) AND next_transaction_num = :expected",
+                )
+                .expression_attribute_values(":expected", expected.clone()),
+            None => update.condition_expression(
+                "attribute_exists(game_id) AND attribute_not_exists(roster) \
+                 AND attribute_not_exists(next_transaction_num)",
+            ),
+        };
+        update.send().await.map_err(aws_sdk_dynamodb::Error::from)?;
+        Ok(())
+    }
+
+    /// The game record, migrated to account ids first if it predates them.
+    async fn get_migrated_game(
+        &self,
+        game: &GameId,
+    ) -> StorageResult<HashMap<String, AttributeValue>> {
+        let mut attempt = 1;
+        loop {
+            let game_item = self.get_game_or_not_found(game).await?;
+            if game_item.contains_key("roster") {
+                return Ok(game_item);
             }
-            None => {
-                // If no game record exists, start with 1
-                Ok(1)
+            // Losing the race to another writer is fine; re-read and look again
+            match self.migrate_game(game, &game_item).await {
+                Err(StorageError::Conflict(message)) if attempt < MAX_WRITE_ATTEMPTS => {
+                    log::warn!(
+                        "Migration conflict on attempt {} of {}, retrying: {}",
+                        attempt,
+                        MAX_WRITE_ATTEMPTS,
+                        message
+                    );
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+                Ok(()) => {}
             }
         }
     }
 
-    async fn put_transaction_with_lock(
+    fn transaction_to_item(
         &self,
+        game: &GameId,
         transaction: &Transaction,
-        transaction_number: i32,
-    ) -> Result<(), aws_sdk_dynamodb::Error> {
+    ) -> HashMap<String, AttributeValue> {
         let mut item = HashMap::new();
+        item.insert("game_id".to_string(), AttributeValue::S(game.0.clone()));
         item.insert(
-            "game_id".to_string(),
-            AttributeValue::S(self.game_id.clone()),
+            "transaction_num".to_string(),
+            AttributeValue::N(transaction.id.0.to_string()),
         );
         item.insert(
-            "transaction_num".to_string(),
-            AttributeValue::N(transaction_number.to_string()),
+            "kind".to_string(),
+            json_to_attribute(serde_json::to_value(transaction.kind).expect("kind serializes")),
         );
+        // Rows written before account ids have `creditor`/`debtor` names instead
         item.insert(
-            "creditor".to_string(),
-            AttributeValue::S(transaction.creditor.clone()),
+            "creditor_id".to_string(),
+            AttributeValue::S(transaction.creditor.to_string()),
         );
         item.insert(
-            "debtor".to_string(),
-            AttributeValue::S(transaction.debtor.clone()),
+            "debtor_id".to_string(),
+            AttributeValue::S(transaction.debtor.to_string()),
         );
         item.insert(
             "amount".to_string(),
             AttributeValue::N(transaction.amount.to_string()),
         );
-        item.insert(
-            "split".to_string(),
-            AttributeValue::S(transaction.split.clone()),
-        );
+        if let Some(split) = transaction.split {
+            item.insert("split".to_string(), AttributeValue::S(split.to_string()));
+        }
         item.insert(
             "time".to_string(),
-            AttributeValue::S(transaction.time.clone()),
+            AttributeValue::N(transaction.time.timestamp_millis().to_string()),
         );
         item.insert(
             "pot_amount".to_string(),
             AttributeValue::N(transaction.pot_amount.to_string()),
         );
-        item.insert(
-            "date".to_string(),
-            AttributeValue::S(transaction.date.clone()),
-        );
-
-        // Optimistic lock - only put if transaction_num doesn't exist
-        self.client
-            .put_item()
-            .table_name(&self.transactions_table)
-            .set_item(Some(item))
-            .condition_expression("attribute_not_exists(transaction_num)")
-            .send()
-            .await?;
+        if let Some(stake) = &transaction.stake {
+            item.insert(
+                "stake".to_string(),
+                json_to_attribute(serde_json::to_value(stake).expect("stake serializes")),
+            );
+        }
+        if let Some(award) = &transaction.award {
+            item.insert(
+                "award".to_string(),
+                json_to_attribute(serde_json::to_value(award).expect("award serializes")),
+            );
+        }
+        if let Some(void) = &transaction.voided {
+            item.insert(
+                "voided".to_string(),
+                json_to_attribute(serde_json::to_value(void).expect("void serializes")),
+            );
+        }
+        item
+    }
 
-        Ok(())
+    /// The put appending `activity` to the game's log as the entry after the `logged`
+    /// ones. It is conditioned on that entry not existing yet, so of two writes racing to
+    /// log it only one succeeds; the game update written with it must set
+    /// `activity_count` (see [`count_activity`]).
+    fn log_activity(
+        &self,
+        game_id: &GameId,
+        logged: u64,
+        mut activity: Activity,
+    ) -> StorageResult<TransactWriteItem> {
+        activity.id = logged + 1;
+        let put = Put::builder()
+            .table_name(&self.activity_table)
+            .set_item(Some(activity_to_item(game_id, &activity)))
+            .condition_expression("attribute_not_exists(activity_num)")
+            .build()
+            .map_err(|e| StorageError::BackendUnavailable(e.to_string()))?;
+        Ok(TransactWriteItem::builder().put(put).build())
     }
 
-    async fn update_game_with_transaction(
+    /// Writes the transaction rows, the game's new balances/counter and `activity` in one
+    /// `TransactWriteItems` call. The game update is conditioned on the ledger being as
+    /// `game` saw it, so a concurrent writer makes the whole call fail with
+    /// `StorageError::Conflict` and nothing is written.
+    async fn commit_transactions(
         &self,
-        transaction: &Transaction,
-        transaction_number: i32,
-    ) -> Result<(), aws_sdk_dynamodb::Error> {
-        // Get current game state to update balances incrementally
-        let current_balances = match self.get_game().await? {
-            Some(game_item) => {
-                if let Some(AttributeValue::M(balances_map)) = game_item.get("balances") {
-                    balances_map
-                        .iter()
-                        .filter_map(|(name, value)| {
-                            if let Ok(amount_str) = value.as_n() {
-                                if let Ok(amount) = amount_str.parse::<f64>() {
-                                    return Some((name.clone(), amount));
-                                }
-                            }
-                            None
-                        })
-                        .collect::<HashMap<String, f64>>()
-                } else {
-                    HashMap::new()
-                }
-            }
-            None => HashMap::new(),
+        game_id: &GameId,
+        game: &GameState,
+        transactions: &[Transaction],
+        mut activity: Activity,
+    ) -> StorageResult<()> {
+        // Two items are the game update and the log entry
+        if transactions.len() > MAX_TRANSACT_ITEMS - 2 {
+            return Err(StorageError::InvalidConfig(format!(
+                "Can't record more than {} transactions at once",
+                MAX_TRANSACT_ITEMS - 2
+            )));
+        }
+        let Some(last) = transactions.last() else {
+            return Ok(());
         };
+        let transaction_number = last.id.0;
 
-        // Calculate new balances based on the transaction
-        let mut new_balances = current_balances;
-        // Creditor gets positive amount
-        *new_balances
-            .entry(transaction.creditor.clone())
-            .or_insert(0.0) += transaction.amount;
-        // Debtor gets negative amount
-        *new_balances
-            .entry(transaction.debtor.clone())
-            .or_insert(0.0) -= transaction.amount;
-
-        // Convert to DynamoDB format
-        let balance_map: HashMap<String, AttributeValue> = new_balances
-            .into_iter()
-            .map(|(name, amount)| (name, AttributeValue::N(amount.to_string())))
-            .collect();
+        let mut new_balances = game.balances.clone();
+        let mut request = self.client.transact_write_items();
+        for transaction in transactions {
+            ledger::apply(&mut new_balances, transaction);
+            let put = Put::builder()
+                .table_name(&self.transactions_table)
+                .set_item(Some(self.transaction_to_item(game_id, transaction)))
+                .condition_expression("attribute_not_exists(transaction_num)")
+                .build()
+                .map_err(|e| StorageError::BackendUnavailable(e.to_string()))?;
+            request = request.transact_items(TransactWriteItem::builder().put(put).build());
+        }
+        activity.transactions = transactions.iter().map(|t| t.id).collect();
+        activity.balances = ledger::changes(&game.balances, &new_balances, &game.roster);
 
-        // Update both balances and next_transaction_num atomically
-        self.client
-            .update_item()
+        let update = Update::builder()
             .table_name(&self.games_table)
-            .key("game_id", AttributeValue::S(self.game_id.clone()))
-            .update_expression("SET balances = :balances, next_transaction_num = :next_num")
-            .expression_attribute_values(":balances", AttributeValue::M(balance_map))
+            .key("game_id", AttributeValue::S(game_id.0.clone()))
+            .update_expression(
+                "SET balances = :balances, next_transaction_num = :next_num, \
+                 activity_count = :activity_count",
+            )
+            .expression_attribute_values(":balances", balances_to_attribute(new_balances))
             .expression_attribute_values(
                 ":next_num",
                 AttributeValue::N((transaction_number + 1).to_string()),
-            )
+            );
+        let update = ledger_unchanged(count_activity(update, game.activity_count), game)
+            .build()
+            .map_err(|e| StorageError::BackendUnavailable(e.to_string()))?;
+
+        request
+            .transact_items(TransactWriteItem::builder().update(update).build())
+            .transact_items(self.log_activity(game_id, game.activity_count, activity)?)
             .send()
-            .await?;
+            .await
+            .map_err(aws_sdk_dynamodb::Error::from)?;
 
         Ok(())
     }
 
-    async fn reverse_transaction_in_game(
+    /// Builds transactions and their log entry from the current game state, plus the
+    /// transactions recorded since `history_since` if given, and commits them, re-reading
+    /// both and rebuilding the transactions when another device wins the race. Returns
+    /// what was committed.
+    async fn add_transactions_with_retry<F>(
         &self,
-        transaction: &Transaction,
-    ) -> Result<(), aws_sdk_dynamodb::Error> {
-        // Get current game state
-        let current_balances = match self.get_game().await? {
-            Some(game_item) => {
-                if let Some(AttributeValue::M(balances_map)) = game_item.get("balances") {
-                    balances_map
-                        .iter()
-                        .filter_map(|(name, value)| {
-                            if let Ok(amount_str) = value.as_n() {
-                                if let Ok(amount) = amount_str.parse::<f64>() {
-                                    return Some((name.clone(), amount));
-                                }
-                            }
-                            None
-                        })
-                        .collect::<HashMap<String, f64>>()
-                } else {
-                    HashMap::new()
+        game_id: &GameId,
+        history_since: Option<DateTime<Utc>>,
+        build: F,
+    ) -> StorageResult<Vec<Transaction>>
+    where
+        F: Fn(&GameState, &[Transaction]) -> StorageResult<(Vec<Transaction>, Activity)>
+            + Send
+            + Sync,
+    {
+        let mut attempt = 1;
+        loop {
+            // The game is read first so that anything recorded after the history was read
+            // changes the counter and fails the commit.
+            let game = self.get_game_state(game_id).await?;
+            let history = match history_since {
+                Some(since) => self.transactions_since(game_id, since).await?,
+                None => Vec::new(),
+            };
+            let (transactions, activity) = build(&game, &history)?;
+            match self
+                .commit_transactions(game_id, &game, &transactions, activity)
+                .await
+            {
+                Err(StorageError::Conflict(message)) if attempt < MAX_WRITE_ATTEMPTS => {
+                    log::warn!(
+                        "Write conflict on attempt {} of {}, retrying: {}",
+                        attempt,
+                        MAX_WRITE_ATTEMPTS,
+                        message
+                    );
+                    attempt += 1;
                 }
+                result => return result.map(|()| transactions),
             }
-            None => HashMap::new(),
-        };
-
-        // Reverse the transaction effects
-        let mut new_balances = current_balances;
-        // Reverse creditor's positive amount
-        *new_balances
-            .entry(transaction.creditor.clone())
-            .or_insert(0.0) -= transaction.amount;
-        // Reverse debtor's negative amount
-        *new_balances
-            .entry(transaction.debtor.clone())
-            .or_insert(0.0) += transaction.amount;
-
-        // Convert to DynamoDB format
-        let balance_map: HashMap<String, AttributeValue> = new_balances
-            .into_iter()
-            .map(|(name, amount)| (name, AttributeValue::N(amount.to_string())))
-            .collect();
-
-        // Update balances
-        self.client
-            .update_item()
-            .table_name(&self.games_table)
-            .key("game_id", AttributeValue::S(self.game_id.clone()))
-            .update_expression("SET balances = :balances")
-            .expression_attribute_values(":balances", AttributeValue::M(balance_map))
-            .send()
-            .await?;
+        }
+    }
 
-        Ok(())
+    /// Transactions recorded at or after `since`, newest first.
+    async fn transactions_since(
+        &self,
+        game: &GameId,
+        since: DateTime<Utc>,
+    ) -> StorageResult<Vec<Transaction>> {
+        let mut recent = Vec::new();
+        let mut before = None;
+        loop {
+            let page = self
+                .get_transactions_page(game, before, RECENT_PAGE_SIZE)
+                .await?;
+            let reached_older = page.transactions.first().is_some_and(|t| t.time < since);
+            recent.extend(
+                page.transactions
+                    .into_iter()
+                    .rev()
+                    .filter(|t| t.time >= since),
+            );
+            before = page.next_before;
+            if reached_older || before.is_none() {
+                return Ok(recent);
+            }
+        }
     }
 
-    async fn delete_transaction(
+    /// Marks `transaction` voided by `void`, or restores it when `void` is `None`, and
+    /// adjusts the game balances to match and logs `activity` in one `TransactWriteItems`
+    /// call. The row write is conditioned on the transaction not already being in that
+    /// state and the game update on the ledger being as `game` saw it, so a racing write
+    /// fails the call with `StorageError::Conflict` rather than voiding or restoring the
+    /// wrong row.
+    async fn commit_void(
         &self,
-        transaction_number: i32,
-    ) -> Result<(), aws_sdk_dynamodb::Error> {
-        self.client
-            .delete_item()
+        game_id: &GameId,
+        game: &GameState,
+        transaction: &Transaction,
+        void: Option<&Void>,
+        mut activity: Activity,
+    ) -> StorageResult<()> {
+        let mut new_balances = game.balances.clone();
+        let row = Update::builder()
             .table_name(&self.transactions_table)
-            .key("game_id", AttributeValue::S(self.game_id.clone()))
+            .key("game_id", AttributeValue::S(game_id.0.clone()))
             .key(
                 "transaction_num",
-                AttributeValue::N(transaction_number.to_string()),
+                AttributeValue::N(transaction.id.0.to_string()),
+            );
+        let row = match void {
+            Some(void) => {
+                ledger::reverse(&mut new_balances, transaction);
+                row.update_expression("SET voided = :void")
+                    .expression_attribute_values(
+                        ":void",
+                        json_to_attribute(serde_json::to_value(void).expect("void serializes")),
+                    )
+                    .condition_expression(
+                        "attribute_exists(transaction_num) AND attribute_not_exists(voided)",
+                    )
+            }
+            None => {
+                ledger::apply(&mut new_balances, transaction);
+                row.update_expression("REMOVE voided")
+                    .condition_expression("attribute_exists(voided)")
+            }
+        }
+        .build()
+        .map_err(|e| StorageError::BackendUnavailable(e.to_string()))?;
+        activity.transactions = vec![transaction.id];
+        activity.balances = ledger::changes(&game.balances, &new_balances, &game.roster);
+
+        self.client
+            .transact_write_items()
+            .transact_items(TransactWriteItem::builder().update(row).build())
+            .transact_items(
+                TransactWriteItem::builder()
+                    .update(self.rewrite_balances(game_id, game, new_balances)?)
+                    .build(),
             )
+            .transact_items(self.log_activity(game_id, game.activity_count, activity)?)
             .send()
-            .await?;
+            .await
+            .map_err(aws_sdk_dynamodb::Error::from)?;
 
         Ok(())
     }
 
-    fn item_to_transaction(&self, item: &HashMap<String, AttributeValue>) -> Option<Transaction> {
-        Some(Transaction {
-            creditor: item.get("creditor")?.as_s().ok()?.clone(),
-            debtor: item.get("debtor")?.as_s().ok()?.clone(),
-            amount: item.get("amount")?.as_n().ok()?.parse().ok()?,
-            split: item.get("split")?.as_s().ok()?.clone(),
-            time: item.get("time")?.as_s().ok()?.clone(),
-            pot_amount: item.get("pot_amount")?.as_n().ok()?.parse().ok()?,
-            date: item.get("date")?.as_s().ok()?.clone(),
-        })
+    /// Rewrites the rows a correction changed, moves the game balances to match and logs
+    /// `activity` in one `TransactWriteItems` call, conditioned like [`Self::commit_void`].
+    async fn commit_correction(
+        &self,
+        game_id: &GameId,
+        game: &GameState,
+        replay: &Replay,
+        mut activity: Activity,
+    ) -> StorageResult<()> {
+        // Two items are the game update and the log entry
+        if replay.changed.len() > MAX_TRANSACT_ITEMS - 2 {
+            return Err(StorageError::InvalidConfig(format!(
+                "This correction changes {} transactions, more than the {} that can be \
+                 rewritten at once",
+                replay.changed.len(),
+                MAX_TRANSACT_ITEMS - 2
+            )));
+        }
+        let mut new_balances = game.balances.clone();
+        for (account, delta) in replay.deltas() {
+            *new_balances.entry(account.clone()).or_default() += delta;
+        }
+
+        let mut request = self.client.transact_write_items();
+        for transaction in &replay.changed {
+            let put = Put::builder()
+                .table_name(&self.transactions_table)
+                .set_item(Some(self.transaction_to_item(game_id, transaction)))
+                .condition_expression("attribute_exists(transaction_num)")
+                .build()
+                .map_err(|e| StorageError::BackendUnavailable(e.to_string()))?;
+            request = request.transact_items(TransactWriteItem::builder().put(put).build());
+        }
+        activity.transactions = replay.changed.iter().map(|t| t.id).collect();
+        activity.balances = replay.balances.clone();
+        request
+            .transact_items(
+                TransactWriteItem::builder()
+                    .update(self.rewrite_balances(game_id, game, new_balances)?)
+                    .build(),
+            )
+            .transact_items(self.log_activity(game_id, game.activity_count, activity)?)
+            .send()
+            .await
+            .map_err(aws_sdk_dynamodb::Error::from)?;
+
+        Ok(())
     }
 
-    async fn get_balances_from_game(&self) -> Vec<Balance> {
-        match self.get_game().await {
-            Ok(Some(game_item)) => {
-                if let Some(AttributeValue::M(balances_map)) = game_item.get("balances") {
-                    balances_map
-                        .iter()
-                        .filter_map(|(name, value)| {
-                            if let Ok(amount_str) = value.as_n() {
-                                if let Ok(amount) = amount_str.parse::<f64>() {
-                                    return Some(Balance {
-                                        name: name.clone(),
-                                        amount: format!("{:.2}", amount),
-                                    });
-                                }
-                            }
-                            None
-                        })
-                        .collect()
-                } else {
-                    panic!("No balances in game");
+    /// Voids the latest transaction that still counts with `void`, or restores the latest
+    /// undone one when `void` is `None`, starting over from a fresh read if another device
+    /// changes the ledger in between. Returns the transaction as it now stands.
+    async fn void_latest_with_retry(
+        &self,
+        game_id: &GameId,
+        void: Option<Void>,
+        activity: Activity,
+    ) -> StorageResult<Transaction> {
+        let mut attempt = 1;
+        loop {
+            // Read the game before the transactions so a write landing in between changes
+            // the ledger we condition on and fails the commit rather than being missed.
+            let game = self.get_game_state(game_id).await?;
+            let recent = self.transactions_back_to_counted(game_id).await?;
+            let candidate = match void {
+                Some(_) => ledger::undo_candidate(&recent)
+                    .ok_or_else(|| StorageError::NotFound("Nothing to undo".to_string())),
+                None => ledger::redo_candidate(&recent)
+                    .ok_or_else(|| StorageError::NotFound("Nothing to redo".to_string())),
+            };
+            let mut transaction = candidate?.clone();
+            match self
+                .commit_void(
+                    game_id,
+                    &game,
+                    &transaction,
+                    void.as_ref(),
+                    activity.clone(),
+                )
+                .await
+            {
+                Err(StorageError::Conflict(message)) if attempt < MAX_WRITE_ATTEMPTS => {
+                    log::warn!(
+                        "Undo conflict on attempt {} of {}, retrying: {}",
+                        attempt,
+                        MAX_WRITE_ATTEMPTS,
+                        message
+                    );
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+                Ok(()) => {
+                    transaction.voided = void;
+                    return Ok(transaction);
                 }
             }
-            _ => {
-                panic!("No game found");
+        }
+    }
+
+    /// The game update setting `balances` after an undo, redo or correction. It bumps
+    /// `ledger_version` and `activity_count` and is conditioned on the ledger being as
+    /// `game` saw it.
+    fn rewrite_balances(
+        &self,
+        game_id: &GameId,
+        game: &GameState,
+        balances: HashMap<AccountId, Money>,
+    ) -> StorageResult<Update> {
+        let update = Update::builder()
+            .table_name(&self.games_table)
+            .key("game_id", AttributeValue::S(game_id.0.clone()))
+            .update_expression(
+                "SET balances = :balances, ledger_version = :next_ledger_version, \
+                 activity_count = :activity_count",
+            )
+            .expression_attribute_values(":balances", balances_to_attribute(balances))
+            .expression_attribute_values(
+                ":next_ledger_version",
+                AttributeValue::N((game.ledger_version + 1).to_string()),
+            );
+        ledger_unchanged(count_activity(update, game.activity_count), game)
+            .build()
+            .map_err(|e| StorageError::BackendUnavailable(e.to_string()))
+    }
+
+    /// The latest transactions, newest first, back to the latest one that still counts
+    /// (or the start of the game if none do).
+    async fn transactions_back_to_counted(&self, game: &GameId) -> StorageResult<Vec<Transaction>> {
+        let mut newest_first = Vec::new();
+        let mut before = None;
+        loop {
+            let page = self
+                .get_transactions_page(game, before, RECENT_PAGE_SIZE)
+                .await?;
+            let reached_counted = page.transactions.iter().any(|t| !t.is_voided());
+            newest_first.extend(page.transactions.into_iter().rev());
+            before = page.next_before;
+            if reached_counted || before.is_none() {
+                return Ok(newest_first);
             }
         }
     }
 
-    async fn get_last_transaction_and_number(&self) -> (Transaction, i32) {
-        match self
-            .client
-            .query()
-            .table_name(&self.transactions_table)
-            .key_condition_expression("game_id = :game_id")
-            .expression_attribute_values(":game_id", AttributeValue::S(self.game_id.clone()))
-            .scan_index_forward(false)
-            .limit(1 as i32) // Limit to n items
+    /// Writes a changed roster and logs `activity`. The update is conditioned on
+    /// `roster_version` still being what `game` saw, so two devices editing players can't
+    /// overwrite each other.
+    async fn commit_roster(
+        &self,
+        game_id: &GameId,
+        game: &GameState,
+        roster: &Roster,
+        activity: Activity,
+    ) -> StorageResult<()> {
+        let update = Update::builder()
+            .table_name(&self.games_table)
+            .key("game_id", AttributeValue::S(game_id.0.clone()))
+            .update_expression(
+                "SET roster = :roster, roster_version = :next_version, \
+                 activity_count = :activity_count",
+            )
+            .expression_attribute_values(":roster", roster_to_attribute(roster))
+            .expression_attribute_values(
+                ":next_version",
+                AttributeValue::N((game.roster_version + 1).to_string()),
+            );
+        let update = match game.roster_version {
+            0 => update.condition_expression(
+                "attribute_exists(roster) AND attribute_not_exists(roster_version)",
+            ),
+            expected => update
+                .condition_expression("roster_version = :expected_version")
+                .expression_attribute_values(
+                    ":expected_version",
+                    AttributeValue::N(expected.to_string()),
+                ),
+        };
+        let update = count_activity(update, game.activity_count)
+            .build()
+            .map_err(|e| StorageError::BackendUnavailable(e.to_string()))?;
+
+        self.client
+            .transact_write_items()
+            .transact_items(TransactWriteItem::builder().update(update).build())
+            .transact_items(self.log_activity(game_id, game.activity_count, activity)?)
             .send()
             .await
-        {
-            Ok(response) => response
-                .items
-                .unwrap_or_default()
-                .iter()
-                .map(|item| {
-                    let transaction_num = item
-                        .get("transaction_num")
-                        .unwrap()
-                        .as_n()
-                        .unwrap()
-                        .parse()
-                        .unwrap();
-                    let transaction = self.item_to_transaction(&item).unwrap();
-                    (transaction, transaction_num)
-                })
-                .next()
-                .unwrap(),
-            Err(e) => {
-                log::error!("Failed to get last n transactions: {:?}", e);
-                panic!("Failed to get last n transactions: {:?}", e);
+            .map_err(aws_sdk_dynamodb::Error::from)?;
+        Ok(())
+    }
+
+    /// Applies `change` to the current roster and commits the result with the log entry
+    /// `describe` writes from the roster before the change, starting over if another
+    /// device changed the roster or logged a change in between.
+    async fn update_roster_with_retry<D, F, T>(
+        &self,
+        game_id: &GameId,
+        describe: D,
+        change: F,
+    ) -> StorageResult<T>
+    where
+        D: Fn(&Roster) -> Activity + Send + Sync,
+        F: Fn(&mut Roster) -> StorageResult<T> + Send + Sync,
+        T: Send,
+    {
+        let mut attempt = 1;
+        loop {
+            let game = self.get_game_state(game_id).await?;
+            let mut roster = game.roster.clone();
+            let activity = describe(&roster);
+            let result = change(&mut roster)?;
+            match self.commit_roster(game_id, &game, &roster, activity).await {
+                Err(StorageError::Conflict(message)) if attempt < MAX_WRITE_ATTEMPTS => {
+                    log::warn!(
+                        "Roster conflict on attempt {} of {}, retrying: {}",
+                        attempt,
+                        MAX_WRITE_ATTEMPTS,
+                        message
+                    );
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+                Ok(()) => return Ok(result),
             }
         }
     }
-}
 
-#[async_trait]
-impl StorageDao for DynamoDbDao {
-    async fn get_names(&self) -> Vec<String> {
-        match self.get_game().await {
-            Ok(Some(game_item)) => {
-                if let Some(AttributeValue::Ss(players)) = game_item.get("players") {
-                    let mut names = players.clone();
-                    // Always ensure "Pot" is included
-                    if !names.contains(&"Pot".to_string()) {
-                        names.push("Pot".to_string());
-                    }
-                    names
-                } else {
-                    panic!("No players in game");
+    /// Sets the game attribute `attribute` to `value` and logs `activity`, starting over
+    /// if another device logs a change in between.
+    async fn set_game_attribute_with_retry(
+        &self,
+        game_id: &GameId,
+        attribute: &str,
+        value: AttributeValue,
+        activity: Activity,
+    ) -> StorageResult<()> {
+        let mut attempt = 1;
+        loop {
+            let game = self.get_game_state(game_id).await?;
+            let update = Update::builder()
+                .table_name(&self.games_table)
+                .key("game_id", AttributeValue::S(game_id.0.clone()))
+                .update_expression(format!(
+                    "SET {} = :value, activity_count = :activity_count",
+                    attribute
+                ))
+                .condition_expression("attribute_exists(game_id)")
+                .expression_attribute_values(":value", value.clone());
+            let update = count_activity(update, game.activity_count)
+                .build()
+                .map_err(|e| StorageError::BackendUnavailable(e.to_string()))?;
+            let result = self
+                .client
+                .transact_write_items()
+                .transact_items(TransactWriteItem::builder().update(update).build())
+                .transact_items(self.log_activity(
+                    game_id,
+                    game.activity_count,
+                    activity.clone(),
+                )?)
+                .send()
+                .await
+                .map_err(|e| StorageError::from(aws_sdk_dynamodb::Error::from(e)));
+            match result {
+                Err(StorageError::Conflict(message)) if attempt < MAX_WRITE_ATTEMPTS => {
+                    log::warn!(
+                        "Setting conflict on attempt {} of {}, retrying: {}",
+                        attempt,
+                        MAX_WRITE_ATTEMPTS,
+                        message
+                    );
+                    attempt += 1;
                 }
-            }
-            _ => {
-                panic!("No game found");
+                result => return result.map(|_| ()),
             }
         }
     }
 
-    async fn get_balances(&self) -> Vec<Balance> {
-        self.get_balances_from_game().await
+    fn item_to_transaction(
+        &self,
+        item: &HashMap<String, AttributeValue>,
+        roster: &Roster,
+    ) -> StorageResult<Transaction> {
+        self.parse_transaction(item, roster).ok_or_else(|| {
+            StorageError::CorruptRecord(format!("Unreadable transaction item: {:?}", item))
+        })
     }
 
-    async fn get_last_n_transactions(&self, n: usize) -> Vec<Transaction> {
-        // Use DynamoDB query with descending sort order and limit
-        match self
-            .client
-            .query()
-            .table_name(&self.transactions_table)
-            .key_condition_expression("game_id = :game_id")
-            .expression_attribute_values(":game_id", AttributeValue::S(self.game_id.clone()))
-            .scan_index_forward(true) // Sort by sort key (transaction_num) in descending order
-            .limit(n as i32) // Limit to n items
-            .send()
-            .await
-        {
-            Ok(response) => response
-                .items
-                .unwrap_or_default()
-                .iter()
-                .filter_map(|item| self.item_to_transaction(item))
-                .collect(),
-            Err(e) => {
-                log::error!("Failed to get last n transactions: {:?}", e);
-                Vec::new()
-            }
+    /// Reads one side of a transaction from its `<side>_id` attribute, or from the name
+    /// in `<side>` on rows written before account ids.
+    fn parse_account(
+        item: &HashMap<String, AttributeValue>,
+        side: &str,
+        roster: &Roster,
+    ) -> Option<AccountId> {
+        match item.get(&format!("{}_id", side)) {
+            Some(id) => Some(AccountId::parse(id.as_s().ok()?)),
+            None => Some(roster.legacy_account(item.get(side)?.as_s().ok()?)),
         }
     }
 
-    async fn remove_last_transaction(&self) {
-        let (last_transaction, last_transaction_num) = self.get_last_transaction_and_number().await;
+    fn parse_transaction(
+        &self,
+        item: &HashMap<String, AttributeValue>,
+        roster: &Roster,
+    ) -> Option<Transaction> {
+        let stored = StoredTransaction {
+            id: TransactionId(item.get("transaction_num")?.as_n().ok()?.parse().ok()?),
+            // Rows written before kinds were stored are classified from the rest
+            kind: match item.get("kind") {
+                Some(kind) => Some(serde_json::from_value(attribute_to_json(kind)?).ok()?),
+                None => None,
+            },
+            creditor: Self::parse_account(item, "creditor", roster)?,
+            debtor: Self::parse_account(item, "debtor", roster)?,
+            amount: Money::parse(item.get("amount")?.as_n().ok()?)?,
+            split: match item.get("split") {
+                Some(split) => Some(split.as_s().ok()?.parse().ok()?),
+                None => None,
+            },
+            time: parse_time(item.get("time")?)?,
+            pot_amount: Money::parse(item.get("pot_amount")?.as_n().ok()?)?,
+            stake: match item.get("stake") {
+                Some(stake) => Some(serde_json::from_value(attribute_to_json(stake)?).ok()?),
+                None => None,
+            },
+            award: match item.get("award") {
+                Some(award) => Some(serde_json::from_value(attribute_to_json(award)?).ok()?),
+                None => None,
+            },
+            voided: match item.get("voided") {
+                Some(void) => Some(serde_json::from_value(attribute_to_json(void)?).ok()?),
+                None => None,
+            },
+        };
+        Some(stored.into())
+    }
 
-        if let Err(e) = self.delete_transaction(last_transaction_num).await {
-            log::error!("Failed to delete transaction: {:?}", e);
-            return;
+    async fn get_game_or_not_found(
+        &self,
+        game: &GameId,
+    ) -> StorageResult<HashMap<String, AttributeValue>> {
+        self.get_game_item(game)
+            .await?
+            .ok_or_else(|| StorageError::NotFound(format!("No game found: {}", game)))
+    }
+
+    async fn get_balances_from_game(&self, game: &GameId) -> StorageResult<Vec<Balance>> {
+        let GameState {
+            mut balances,
+            roster,
+            ..
+        } = self.get_game_state(game).await?;
+        // Players who have yet to play have no entry in the map
+        for account in roster.active_accounts() {
+            balances.entry(account).or_default();
         }
+        Ok(ledger::to_balances(balances, &roster))
+    }
+}
 
-        // Reverse the transaction effects in the game balances
-        if let Err(e) = self.reverse_transaction_in_game(&last_transaction).await {
-            log::error!("Failed to reverse transaction in game: {:?}", e);
+#[async_trait]
+impl StorageDao for DynamoDbDao {
+    async fn list_games(&self) -> StorageResult<Vec<GameSummary>> {
+        let mut games = Vec::new();
+        let mut start_key = None;
+        loop {
+            // `name` is a reserved word in expressions
+            let response = self
+                .client
+                .scan()
+                .table_name(&self.games_table)
+                .projection_expression("game_id, #name")
+                .expression_attribute_names("#name", "name")
+                .set_exclusive_start_key(start_key)
+                .send()
+                .await
+                .map_err(aws_sdk_dynamodb::Error::from)?;
+            for item in response.items.unwrap_or_default() {
+                let id = item
+                    .get("game_id")
+                    .and_then(|value| value.as_s().ok())
+                    .ok_or_else(|| {
+                        StorageError::CorruptRecord(format!("Game without an id: {:?}", item))
+                    })?;
+                // Games created by hand before names existed are shown by id
+                let name = item
+                    .get("name")
+                    .and_then(|value| value.as_s().ok())
+                    .unwrap_or(id);
+                games.push(GameSummary {
+                    id: GameId(id.clone()),
+                    name: name.clone(),
+                });
+            }
+            start_key = response.last_evaluated_key;
+            if start_key.is_none() {
+                break;
+            }
         }
+        games.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(games)
     }
 
-    async fn add_split(&self, name: String, split: String) {
-        let pot_balance = self
-            .get_balances_from_game()
+    async fn create_game(&self, game: NewGame, actor: &str) -> StorageResult<GameSummary> {
+        game.validate()?;
+        let id = GameId::generate(&game.name, Utc::now());
+
+        // Every player starts with an explicit zero balance, as does the pot
+        let roster = game.roster()?;
+        let balances = roster
+            .active_accounts()
+            .into_iter()
+            .map(|account| (account, Money::ZERO))
+            .collect();
+        let put = Put::builder()
+            .table_name(&self.games_table)
+            .item("game_id", AttributeValue::S(id.0.clone()))
+            .item("name", AttributeValue::S(game.name.clone()))
+            .item("roster", roster_to_attribute(&roster))
+            .item("balances", balances_to_attribute(balances))
+            .item("next_transaction_num", AttributeValue::N("1".to_string()))
+            .item("stake", stake_policy_to_attribute(&game.stake))
+            .item("pot_policy", pot_policy_to_attribute(&game.pot_policy))
+            .item("award_table", award_table_to_attribute(&game.award_table))
+            .item("activity_count", AttributeValue::N("1".to_string()))
+            .condition_expression("attribute_not_exists(game_id)")
+            .build()
+            .map_err(|e| StorageError::BackendUnavailable(e.to_string()))?;
+        let activity = Activity::new(actor, Action::CreateGame, Some(game.name.clone()));
+        self.client
+            .transact_write_items()
+            .transact_items(TransactWriteItem::builder().put(put).build())
+            .transact_items(self.log_activity(&id, 0, activity)?)
+            .send()
             .await
-            .iter()
-            .find(|b| b.name == "Pot")
-            .map(|b| b.amount.parse::<f64>().unwrap_or(0.0))
-            .unwrap_or(0.0);
-
-        let now = SystemTime::now();
-        let now: DateTime<Utc> = now.into();
-        let now_string = now.format("%-m/%-d/%Y, %l:%M:%S %p UTC").to_string();
-        let today_string = now.format("%-m/%-d/%Y").to_string();
-
-        let transaction = Transaction {
-            creditor: "Pot".to_string(),
-            debtor: name,
-            amount: 1.0,
-            split,
-            time: now_string.clone(),
-            pot_amount: pot_balance,
-            date: today_string,
+            .map_err(aws_sdk_dynamodb::Error::from)
+            .inspect_err(|e| log::error!("Failed to create game: {:?}", e))?;
+
+        Ok(GameSummary {
+            id,
+            name: game.name,
+        })
+    }
+
+    async fn get_game(&self, game: &GameId) -> StorageResult<GameSummary> {
+        let game_item = self.get_game_or_not_found(game).await?;
+        Ok(GameSummary {
+            id: game.clone(),
+            name: game_item
+                .get("name")
+                .and_then(|value| value.as_s().ok())
+                .cloned()
+                .unwrap_or_else(|| game.0.clone()),
+        })
+    }
+
+    async fn get_players(&self, game: &GameId) -> StorageResult<Vec<Player>> {
+        let game_item = self.get_migrated_game(game).await?;
+        Ok(parse_roster(game, &game_item)?.players)
+    }
+
+    async fn add_player(
+        &self,
+        game: &GameId,
+        name: String,
+        actor: &str,
+    ) -> StorageResult<PlayerId> {
+        self.update_roster_with_retry(
+            game,
+            |_| Activity::new(actor, Action::AddPlayer, Some(name.clone())),
+            |roster| roster.add(&name),
+        )
+        .await
+        .inspect_err(|e| log::error!("Failed to add player: {:?}", e))
+    }
+
+    async fn rename_player(
+        &self,
+        game: &GameId,
+        player: &PlayerId,
+        new_name: String,
+        actor: &str,
+    ) -> StorageResult<()> {
+        self.update_roster_with_retry(
+            game,
+            |roster| {
+                let renamed = activity::describe_rename(roster, player, &new_name);
+                Activity::new(actor, Action::RenamePlayer, Some(renamed))
+            },
+            |roster| roster.rename(player, &new_name),
+        )
+        .await
+        .inspect_err(|e| log::error!("Failed to rename player: {:?}", e))
+    }
+
+    async fn retire_player(
+        &self,
+        game: &GameId,
+        player: &PlayerId,
+        actor: &str,
+    ) -> StorageResult<()> {
+        self.update_roster_with_retry(
+            game,
+            |roster| {
+                let retired = roster.name_of(&AccountId::Player(player.clone()));
+                Activity::new(actor, Action::RetirePlayer, Some(retired))
+            },
+            |roster| roster.retire(player),
+        )
+        .await
+        .inspect_err(|e| log::error!("Failed to retire player: {:?}", e))
+    }
+
+    async fn get_balances(&self, game: &GameId) -> StorageResult<Vec<Balance>> {
+        self.get_balances_from_game(game).await
+    }
+
+    async fn get_transactions_page(
+        &self,
+        game: &GameId,
+        before: Option<TransactionId>,
+        limit: usize,
+    ) -> StorageResult<TransactionPage> {
+        let limit = page_limit(limit);
+        let roster = parse_roster(game, &self.get_migrated_game(game).await?)?;
+
+        // Newest first, fetching one extra item to learn whether older ones exist
+        let query = self
+            .client
+            .query()
+            .table_name(&self.transactions_table)
+            .expression_attribute_values(":game_id", AttributeValue::S(game.0.clone()))
+            .scan_index_forward(false)
+            .limit(i32::try_from(limit + 1).expect("page limits are clamped"));
+        let query = match before {
+            Some(before) => query
+                .key_condition_expression("game_id = :game_id AND transaction_num < :before")
+                .expression_attribute_values(":before", AttributeValue::N(before.0.to_string())),
+            None => query.key_condition_expression("game_id = :game_id"),
         };
+        let response = query.send().await.map_err(aws_sdk_dynamodb::Error::from)?;
 
-        // Get next transaction number and try to add with optimistic lock
-        match self.get_next_transaction_number().await {
-            Ok(transaction_number) => {
-                if let Err(e) = self
-                    .put_transaction_with_lock(&transaction, transaction_number)
-                    .await
-                {
-                    log::error!("Failed to add split transaction: {:?}", e);
-                    return;
-                }
+        let newest_first = response
+            .items
+            .unwrap_or_default()
+            .iter()
+            .map(|item| self.item_to_transaction(item, &roster))
+            .collect::<StorageResult<Vec<Transaction>>>()?;
+        Ok(TransactionPage::from_newest_first(newest_first, limit))
+    }
+
+    async fn remove_last_transaction(
+        &self,
+        game_id: &GameId,
+        void: Void,
+    ) -> StorageResult<Transaction> {
+        let activity = Activity::new(&void.by, Action::Undo, void.reason.clone());
+        self.void_latest_with_retry(game_id, Some(void), activity)
+            .await
+            .inspect_err(|e| log::error!("Failed to undo transaction: {:?}", e))
+    }
+
+    async fn correct_transaction(
+        &self,
+        game_id: &GameId,
+        id: TransactionId,
+        correction: Correction,
+        actor: &str,
+    ) -> StorageResult<Replay> {
+        let mut attempt = 1;
+        loop {
+            // Read the game before the history so a write landing in between changes the
+            // ledger we condition on and fails the correction rather than being missed.
+            let game = self.get_game_state(game_id).await?;
+            let award_table = self.get_award_table(game_id).await?;
+            let history = self.get_all_transactions(game_id).await?;
+            let rules = correction::Rules {
+                roster: &game.roster,
+                stake: &game.stake,
+                pot_policy: &game.pot_policy,
+                award_table: &award_table,
+            };
+            let replay = correction::replay(&history, id, &correction, &rules)?;
 
-                // Update game balances and next_transaction_num atomically
-                if let Err(e) = self
-                    .update_game_with_transaction(&transaction, transaction_number)
-                    .await
-                {
-                    log::error!("Failed to update game with transaction: {:?}", e);
+            let activity = correction.activity(actor, &game.roster);
+            match self
+                .commit_correction(game_id, &game, &replay, activity)
+                .await
+            {
+                Err(StorageError::Conflict(message)) if attempt < MAX_WRITE_ATTEMPTS => {
+                    log::warn!(
+                        "Correction conflict on attempt {} of {}, retrying: {}",
+                        attempt,
+                        MAX_WRITE_ATTEMPTS,
+                        message
+                    );
+                    attempt += 1;
+                }
+                result => {
+                    return result
+                        .map(|()| replay)
+                        .inspect_err(|e| log::error!("Failed to correct transaction: {:?}", e))
                 }
-            }
-            Err(e) => {
-                log::error!("Failed to get next transaction number: {:?}", e);
             }
         }
     }
 
-    async fn add_conversion(&self, name: String, split: String) {
-        let pot_balance = self
-            .get_balances_from_game()
+    async fn redo_last_undo(&self, game_id: &GameId, actor: &str) -> StorageResult<Transaction> {
+        let activity = Activity::new(actor, Action::Redo, None);
+        self.void_latest_with_retry(game_id, None, activity)
             .await
-            .iter()
-            .find(|b| b.name == "Pot")
-            .map(|b| b.amount.parse::<f64>().unwrap_or(0.0))
-            .unwrap_or(0.0);
-
-        let split_awards = self.get_split_awards().await;
-        let award_multiplier_percent = split_awards
-            .get(&split)
-            .expect(&format!("Invalid split: {}", split));
-        let award = (pot_balance * award_multiplier_percent).round() / 100.0;
-
-        let now = SystemTime::now();
-        let now: DateTime<Utc> = now.into();
-        let now_string = now.format("%-m/%-d/%Y, %l:%M:%S %p UTC").to_string();
-        let today_string = now.format("%-m/%-d/%Y").to_string();
-
-        let transaction = Transaction {
-            creditor: name,
-            debtor: "Pot".to_string(),
-            amount: award,
-            split,
-            time: now_string.clone(),
-            pot_amount: pot_balance,
-            date: today_string,
-        };
+            .inspect_err(|e| log::error!("Failed to redo transaction: {:?}", e))
+    }
 
-        // Get next transaction number and try to add with optimistic lock
-        match self.get_next_transaction_number().await {
-            Ok(transaction_number) => {
-                if let Err(e) = self
-                    .put_transaction_with_lock(&transaction, transaction_number)
-                    .await
-                {
-                    log::error!("Failed to add conversion transaction: {:?}", e);
-                    return;
-                }
+    async fn add_split(
+        &self,
+        game_id: &GameId,
+        player: &PlayerId,
+        split: Split,
+        actor: &str,
+    ) -> StorageResult<()> {
+        let now = Utc::now();
+
+        // Escalation depends on the player's earlier splits tonight
+        let since = stake::night_start(now);
+        self.add_transactions_with_retry(game_id, Some(since), |game, tonight| {
+            game.roster.require_active(player)?;
+            let (amount, stake) = game.stake.stake_for(player, now, tonight);
+            let detail = activity::describe_play(&game.roster, player, split);
+            let transaction = Transaction {
+                id: game.next_id(),
+                kind: TransactionKind::Split,
+                creditor: AccountId::Pot,
+                debtor: AccountId::Player(player.clone()),
+                amount,
+                split: Some(split),
+                time: now,
+                pot_amount: game.pot_balance(),
+                stake: Some(stake),
+                award: None,
+                voided: None,
+            };
+            let activity = Activity::new(actor, Action::Split, Some(detail));
+            Ok((vec![transaction], activity))
+        })
+        .await
+        .inspect_err(|e| log::error!("Failed to add split transaction: {:?}", e))?;
+        Ok(())
+    }
+
+    async fn add_conversion(
+        &self,
+        game_id: &GameId,
+        player: &PlayerId,
+        split: Split,
+        actor: &str,
+    ) -> StorageResult<()> {
+        let award_multiplier_percent = self
+            .get_award_table(game_id)
+            .await?
+            .percent_for(split)
+            .ok_or_else(|| StorageError::InvalidSplit(split.to_string()))?;
+
+        let now = Utc::now();
 
-                // Update game balances and next_transaction_num atomically
-                if let Err(e) = self
-                    .update_game_with_transaction(&transaction, transaction_number)
-                    .await
-                {
-                    log::error!("Failed to update game with transaction: {:?}", e);
+        // The award depends on the pot, so it is recomputed if a retry sees a new pot
+        self.add_transactions_with_retry(game_id, None, |game, _| {
+            game.roster.require_active(player)?;
+            let pot_balance = game.pot_balance();
+            let (amount, award) = game.pot_policy.award(pot_balance, award_multiplier_percent);
+            let detail = activity::describe_play(&game.roster, player, split);
+            let transaction = Transaction {
+                id: game.next_id(),
+                kind: TransactionKind::Conversion,
+                creditor: AccountId::Player(player.clone()),
+                debtor: AccountId::Pot,
+                amount,
+                split: Some(split),
+                time: now,
+                pot_amount: pot_balance,
+                stake: None,
+                award: Some(award),
+                voided: None,
+            };
+            let activity = Activity::new(actor, Action::Conversion, Some(detail));
+            Ok((vec![transaction], activity))
+        })
+        .await
+        .inspect_err(|e| log::error!("Failed to add conversion transaction: {:?}", e))?;
+        Ok(())
+    }
+
+    async fn record_settlement(
+        &self,
+        game_id: &GameId,
+        payments: Vec<Payment>,
+        actor: &str,
+    ) -> StorageResult<Vec<Transaction>> {
+        let now = Utc::now();
+        self.add_transactions_with_retry(game_id, None, |game, _| {
+            settlement::validate(&payments, &game.roster)?;
+            let transactions =
+                settlement::to_transactions(&payments, game.next_id(), now, game.pot_balance());
+            Ok((transactions, Activity::new(actor, Action::Settlement, None)))
+        })
+        .await
+        .inspect_err(|e| log::error!("Failed to record settlement: {:?}", e))
+    }
+
+    async fn get_stake_policy(&self, game: &GameId) -> StorageResult<StakePolicy> {
+        let game_item = self.get_game_or_not_found(game).await?;
+        parse_stake_policy(game, &game_item)
+    }
+
+    async fn set_stake_policy(
+        &self,
+        game: &GameId,
+        policy: StakePolicy,
+        actor: &str,
+    ) -> StorageResult<()> {
+        policy.validate()?;
+        let activity = Activity::new(actor, Action::SetStakePolicy, None);
+        self.set_game_attribute_with_retry(
+            game,
+            "stake",
+            stake_policy_to_attribute(&policy),
+            activity,
+        )
+        .await
+    }
+
+    async fn get_pot_policy(&self, game: &GameId) -> StorageResult<PotPolicy> {
+        let game_item = self.get_game_or_not_found(game).await?;
+        parse_pot_policy(game, &game_item)
+    }
+
+    async fn set_pot_policy(
+        &self,
+        game: &GameId,
+        policy: PotPolicy,
+        actor: &str,
+    ) -> StorageResult<()> {
+        policy.validate()?;
+        let activity = Activity::new(actor, Action::SetPotPolicy, None);
+        self.set_game_attribute_with_retry(
+            game,
+            "pot_policy",
+            pot_policy_to_attribute(&policy),
+            activity,
+        )
+        .await
+    }
+
+    async fn get_display_timezone(&self, game: &GameId) -> StorageResult<String> {
+        let game_item = self.get_game_or_not_found(game).await?;
+        Ok(game_item
+            .get("display_timezone")
+            .and_then(|value| value.as_s().ok())
+            .cloned()
+            .unwrap_or_else(|| "UTC".to_string()))
+    }
+
+    async fn set_display_timezone(
+        &self,
+        game: &GameId,
+        timezone: String,
+        actor: &str,
+    ) -> StorageResult<()> {
+        timestamp::validate_timezone(&timezone)?;
+        let activity = Activity::new(actor, Action::SetDisplayTimezone, Some(timezone.clone()));
+        self.set_game_attribute_with_retry(
+            game,
+            "display_timezone",
+            AttributeValue::S(timezone),
+            activity,
+        )
+        .await
+    }
+
+    async fn get_award_table(&self, game: &GameId) -> StorageResult<AwardTable> {
+        let game_item = self.get_game_or_not_found(game).await?;
+        match game_item.get("award_table") {
+            Some(value) => parse_award_table(value).ok_or_else(|| {
+                StorageError::CorruptRecord(format!("Unreadable award table: {:?}", value))
+            }),
+            None => Ok(AwardTable::standard()),
+        }
+    }
+
+    async fn set_award_table(
+        &self,
+        game: &GameId,
+        table: AwardTable,
+        actor: &str,
+    ) -> StorageResult<()> {
+        table.validate()?;
+        let activity = Activity::new(actor, Action::SetAwardTable, None);
+        self.set_game_attribute_with_retry(
+            game,
+            "award_table",
+            award_table_to_attribute(&table),
+            activity,
+        )
+        .await
+    }
+
+    async fn get_activity_log(
+        &self,
+        game: &GameId,
+        filter: &ActivityFilter,
+    ) -> StorageResult<Vec<Activity>> {
+        // An unknown game is an error rather than an empty log
+        self.get_game_or_not_found(game).await?;
+        let limit = filter.limit.unwrap_or(usize::MAX);
+        let mut matching = Vec::new();
+        let mut start_key = None;
+        loop {
+            let response = self
+                .client
+                .query()
+                .table_name(&self.activity_table)
+                .key_condition_expression("game_id = :game_id")
+                .expression_attribute_values(":game_id", AttributeValue::S(game.0.clone()))
+                .scan_index_forward(false)
+                .limit(ACTIVITY_PAGE_SIZE)
+                .set_exclusive_start_key(start_key)
+                .send()
+                .await
+                .map_err(aws_sdk_dynamodb::Error::from)?;
+            for item in response.items.unwrap_or_default() {
+                let activity = parse_activity(&item).ok_or_else(|| {
+                    StorageError::CorruptRecord(format!("Unreadable activity item: {:?}", item))
+                })?;
+                if filter.matches(&activity) {
+                    matching.push(activity);
                 }
             }
-            Err(e) => {
-                log::error!("Failed to get next transaction number: {:?}", e);
+            start_key = response.last_evaluated_key;
+            if matching.len() >= limit || start_key.is_none() {
+                matching.truncate(limit);
+                return Ok(matching);
             }
         }
     }
+}
 
-    async fn get_split_awards(&self) -> HashMap<String, f64> {
-        let mut awards = HashMap::new();
-
-        // 10% splits
-        awards.insert("2-3".to_string(), 10.0);
-        awards.insert("2-7".to_string(), 10.0);
-        awards.insert("2-9".to_string(), 10.0);
-        awards.insert("3-10".to_string(), 10.0);
-        awards.insert("3-8".to_string(), 10.0);
-        awards.insert("4-5".to_string(), 10.0);
-        awards.insert("5-6".to_string(), 10.0);
-        awards.insert("7-8".to_string(), 10.0);
-        awards.insert("8-9".to_string(), 10.0);
-        awards.insert("9-10".to_string(), 10.0);
-
-        // 30% splits
-        awards.insert("2-10".to_string(), 30.0);
-        awards.insert("2-3-10".to_string(), 30.0);
-        awards.insert("2-3-4".to_string(), 30.0);
-        awards.insert("2-3-4-10".to_string(), 30.0);
-        awards.insert("2-3-4-5".to_string(), 30.0);
-        awards.insert("2-3-4-5-10".to_string(), 30.0);
-        awards.insert("2-3-4-5-6".to_string(), 30.0);
-        awards.insert("2-3-4-5-6-10".to_string(), 30.0);
-        awards.insert("2-3-4-5-6-7".to_string(), 30.0);
-        awards.insert("2-3-4-5-6-7-10".to_string(), 30.0);
-        awards.insert("2-3-4-5-6-7-8".to_string(), 30.0);
-        awards.insert("2-3-4-5-6-7-8-10".to_string(), 30.0);
-        awards.insert("2-3-4-5-6-7-8-9".to_string(), 30.0);
-        awards.insert("2-3-4-5-6-7-8-9-10".to_string(), 30.0);
-        awards.insert("2-3-4-5-6-7-9".to_string(), 30.0);
-        awards.insert("2-3-4-5-6-7-9-10".to_string(), 30.0);
-        awards.insert("2-3-4-5-6-8".to_string(), 30.0);
-        awards.insert("2-3-4-5-6-8-10".to_string(), 30.0);
-        awards.insert("2-3-4-5-6-8-9".to_string(), 30.0);
-        awards.insert("2-3-4-5-6-8-9-10".to_string(), 30.0);
-        awards.insert("2-3-4-5-6-9".to_string(), 30.0);
-        awards.insert("2-3-4-5-6-9-10".to_string(), 30.0);
-        awards.insert("2-3-4-5-7".to_string(), 30.0);
-        awards.insert("2-3-4-5-7-10".to_string(), 30.0);
-        awards.insert("2-3-4-5-7-8".to_string(), 30.0);
-        awards.insert("2-3-4-5-7-8-10".to_string(), 30.0);
-        awards.insert("2-3-4-5-7-8-9".to_string(), 30.0);
-        awards.insert("2-3-4-5-7-8-9-10".to_string(), 30.0);
-        awards.insert("2-3-4-5-7-9".to_string(), 30.0);
-        awards.insert("2-3-4-5-7-9-10".to_string(), 30.0);
-        awards.insert("2-3-4-5-8".to_string(), 30.0);
-        awards.insert("2-3-4-5-8-10".to_string(), 30.0);
-        awards.insert("2-3-4-5-8-9".to_string(), 30.0);
-        awards.insert("2-3-4-5-8-9-10".to_string(), 30.0);
-        awards.insert("2-3-4-5-9".to_string(), 30.0);
-        awards.insert("2-3-4-5-9-10".to_string(), 30.0);
-        awards.insert("2-3-4-6".to_string(), 30.0);
-        awards.insert("2-3-4-6-10".to_string(), 30.0);
-        awards.insert("2-3-4-6-7".to_string(), 30.0);
-        awards.insert("2-3-4-6-7-10".to_string(), 30.0);
-        awards.insert("2-3-4-6-7-8".to_string(), 30.0);
-        awards.insert("2-3-4-6-7-8-10".to_string(), 30.0);
-        awards.insert("2-3-4-6-7-8-9".to_string(), 30.0);
-        awards.insert("2-3-4-6-7-8-9-10".to_string(), 30.0);
-        awards.insert("2-3-4-6-7-9".to_string(), 30.0);
-        awards.insert("2-3-4-6-7-9-10".to_string(), 30.0);
-        awards.insert("2-3-4-6-8".to_string(), 30.0);
-        awards.insert("2-3-4-6-8-10".to_string(), 30.0);
-        awards.insert("2-3-4-6-8-9".to_string(), 30.0);
-        awards.insert("2-3-4-6-8-9-10".to_string(), 30.0);
-        awards.insert("2-3-4-6-9".to_string(), 30.0);
-        awards.insert("2-3-4-6-9-10".to_string(), 30.0);
-        awards.insert("2-3-4-7".to_string(), 30.0);
-        awards.insert("2-3-4-7-10".to_string(), 30.0);
-        awards.insert("2-3-4-7-8".to_string(), 30.0);
-        awards.insert("2-3-4-7-8-10".to_string(), 30.0);
-        awards.insert("2-3-4-7-8-9".to_string(), 30.0);
-        awards.insert("2-3-4-7-8-9-10".to_string(), 30.0);
-        awards.insert("2-3-4-7-9".to_string(), 30.0);
-        awards.insert("2-3-4-7-9-10".to_string(), 30.0);
-        awards.insert("2-3-4-8".to_string(), 30.0);
-        awards.insert("2-3-4-8-10".to_string(), 30.0);
-        awards.insert("2-3-4-8-9".to_string(), 30.0);
-        awards.insert("2-3-4-8-9-10".to_string(), 30.0);
-        awards.insert("2-3-4-9".to_string(), 30.0);
-        awards.insert("2-3-4-9-10".to_string(), 30.0);
-        awards.insert("2-3-5".to_string(), 30.0);
-        awards.insert("2-3-5-10".to_string(), 30.0);
-        awards.insert("2-3-5-6".to_string(), 30.0);
-        awards.insert("2-3-5-6-10".to_string(), 30.0);
-        awards.insert("2-3-5-6-7".to_string(), 30.0);
-        awards.insert("2-3-5-6-7-10".to_string(), 30.0);
-        awards.insert("2-3-5-6-7-8".to_string(), 30.0);
-        awards.insert("2-3-5-6-7-8-10".to_string(), 30.0);
-        awards.insert("2-3-5-6-7-8-9".to_string(), 30.0);
-        awards.insert("2-3-5-6-7-8-9-10".to_string(), 30.0);
-        awards.insert("2-3-5-6-7-9".to_string(), 30.0);
-        awards.insert("2-3-5-6-7-9-10".to_string(), 30.0);
-        awards.insert("2-3-5-6-8".to_string(), 30.0);
-        awards.insert("2-3-5-6-8-10".to_string(), 30.0);
-        awards.insert("2-3-5-6-8-9".to_string(), 30.0);
-        awards.insert("2-3-5-6-8-9-10".to_string(), 30.0);
-        awards.insert("2-3-5-6-9".to_string(), 30.0);
-        awards.insert("2-3-5-6-9-10".to_string(), 30.0);
-        awards.insert("2-3-5-7".to_string(), 30.0);
-        awards.insert("2-3-5-7-10".to_string(), 30.0);
-        awards.insert("2-3-5-7-8".to_string(), 30.0);
-        awards.insert("2-3-5-7-8-10".to_string(), 30.0);
-        awards.insert("2-3-5-7-8-9".to_string(), 30.0);
-        awards.insert("2-3-5-7-8-9-10".to_string(), 30.0);
-        awards.insert("2-3-5-7-9".to_string(), 30.0);
-        awards.insert("2-3-5-7-9-10".to_string(), 30.0);
-        awards.insert("2-3-5-8".to_string(), 30.0);
-        awards.insert("2-3-5-8-10".to_string(), 30.0);
-        awards.insert("2-3-5-8-9".to_string(), 30.0);
-        awards.insert("2-3-5-8-9-10".to_string(), 30.0);
-        awards.insert("2-3-5-9".to_string(), 30.0);
-        awards.insert("2-3-5-9-10".to_string(), 30.0);
-        awards.insert("2-3-6".to_string(), 30.0);
-        awards.insert("2-3-6-10".to_string(), 30.0);
-        awards.insert("2-3-6-7".to_string(), 30.0);
-        awards.insert("2-3-6-7-10".to_string(), 30.0);
-        awards.insert("2-3-6-7-8".to_string(), 30.0);
-        awards.insert("2-3-6-7-8-10".to_string(), 30.0);
-        awards.insert("2-3-6-7-8-9".to_string(), 30.0);
-        awards.insert("2-3-6-7-8-9-10".to_string(), 30.0);
-        awards.insert("2-3-6-7-9".to_string(), 30.0);
-        awards.insert("2-3-6-7-9-10".to_string(), 30.0);
-        awards.insert("2-3-6-8".to_string(), 30.0);
-        awards.insert("2-3-6-8-10".to_string(), 30.0);
-        awards.insert("2-3-6-8-9".to_string(), 30.0);
-        awards.insert("2-3-6-8-9-10".to_string(), 30.0);
-        awards.insert("2-3-6-9".to_string(), 30.0);
-        awards.insert("2-3-6-9-10".to_string(), 30.0);
-        awards.insert("2-3-7".to_string(), 30.0);
-        awards.insert("2-3-7-10".to_string(), 30.0);
-        awards.insert("2-3-7-8".to_string(), 30.0);
-        awards.insert("2-3-7-8-10".to_string(), 30.0);
-        awards.insert("2-3-7-8-9".to_string(), 30.0);
-        awards.insert("2-3-7-8-9-10".to_string(), 30.0);
-        awards.insert("2-3-7-9".to_string(), 30.0);
-        awards.insert("2-3-7-9-10".to_string(), 30.0);
-        awards.insert("2-3-8".to_string(), 30.0);
-        awards.insert("2-3-8-10".to_string(), 30.0);
-        awards.insert("2-3-8-9".to_string(), 30.0);
-        awards.insert("2-3-8-9-10".to_string(), 30.0);
-        awards.insert("2-3-9".to_string(), 30.0);
-        awards.insert("2-3-9-10".to_string(), 30.0);
-        awards.insert("2-4-10".to_string(), 30.0);
-        awards.insert("2-4-5-10".to_string(), 30.0);
-        awards.insert("2-4-5-6".to_string(), 30.0);
-        awards.insert("2-4-5-6-10".to_string(), 30.0);
-        awards.insert("2-4-5-6-7".to_string(), 30.0);
-        awards.insert("2-4-5-6-7-10".to_string(), 30.0);
-        awards.insert("2-4-5-6-7-8".to_string(), 30.0);
-        awards.insert("2-4-5-6-7-8-10".to_string(), 30.0);
-        awards.insert("2-4-5-6-7-8-9".to_string(), 30.0);
-        awards.insert("2-4-5-6-7-8-9-10".to_string(), 30.0);
-        awards.insert("2-4-5-6-7-9".to_string(), 30.0);
-        awards.insert("2-4-5-6-7-9-10".to_string(), 30.0);
-        awards.insert("2-4-5-6-8".to_string(), 30.0);
-        awards.insert("2-4-5-6-8-10".to_string(), 30.0);
-        awards.insert("2-4-5-6-8-9".to_string(), 30.0);
-        awards.insert("2-4-5-6-8-9-10".to_string(), 30.0);
-        awards.insert("2-4-5-6-9".to_string(), 30.0);
-        awards.insert("2-4-5-6-9-10".to_string(), 30.0);
-        awards.insert("2-4-5-7-10".to_string(), 30.0);
-        awards.insert("2-4-5-7-8-10".to_string(), 30.0);
-        awards.insert("2-4-5-7-8-9-10".to_string(), 30.0);
-        awards.insert("2-4-5-7-9-10".to_string(), 30.0);
-        awards.insert("2-4-5-8-10".to_string(), 30.0);
-        awards.insert("2-4-5-8-9-10".to_string(), 30.0);
-        awards.insert("2-4-5-9-10".to_string(), 30.0);
-        awards.insert("2-4-6".to_string(), 30.0);
-        awards.insert("2-4-6-10".to_string(), 30.0);
-        awards.insert("2-4-6-7".to_string(), 30.0);
-        awards.insert("2-4-6-7-10".to_string(), 30.0);
-        awards.insert("2-4-6-7-8".to_string(), 30.0);
-        awards.insert("2-4-6-7-8-10".to_string(), 30.0);
-        awards.insert("2-4-6-7-8-9".to_string(), 30.0);
-        awards.insert("2-4-6-7-8-9-10".to_string(), 30.0);
-        awards.insert("2-4-6-7-9".to_string(), 30.0);
-        awards.insert("2-4-6-7-9-10".to_string(), 30.0);
-        awards.insert("2-4-6-8".to_string(), 30.0);
-        awards.insert("2-4-6-8-10".to_string(), 30.0);
-        awards.insert("2-4-6-8-9".to_string(), 30.0);
-        awards.insert("2-4-6-8-9-10".to_string(), 30.0);
-        awards.insert("2-4-6-9".to_string(), 30.0);
-        awards.insert("2-4-6-9-10".to_string(), 30.0);
-        awards.insert("2-4-7-10".to_string(), 30.0);
-        awards.insert("2-4-7-8-10".to_string(), 30.0);
-        awards.insert("2-4-7-8-9".to_string(), 30.0);
-        awards.insert("2-4-7-8-9-10".to_string(), 30.0);
-        awards.insert("2-4-7-9".to_string(), 30.0);
-        awards.insert("2-4-7-9-10".to_string(), 30.0);
-        awards.insert("2-4-8-10".to_string(), 30.0);
-        awards.insert("2-4-8-9".to_string(), 30.0);
-        awards.insert("2-4-8-9-10".to_string(), 30.0);
-        awards.insert("2-4-9".to_string(), 30.0);
-        awards.insert("2-4-9-10".to_string(), 30.0);
-        awards.insert("2-5-10".to_string(), 30.0);
-        awards.insert("2-5-6".to_string(), 30.0);
-        awards.insert("2-5-6-10".to_string(), 30.0);
-        awards.insert("2-5-6-7".to_string(), 30.0);
-        awards.insert("2-5-6-7-10".to_string(), 30.0);
-        awards.insert("2-5-6-7-8".to_string(), 30.0);
-        awards.insert("2-5-6-7-8-10".to_string(), 30.0);
-        awards.insert("2-5-6-7-8-9".to_string(), 30.0);
-        awards.insert("2-5-6-7-8-9-10".to_string(), 30.0);
-        awards.insert("2-5-6-7-9".to_string(), 30.0);
-        awards.insert("2-5-6-7-9-10".to_string(), 30.0);
-        awards.insert("2-5-6-8".to_string(), 30.0);
-        awards.insert("2-5-6-8-10".to_string(), 30.0);
-        awards.insert("2-5-6-8-9".to_string(), 30.0);
-        awards.insert("2-5-6-8-9-10".to_string(), 30.0);
-        awards.insert("2-5-6-9".to_string(), 30.0);
-        awards.insert("2-5-6-9-10".to_string(), 30.0);
-        awards.insert("2-5-7".to_string(), 30.0);
-        awards.insert("2-5-7-10".to_string(), 30.0);
-        awards.insert("2-5-7-8".to_string(), 30.0);
-        awards.insert("2-5-7-8-10".to_string(), 30.0);
-        awards.insert("2-5-7-8-9".to_string(), 30.0);
-        awards.insert("2-5-7-8-9-10".to_string(), 30.0);
-        awards.insert("2-5-7-9".to_string(), 30.0);
-        awards.insert("2-5-7-9-10".to_string(), 30.0);
-        awards.insert("2-5-8-10".to_string(), 30.0);
-        awards.insert("2-5-8-9-10".to_string(), 30.0);
-        awards.insert("2-5-9-10".to_string(), 30.0);
-        awards.insert("2-6".to_string(), 30.0);
-        awards.insert("2-6-10".to_string(), 30.0);
-        awards.insert("2-6-7".to_string(), 30.0);
-        awards.insert("2-6-7-10".to_string(), 30.0);
-        awards.insert("2-6-7-8".to_string(), 30.0);
-        awards.insert("2-6-7-8-10".to_string(), 30.0);
-        awards.insert("2-6-7-8-9".to_string(), 30.0);
-        awards.insert("2-6-7-8-9-10".to_string(), 30.0);
-        awards.insert("2-6-7-9".to_string(), 30.0);
-        awards.insert("2-6-7-9-10".to_string(), 30.0);
-        awards.insert("2-6-8".to_string(), 30.0);
-        awards.insert("2-6-8-10".to_string(), 30.0);
-        awards.insert("2-6-8-9".to_string(), 30.0);
-        awards.insert("2-6-8-9-10".to_string(), 30.0);
-        awards.insert("2-6-9".to_string(), 30.0);
-        awards.insert("2-6-9-10".to_string(), 30.0);
-        awards.insert("2-7-10".to_string(), 30.0);
-        awards.insert("2-7-8".to_string(), 30.0);
-        awards.insert("2-7-8-10".to_string(), 30.0);
-        awards.insert("2-7-8-9".to_string(), 30.0);
-        awards.insert("2-7-8-9-10".to_string(), 30.0);
-        awards.insert("2-7-9".to_string(), 30.0);
-        awards.insert("2-7-9-10".to_string(), 30.0);
-        awards.insert("2-8-9".to_string(), 30.0);
-        awards.insert("2-8-9-10".to_string(), 30.0);
-        awards.insert("2-9-10".to_string(), 30.0);
-        awards.insert("3-4".to_string(), 30.0);
-        awards.insert("3-4-10".to_string(), 30.0);
-        awards.insert("3-4-5".to_string(), 30.0);
-        awards.insert("3-4-5-10".to_string(), 30.0);
-        awards.insert("3-4-5-6".to_string(), 30.0);
-        awards.insert("3-4-5-6-10".to_string(), 30.0);
-        awards.insert("3-4-5-6-7".to_string(), 30.0);
-        awards.insert("3-4-5-6-7-10".to_string(), 30.0);
-        awards.insert("3-4-5-6-7-8".to_string(), 30.0);
-        awards.insert("3-4-5-6-7-8-10".to_string(), 30.0);
-        awards.insert("3-4-5-6-7-8-9".to_string(), 30.0);
-        awards.insert("3-4-5-6-7-8-9-10".to_string(), 30.0);
-        awards.insert("3-4-5-6-7-9".to_string(), 30.0);
-        awards.insert("3-4-5-6-7-9-10".to_string(), 30.0);
-        awards.insert("3-4-5-6-8".to_string(), 30.0);
-        awards.insert("3-4-5-6-8-10".to_string(), 30.0);
-        awards.insert("3-4-5-6-8-9".to_string(), 30.0);
-        awards.insert("3-4-5-6-8-9-10".to_string(), 30.0);
-        awards.insert("3-4-5-6-9".to_string(), 30.0);
-        awards.insert("3-4-5-6-9-10".to_string(), 30.0);
-        awards.insert("3-4-5-7".to_string(), 30.0);
-        awards.insert("3-4-5-7-10".to_string(), 30.0);
-        awards.insert("3-4-5-7-8".to_string(), 30.0);
-        awards.insert("3-4-5-7-8-10".to_string(), 30.0);
-        awards.insert("3-4-5-7-8-9".to_string(), 30.0);
-        awards.insert("3-4-5-7-8-9-10".to_string(), 30.0);
-        awards.insert("3-4-5-7-9".to_string(), 30.0);
-        awards.insert("3-4-5-7-9-10".to_string(), 30.0);
-        awards.insert("3-4-5-8".to_string(), 30.0);
-        awards.insert("3-4-5-8-10".to_string(), 30.0);
-        awards.insert("3-4-5-8-9".to_string(), 30.0);
-        awards.insert("3-4-5-8-9-10".to_string(), 30.0);
-        awards.insert("3-4-5-9".to_string(), 30.0);
-        awards.insert("3-4-5-9-10".to_string(), 30.0);
-        awards.insert("3-4-6".to_string(), 30.0);
-        awards.insert("3-4-6-10".to_string(), 30.0);
-        awards.insert("3-4-6-7".to_string(), 30.0);
-        awards.insert("3-4-6-7-10".to_string(), 30.0);
-        awards.insert("3-4-6-7-8".to_string(), 30.0);
-        awards.insert("3-4-6-7-8-10".to_string(), 30.0);
-        awards.insert("3-4-6-7-8-9".to_string(), 30.0);
-        awards.insert("3-4-6-7-8-9-10".to_string(), 30.0);
-        awards.insert("3-4-6-7-9".to_string(), 30.0);
-        awards.insert("3-4-6-7-9-10".to_string(), 30.0);
-        awards.insert("3-4-6-8".to_string(), 30.0);
-        awards.insert("3-4-6-8-10".to_string(), 30.0);
-        awards.insert("3-4-6-8-9".to_string(), 30.0);
-        awards.insert("3-4-6-8-9-10".to_string(), 30.0);
-        awards.insert("3-4-6-9".to_string(), 30.0);
-        awards.insert("3-4-6-9-10".to_string(), 30.0);
-        awards.insert("3-4-7".to_string(), 30.0);
-        awards.insert("3-4-7-10".to_string(), 30.0);
-        awards.insert("3-4-7-8".to_string(), 30.0);
-        awards.insert("3-4-7-8-10".to_string(), 30.0);
-        awards.insert("3-4-7-8-9".to_string(), 30.0);
-        awards.insert("3-4-7-8-9-10".to_string(), 30.0);
-        awards.insert("3-4-7-9".to_string(), 30.0);
-        awards.insert("3-4-7-9-10".to_string(), 30.0);
-        awards.insert("3-4-8".to_string(), 30.0);
-        awards.insert("3-4-8-10".to_string(), 30.0);
-        awards.insert("3-4-8-9".to_string(), 30.0);
-        awards.insert("3-4-8-9-10".to_string(), 30.0);
-        awards.insert("3-4-9".to_string(), 30.0);
-        awards.insert("3-4-9-10".to_string(), 30.0);
-        awards.insert("3-5-10".to_string(), 30.0);
-        awards.insert("3-5-6-7".to_string(), 30.0);
-        awards.insert("3-5-6-7-10".to_string(), 30.0);
-        awards.insert("3-5-6-7-8".to_string(), 30.0);
-        awards.insert("3-5-6-7-8-10".to_string(), 30.0);
-        awards.insert("3-5-6-7-8-9".to_string(), 30.0);
-        awards.insert("3-5-6-7-8-9-10".to_string(), 30.0);
-        awards.insert("3-5-6-7-9".to_string(), 30.0);
-        awards.insert("3-5-6-7-9-10".to_string(), 30.0);
-        awards.insert("3-5-7".to_string(), 30.0);
-        awards.insert("3-5-7-10".to_string(), 30.0);
-        awards.insert("3-5-7-8".to_string(), 30.0);
-        awards.insert("3-5-7-8-10".to_string(), 30.0);
-        awards.insert("3-5-7-8-9".to_string(), 30.0);
-        awards.insert("3-5-7-8-9-10".to_string(), 30.0);
-        awards.insert("3-5-7-9".to_string(), 30.0);
-        awards.insert("3-5-7-9-10".to_string(), 30.0);
-        awards.insert("3-5-8-10".to_string(), 30.0);
-        awards.insert("3-5-8-9-10".to_string(), 30.0);
-        awards.insert("3-5-9-10".to_string(), 30.0);
-        awards.insert("3-6-7".to_string(), 30.0);
-        awards.insert("3-6-7-10".to_string(), 30.0);
-        awards.insert("3-6-7-8".to_string(), 30.0);
-        awards.insert("3-6-7-8-10".to_string(), 30.0);
-        awards.insert("3-6-7-8-9".to_string(), 30.0);
-        awards.insert("3-6-7-8-9-10".to_string(), 30.0);
-        awards.insert("3-6-7-9".to_string(), 30.0);
-        awards.insert("3-6-7-9-10".to_string(), 30.0);
-        awards.insert("3-6-8".to_string(), 30.0);
-        awards.insert("3-6-8-10".to_string(), 30.0);
-        awards.insert("3-6-8-9".to_string(), 30.0);
-        awards.insert("3-6-8-9-10".to_string(), 30.0);
-        awards.insert("3-7".to_string(), 30.0);
-        awards.insert("3-7-10".to_string(), 30.0);
-        awards.insert("3-7-8".to_string(), 30.0);
-        awards.insert("3-7-8-10".to_string(), 30.0);
-        awards.insert("3-7-8-9".to_string(), 30.0);
-        awards.insert("3-7-8-9-10".to_string(), 30.0);
-        awards.insert("3-7-9-10".to_string(), 30.0);
-        awards.insert("3-8-10".to_string(), 30.0);
-        awards.insert("3-8-9".to_string(), 30.0);
-        awards.insert("3-8-9-10".to_string(), 30.0);
-        awards.insert("3-9-10".to_string(), 30.0);
-        awards.insert("4-10".to_string(), 30.0);
-        awards.insert("4-5-10".to_string(), 30.0);
-        awards.insert("4-5-6".to_string(), 30.0);
-        awards.insert("4-5-6-10".to_string(), 30.0);
-        awards.insert("4-5-6-7".to_string(), 30.0);
-        awards.insert("4-5-6-7-10".to_string(), 30.0);
-        awards.insert("4-5-6-7-8".to_string(), 30.0);
-        awards.insert("4-5-6-7-8-10".to_string(), 30.0);
-        awards.insert("4-5-6-7-8-9".to_string(), 30.0);
-        awards.insert("4-5-6-7-8-9-10".to_string(), 30.0);
-        awards.insert("4-5-6-7-9".to_string(), 30.0);
-        awards.insert("4-5-6-7-9-10".to_string(), 30.0);
-        awards.insert("4-5-6-8".to_string(), 30.0);
-        awards.insert("4-5-6-8-10".to_string(), 30.0);
-        awards.insert("4-5-6-8-9".to_string(), 30.0);
-        awards.insert("4-5-6-8-9-10".to_string(), 30.0);
-        awards.insert("4-5-6-9".to_string(), 30.0);
-        awards.insert("4-5-6-9-10".to_string(), 30.0);
-        awards.insert("4-5-7".to_string(), 30.0);
-        awards.insert("4-5-7-10".to_string(), 30.0);
-        awards.insert("4-5-7-8".to_string(), 30.0);
-        awards.insert("4-5-7-8-10".to_string(), 30.0);
-        awards.insert("4-5-7-8-9".to_string(), 30.0);
-        awards.insert("4-5-7-8-9-10".to_string(), 30.0);
-        awards.insert("4-5-7-9".to_string(), 30.0);
-        awards.insert("4-5-7-9-10".to_string(), 30.0);
-        awards.insert("4-5-8".to_string(), 30.0);
-        awards.insert("4-5-8-10".to_string(), 30.0);
-        awards.insert("4-5-8-9".to_string(), 30.0);
-        awards.insert("4-5-8-9-10".to_string(), 30.0);
-        awards.insert("4-5-9".to_string(), 30.0);
-        awards.insert("4-5-9-10".to_string(), 30.0);
-        awards.insert("4-6-7-8".to_string(), 30.0);
-        awards.insert("4-6-7-8-9".to_string(), 30.0);
-        awards.insert("4-6-7-8-9-10".to_string(), 30.0);
-        awards.insert("4-6-7-9".to_string(), 30.0);
-        awards.insert("4-6-8".to_string(), 30.0);
-        awards.insert("4-6-8-10".to_string(), 30.0);
-        awards.insert("4-6-8-9".to_string(), 30.0);
-        awards.insert("4-6-8-9-10".to_string(), 30.0);
-        awards.insert("4-6-9".to_string(), 30.0);
-        awards.insert("4-6-9-10".to_string(), 30.0);
-        awards.insert("4-7-10".to_string(), 30.0);
-        awards.insert("4-7-8-10".to_string(), 30.0);
-        awards.insert("4-7-8-9".to_string(), 30.0);
-        awards.insert("4-7-8-9-10".to_string(), 30.0);
-        awards.insert("4-7-9".to_string(), 30.0);
-        awards.insert("4-7-9-10".to_string(), 30.0);
-        awards.insert("4-8-10".to_string(), 30.0);
-        awards.insert("4-8-9".to_string(), 30.0);
-        awards.insert("4-8-9-10".to_string(), 30.0);
-        awards.insert("4-9".to_string(), 30.0);
-        awards.insert("4-9-10".to_string(), 30.0);
-        awards.insert("5-10".to_string(), 30.0);
-        awards.insert("5-6-10".to_string(), 30.0);
-        awards.insert("5-6-7".to_string(), 30.0);
-        awards.insert("5-6-7-10".to_string(), 30.0);
-        awards.insert("5-6-7-8".to_string(), 30.0);
-        awards.insert("5-6-7-8-10".to_string(), 30.0);
-        awards.insert("5-6-7-8-9".to_string(), 30.0);
-        awards.insert("5-6-7-8-9-10".to_string(), 30.0);
-        awards.insert("5-6-7-9".to_string(), 30.0);
-        awards.insert("5-6-7-9-10".to_string(), 30.0);
-        awards.insert("5-6-8".to_string(), 30.0);
-        awards.insert("5-6-8-10".to_string(), 30.0);
-        awards.insert("5-6-8-9".to_string(), 30.0);
-        awards.insert("5-6-8-9-10".to_string(), 30.0);
-        awards.insert("5-6-9".to_string(), 30.0);
-        awards.insert("5-6-9-10".to_string(), 30.0);
-        awards.insert("5-7".to_string(), 30.0);
-        awards.insert("5-7-10".to_string(), 30.0);
-        awards.insert("5-7-8".to_string(), 30.0);
-        awards.insert("5-7-8-10".to_string(), 30.0);
-        awards.insert("5-7-8-9".to_string(), 30.0);
-        awards.insert("5-7-8-9-10".to_string(), 30.0);
-        awards.insert("5-7-9".to_string(), 30.0);
-        awards.insert("5-7-9-10".to_string(), 30.0);
-        awards.insert("5-8-10".to_string(), 30.0);
-        awards.insert("5-8-9-10".to_string(), 30.0);
-        awards.insert("5-9-10".to_string(), 30.0);
-        awards.insert("6-7".to_string(), 30.0);
-        awards.insert("6-7-10".to_string(), 30.0);
-        awards.insert("6-7-8".to_string(), 30.0);
-        awards.insert("6-7-8-10".to_string(), 30.0);
-        awards.insert("6-7-8-9".to_string(), 30.0);
-        awards.insert("6-7-8-9-10".to_string(), 30.0);
-        awards.insert("6-7-9".to_string(), 30.0);
-        awards.insert("6-7-9-10".to_string(), 30.0);
-        awards.insert("6-8".to_string(), 30.0);
-        awards.insert("6-8-10".to_string(), 30.0);
-        awards.insert("6-8-9".to_string(), 30.0);
-        awards.insert("6-8-9-10".to_string(), 30.0);
-        awards.insert("7-8-10".to_string(), 30.0);
-        awards.insert("7-8-9".to_string(), 30.0);
-        awards.insert("7-8-9-10".to_string(), 30.0);
-        awards.insert("7-9-10".to_string(), 30.0);
-        awards.insert("8-9-10".to_string(), 30.0);
-
-        // 50% splits
-        awards.insert("2-8-10".to_string(), 50.0);
-        awards.insert("3-7-9".to_string(), 50.0);
-        awards.insert("4-6".to_string(), 50.0);
-        awards.insert("4-6-10".to_string(), 50.0);
-        awards.insert("4-6-7".to_string(), 50.0);
-        awards.insert("4-6-7-10".to_string(), 50.0);
-        awards.insert("4-6-7-8-10".to_string(), 50.0);
-        awards.insert("4-6-7-9-10".to_string(), 50.0);
-        awards.insert("7-10".to_string(), 50.0);
-        awards.insert("7-9".to_string(), 50.0);
-        awards.insert("8-10".to_string(), 50.0);
-
-        awards
+#[cfg(test)]
+impl DynamoDbDao {
+    /// Creates this DAO's games, transactions and activity tables, for tests against a local
+    /// endpoint.
+    pub(crate) async fn create_tables(&self) -> StorageResult<()> {
+        use aws_sdk_dynamodb::types::{
+            AttributeDefinition, BillingMode, KeySchemaElement, KeyType, ScalarAttributeType,
+        };
+        let attribute = |name: &str, kind: ScalarAttributeType| {
+            AttributeDefinition::builder()
+                .attribute_name(name)
+                .attribute_type(kind)
+                .build()
+                .map_err(|e| StorageError::BackendUnavailable(e.to_string()))
+        };
+        let key = |name: &str, kind: KeyType| {
+            KeySchemaElement::builder()
+                .attribute_name(name)
+                .key_type(kind)
+                .build()
+                .map_err(|e| StorageError::BackendUnavailable(e.to_string()))
+        };
+
+        self.client
+            .create_table()
+            .table_name(&self.games_table)
+            .attribute_definitions(attribute("game_id", ScalarAttributeType::S)?)
+            .key_schema(key("game_id", KeyType::Hash)?)
+            .billing_mode(BillingMode::PayPerRequest)
+            .send()
+            .await
+            .map_err(aws_sdk_dynamodb::Error::from)?;
+        self.client
+            .create_table()
+            .table_name(&self.transactions_table)
+            .attribute_definitions(attribute("game_id", ScalarAttributeType::S)?)
+            .attribute_definitions(attribute("transaction_num", ScalarAttributeType::N)?)
+            .key_schema(key("game_id", KeyType::Hash)?)
+            .key_schema(key("transaction_num", KeyType::Range)?)
+            .billing_mode(BillingMode::PayPerRequest)
+            .send()
+            .await
+            .map_err(aws_sdk_dynamodb::Error::from)?;
+        self.client
+            .create_table()
+            .table_name(&self.activity_table)
+            .attribute_definitions(attribute("game_id", ScalarAttributeType::S)?)
+            .attribute_definitions(attribute("activity_num", ScalarAttributeType::N)?)
+            .key_schema(key("game_id", KeyType::Hash)?)
+            .key_schema(key("activity_num", KeyType::Range)?)
+            .billing_mode(BillingMode::PayPerRequest)
+            .send()
+            .await
+            .map_err(aws_sdk_dynamodb::Error::from)?;
+        Ok(())
     }
 }
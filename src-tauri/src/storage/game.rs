@@ -0,0 +1,109 @@
+use super::{AwardTable, PotPolicy, Roster, StakePolicy, StorageError, StorageResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Identifies a game, i.e. one ledger with its own players, pot and settings, within a
+/// backend.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(transparent)]
+pub struct GameId(pub String);
+
+impl GameId {
+    /// A readable id for a new game: the name as a slug plus the creation time in base 36,
+    /// e.g. `tuesday-league-m5x2k1q0`.
+    pub fn generate(name: &str, now: DateTime<Utc>) -> Self {
+        let mut slug = String::new();
+        for c in name.chars().flat_map(char::to_lowercase) {
+            if c.is_ascii_alphanumeric() {
+                slug.push(c);
+            } else if !slug.is_empty() && !slug.ends_with('-') {
+                slug.push('-');
+            }
+        }
+        slug.truncate(32);
+        let slug = slug.trim_end_matches('-');
+        let slug = if slug.is_empty() { "game" } else { slug };
+        GameId(format!(
+            "{}-{}",
+            slug,
+            to_base36(now.timestamp_millis() as u64)
+        ))
+    }
+}
+
+fn to_base36(mut value: u64) -> String {
+    const DIGITS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+    let mut digits = Vec::new();
+    loop {
+        digits.push(DIGITS[(value % 36) as usize]);
+        value /= 36;
+        if value == 0 {
+            break;
+        }
+    }
+    digits.reverse();
+    String::from_utf8(digits).expect("base 36 digits are ASCII")
+}
+
+impl fmt::Display for GameId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// The game existing installs used before games could be selected; selected until the
+/// user picks another.
+pub const DEFAULT_GAME_ID: &str = "sample_game_id";
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct GameSummary {
+    pub id: GameId,
+    pub name: String,
+}
+
+/// Everything needed to start a game. The pot starts empty and every player at zero.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct NewGame {
+    pub name: String,
+    pub players: Vec<String>,
+    /// What a player pays into the pot for each split; a bare amount such as `"2.00"`
+    /// is a flat stake. Player multipliers refer to ids in the order players are listed.
+    pub stake: StakePolicy,
+    /// Limits on conversion awards; none by default.
+    #[serde(default)]
+    pub pot_policy: PotPolicy,
+    /// Defaults to [`AwardTable::standard`].
+    #[serde(default)]
+    pub award_table: AwardTable,
+}
+
+impl NewGame {
+    /// Rejects a blank name, games without players, player names [`Roster::add`] would
+    /// refuse (blank or duplicate), invalid stakes, pot policies and award tables.
+    pub fn validate(&self) -> StorageResult<()> {
+        if self.name.trim().is_empty() {
+            return Err(StorageError::InvalidConfig(
+                "The game needs a name".to_string(),
+            ));
+        }
+        if self.players.is_empty() {
+            return Err(StorageError::InvalidConfig(
+                "The game needs at least one player".to_string(),
+            ));
+        }
+        self.roster()?;
+        self.stake.validate()?;
+        self.pot_policy.validate()?;
+        self.award_table.validate()
+    }
+
+    /// The starting roster, with ids assigned in the order the players are listed.
+    pub fn roster(&self) -> StorageResult<Roster> {
+        let mut roster = Roster::default();
+        for player in &self.players {
+            roster.add(player)?;
+        }
+        Ok(roster)
+    }
+}
@@ -0,0 +1,341 @@
+//! Where the pins stand in the rack and what that means for a leave.
+//!
+//! Functions here take the standing pins as a slice so they also work for leaves that
+//! aren't a [`Split`], such as washouts with the headpin still up.
+
+use super::award_table::{AwardTable, AwardTier};
+use super::split::{HEADPIN, PIN_COUNT};
+use super::{Split, StorageError, StorageResult};
+use serde::Serialize;
+
+/// `(column, row)` of each pin seen from the foul line. Rows count back from the headpin;
+/// columns are offsets from the headpin in half pin spacings, so neighbours in a row are
+/// two columns apart.
+const POSITIONS: [(i8, i8); PIN_COUNT as usize] = [
+    (0, 0),
+    (-1, 1),
+    (1, 1),
+    (-2, 2),
+    (0, 2),
+    (2, 2),
+    (-3, 3),
+    (-1, 3),
+    (1, 3),
+    (3, 3),
+];
+
+/// Difficulty below which a split is suggested as a 10% award.
+const EASY_DIFFICULTY: f64 = 2.0;
+/// Difficulty from which a split is suggested as a 50% award.
+const HARD_DIFFICULTY: f64 = 5.5;
+
+pub fn position(pin: u8) -> (i8, i8) {
+    POSITIONS[(pin - 1) as usize]
+}
+
+/// Whether `back` is in the shadow of `front`: further back and within the triangle a
+/// ball deflecting off `front` can reach.
+fn is_behind(front: u8, back: u8) -> bool {
+    let (front_column, front_row) = position(front);
+    let (back_column, back_row) = position(back);
+    back_row > front_row && (back_column - front_column).abs() <= back_row - front_row
+}
+
+/// Whether `middle` lies on the straight line strictly between pins `a` and `b`.
+fn is_between(a: u8, b: u8, middle: u8) -> bool {
+    let (ax, ay) = position(a);
+    let (bx, by) = position(b);
+    let (mx, my) = position(middle);
+    let cross = (bx - ax) * (my - ay) - (by - ay) * (mx - ax);
+    let dot = (mx - ax) * (bx - ax) + (my - ay) * (by - ay);
+    let length_squared = (bx - ax) * (bx - ax) + (by - ay) * (by - ay);
+    cross == 0 && dot > 0 && dot < length_squared
+}
+
+/// The neighbouring occupied columns with the most empty columns between them.
+fn widest_gap(pins: &[u8]) -> Option<(i8, i8)> {
+    let mut columns: Vec<i8> = pins.iter().map(|&pin| position(pin).0).collect();
+    columns.sort_unstable();
+    columns.dedup();
+    columns
+        .windows(2)
+        .map(|pair| (pair[0], pair[1]))
+        .max_by_key(|(left, right)| right - left)
+}
+
+/// Widest run of empty columns between standing pins: 1 for 2-3 or 8-9, 5 for 7-10.
+pub fn lateral_gap(pins: &[u8]) -> u8 {
+    match widest_gap(pins) {
+        Some((left, right)) => (right - left - 1) as u8,
+        None => 0,
+    }
+}
+
+/// Rows between the front-most and back-most standing pins; 0 when all share a row.
+pub fn row_span(pins: &[u8]) -> u8 {
+    let rows = pins.iter().map(|&pin| position(pin).1);
+    match (rows.clone().min(), rows.max()) {
+        (Some(front), Some(back)) => (back - front) as u8,
+        _ => 0,
+    }
+}
+
+/// Row of the front-most standing pin among those in the matching columns.
+fn front_row(pins: &[u8], in_columns: impl Fn(i8) -> bool) -> Option<i8> {
+    pins.iter()
+        .map(|&pin| position(pin))
+        .filter(|&(column, _)| in_columns(column))
+        .map(|(_, row)| row)
+        .min()
+}
+
+/// Whether one standing pin is in front of all the others with nothing knocked down
+/// between it and any of them, like the 2-8 sleeper or the 2-4-5 cluster. Such leaves
+/// aren't really splits: hitting the lead pin can take out the rest.
+pub fn is_cluster(pins: &[u8]) -> bool {
+    pins.iter().any(|&lead| {
+        pins.iter().all(|&other| {
+            other == lead
+                || (is_behind(lead, other)
+                    && !(1..=PIN_COUNT)
+                        .any(|down| !pins.contains(&down) && is_between(lead, other, down)))
+        })
+    })
+}
+
+/// Pins hidden directly behind another standing pin, like the 8 in 2-8.
+fn sleepers(pins: &[u8]) -> Vec<u8> {
+    pins.iter()
+        .copied()
+        .filter(|&back| {
+            let (column, row) = position(back);
+            pins.iter()
+                .any(|&front| position(front) == (column, row - 2))
+        })
+        .collect()
+}
+
+/// Whether the leave is a cluster built around a diamond such as 2-4-5-8: a pin, both
+/// pins behind it and the one behind those.
+fn is_bucket(pins: &[u8]) -> bool {
+    let standing = |column: i8, row: i8| pins.iter().any(|&pin| position(pin) == (column, row));
+    is_cluster(pins)
+        && pins.iter().any(|&front| {
+            let (column, row) = position(front);
+            standing(column - 1, row + 1)
+                && standing(column + 1, row + 1)
+                && standing(column, row + 2)
+        })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaveKind {
+    /// Headpin down and the standing pins can't all be reached through one of them.
+    Split,
+    /// Like a split, but with the headpin still standing (1-2-10).
+    Washout,
+    /// One pin shields the rest (see [`is_cluster`]); not a split at all.
+    Cluster,
+}
+
+/// What the pin layout of a leave says about it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SplitGeometry {
+    /// Standing pins in ascending order.
+    pub pins: Vec<u8>,
+    pub kind: LeaveKind,
+    /// A split of two pins with a single column between them, like 2-3 or 3-10.
+    pub baby_split: bool,
+    /// A split with three or more empty columns to cover, like 4-6 or 7-10.
+    pub wide_split: bool,
+    /// A cluster around a diamond, like the 2-4-5-8 bucket.
+    pub bucket: bool,
+    pub sleepers: Vec<u8>,
+    pub lateral_gap: u8,
+    pub row_span: u8,
+    /// Rough 0–10 rating of how hard the leave is to convert.
+    pub difficulty: f64,
+    /// Award percentage a table could use for this split, `None` for non-splits.
+    pub suggested_percent: Option<f64>,
+}
+
+/// Describes a leave of two or more pins; the headpin may be standing.
+pub fn classify(pins: &[u8]) -> StorageResult<SplitGeometry> {
+    if let Some(pin) = pins.iter().find(|pin| !(1..=PIN_COUNT).contains(*pin)) {
+        return Err(StorageError::InvalidSplit(format!(
+            "Pin {} is not between 1 and {}",
+            pin, PIN_COUNT
+        )));
+    }
+    let mut pins = pins.to_vec();
+    pins.sort_unstable();
+    pins.dedup();
+    if pins.len() < 2 {
+        return Err(StorageError::InvalidSplit(
+            "A leave needs at least two pins".to_string(),
+        ));
+    }
+
+    let kind = if is_cluster(&pins) {
+        LeaveKind::Cluster
+    } else if pins.contains(&HEADPIN) {
+        LeaveKind::Washout
+    } else {
+        LeaveKind::Split
+    };
+    let gap = lateral_gap(&pins);
+    let sleepers = sleepers(&pins);
+    let difficulty = difficulty(&pins, sleepers.len());
+    let suggested_percent = (kind == LeaveKind::Split).then(|| suggested_percent(difficulty));
+
+    Ok(SplitGeometry {
+        kind,
+        baby_split: kind == LeaveKind::Split && pins.len() == 2 && gap == 1,
+        wide_split: kind != LeaveKind::Cluster && gap >= 3,
+        bucket: is_bucket(&pins),
+        lateral_gap: gap,
+        row_span: row_span(&pins),
+        sleepers,
+        difficulty,
+        suggested_percent,
+        pins,
+    })
+}
+
+/// Each empty column to cover adds 1.2. Covering a wide gap is much harder when the
+/// front pins on either side are level, since there's no angle to deflect one pin into
+/// the other (4-6, 7-10), which adds 2. Extra pins and sleepers add 0.5 each.
+fn difficulty(pins: &[u8], sleeper_count: usize) -> f64 {
+    let gap = lateral_gap(pins);
+    let level = widest_gap(pins).is_some_and(|(left, right)| {
+        front_row(pins, |column| column <= left) == front_row(pins, |column| column >= right)
+    });
+
+    let mut score = 1.2 * gap as f64;
+    if gap >= 2 && level {
+        score += 2.0;
+    }
+    score += 0.5 * (pins.len() - 2) as f64 + 0.5 * sleeper_count as f64;
+    (score.min(10.0) * 10.0).round() / 10.0
+}
+
+fn suggested_percent(difficulty: f64) -> f64 {
+    if difficulty < EASY_DIFFICULTY {
+        10.0
+    } else if difficulty < HARD_DIFFICULTY {
+        30.0
+    } else {
+        50.0
+    }
+}
+
+/// An award table built purely from geometry: every split in the tier its difficulty
+/// suggests. A starting point for a league designing its own table.
+pub fn suggested_award_table() -> AwardTable {
+    let mut tiers: Vec<AwardTier> = [10.0, 30.0, 50.0]
+        .into_iter()
+        .map(|percent| AwardTier {
+            percent,
+            splits: Vec::new(),
+            rule: None,
+        })
+        .collect();
+    for split in Split::all() {
+        let pins: Vec<u8> = split.pins().collect();
+        if is_cluster(&pins) {
+            continue;
+        }
+        let percent = suggested_percent(difficulty(&pins, sleepers(&pins).len()));
+        if let Some(tier) = tiers.iter_mut().find(|tier| tier.percent == percent) {
+            tier.splits.push(split);
+        }
+    }
+    AwardTable {
+        name: "Suggested".to_string(),
+        tiers,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seven_ten_is_a_wide_level_split() {
+        let geometry = classify(&[10, 7]).unwrap();
+        assert_eq!(geometry.pins, [7, 10]);
+        assert_eq!(geometry.kind, LeaveKind::Split);
+        assert_eq!(geometry.lateral_gap, 5);
+        assert_eq!(geometry.row_span, 0);
+        assert!(geometry.wide_split && !geometry.baby_split);
+        // Five columns plus the level bonus
+        assert_eq!(geometry.difficulty, 8.0);
+        assert_eq!(geometry.suggested_percent, Some(50.0));
+    }
+
+    #[test]
+    fn baby_splits_have_one_column_between() {
+        for pins in [[2, 3], [3, 10]] {
+            let geometry = classify(&pins).unwrap();
+            assert_eq!(geometry.kind, LeaveKind::Split, "{:?}", pins);
+            assert!(geometry.baby_split, "{:?}", pins);
+            assert_eq!(geometry.difficulty, 1.2, "{:?}", pins);
+            assert_eq!(geometry.suggested_percent, Some(10.0), "{:?}", pins);
+        }
+    }
+
+    #[test]
+    fn clusters_are_not_splits() {
+        let sleeper = classify(&[2, 8]).unwrap();
+        assert_eq!(sleeper.kind, LeaveKind::Cluster);
+        assert_eq!(sleeper.sleepers, [8]);
+        assert_eq!(sleeper.suggested_percent, None);
+
+        let bucket = classify(&[2, 4, 5, 8]).unwrap();
+        assert_eq!(bucket.kind, LeaveKind::Cluster);
+        assert!(bucket.bucket);
+
+        // A knocked-down pin between the two breaks the cluster
+        assert!(!is_cluster(&[3, 10]));
+        assert!(is_cluster(&[3, 6, 10]));
+    }
+
+    #[test]
+    fn headpin_standing_is_a_washout() {
+        let geometry = classify(&[1, 2, 10]).unwrap();
+        assert_eq!(geometry.kind, LeaveKind::Washout);
+        assert_eq!(geometry.lateral_gap, 2);
+        assert_eq!(geometry.row_span, 3);
+        assert!(!geometry.wide_split);
+        assert_eq!(geometry.suggested_percent, None);
+    }
+
+    #[test]
+    fn suggested_percent_tiers_start_at_their_thresholds() {
+        assert_eq!(suggested_percent(EASY_DIFFICULTY - 0.1), 10.0);
+        assert_eq!(suggested_percent(EASY_DIFFICULTY), 30.0);
+        assert_eq!(suggested_percent(HARD_DIFFICULTY - 0.1), 30.0);
+        assert_eq!(suggested_percent(HARD_DIFFICULTY), 50.0);
+    }
+
+    #[test]
+    fn classify_rejects_what_isnt_a_leave() {
+        for pins in [&[0, 7][..], &[7, 11], &[7], &[7, 7]] {
+            assert!(
+                matches!(classify(pins), Err(StorageError::InvalidSplit(_))),
+                "{:?}",
+                pins
+            );
+        }
+    }
+
+    #[test]
+    fn suggested_table_is_valid_and_skips_clusters() {
+        let table = suggested_award_table();
+        table.validate().unwrap();
+        let listed: Vec<Split> = table.tiers.iter().flat_map(|t| t.splits.clone()).collect();
+        assert!(listed.contains(&"7-10".parse().unwrap()));
+        assert!(!listed.contains(&"2-8".parse().unwrap()));
+    }
+}
@@ -0,0 +1,114 @@
+//! Ledger arithmetic shared by every `StorageDao` implementation so the backends can't
+//! disagree about what a transaction does to balances.
+
+use super::money::Money;
+use super::{AccountId, Balance, Roster, Transaction};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+/// Display name of the account that holds the pooled stakes.
+pub const POT: &str = "Pot";
+
+/// What a player pays into the pot for each split in games created before the stake
+/// was a game setting.
+pub const SPLIT_STAKE: Money = Money::from_dollars(1);
+
+/// Applies `transaction` to running balances: the creditor gains the amount and the
+/// debtor loses it, so the total is unchanged.
+pub fn apply(balances: &mut HashMap<AccountId, Money>, transaction: &Transaction) {
+    *balances.entry(transaction.creditor.clone()).or_default() += transaction.amount;
+    *balances.entry(transaction.debtor.clone()).or_default() -= transaction.amount;
+}
+
+/// Exactly undoes [`apply`].
+pub fn reverse(balances: &mut HashMap<AccountId, Money>, transaction: &Transaction) {
+    *balances.entry(transaction.creditor.clone()).or_default() -= transaction.amount;
+    *balances.entry(transaction.debtor.clone()).or_default() += transaction.amount;
+}
+
+/// Balances of every account after replaying `transactions` from zero. Voided
+/// transactions are skipped.
+pub fn fold<'a>(
+    accounts: impl IntoIterator<Item = AccountId>,
+    transactions: impl IntoIterator<Item = &'a Transaction>,
+) -> HashMap<AccountId, Money> {
+    let mut balances: HashMap<AccountId, Money> = accounts
+        .into_iter()
+        .map(|account| (account, Money::ZERO))
+        .collect();
+    for transaction in transactions {
+        if !transaction.is_voided() {
+            apply(&mut balances, transaction);
+        }
+    }
+    balances
+}
+
+/// The transaction an undo voids: the latest that still counts. `newest_first` need only
+/// reach back as far as that transaction.
+pub fn undo_candidate<'a>(
+    newest_first: impl IntoIterator<Item = &'a Transaction>,
+) -> Option<&'a Transaction> {
+    newest_first.into_iter().find(|t| !t.is_voided())
+}
+
+/// The transaction a redo restores: the earliest of the voided transactions after the
+/// latest that still counts, which is the one the most recent undo voided. Anything
+/// recorded after an undo counts, so there is then nothing to redo. `newest_first` must
+/// reach back to a counted transaction or the start of the game.
+pub fn redo_candidate<'a>(
+    newest_first: impl IntoIterator<Item = &'a Transaction>,
+) -> Option<&'a Transaction> {
+    newest_first
+        .into_iter()
+        .take_while(|t| t.is_voided())
+        .last()
+}
+
+pub fn pot_balance(balances: &HashMap<AccountId, Money>) -> Money {
+    balances.get(&AccountId::Pot).copied().unwrap_or_default()
+}
+
+/// Labels each account's balance with its current name from `roster`.
+pub fn to_balances(balances: HashMap<AccountId, Money>, roster: &Roster) -> Vec<Balance> {
+    balances
+        .into_iter()
+        .map(|(account, amount)| Balance {
+            name: roster.name_of(&account),
+            account,
+            amount,
+        })
+        .collect()
+}
+
+/// How one account's balance moved.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct BalanceChange {
+    pub account: AccountId,
+    /// The account's display name when the change was worked out.
+    pub name: String,
+    pub before: Money,
+    pub after: Money,
+}
+
+/// Every account whose balance differs between `before` and `after`, ordered by name.
+/// An account missing from either side is at zero there.
+pub fn changes(
+    before: &HashMap<AccountId, Money>,
+    after: &HashMap<AccountId, Money>,
+    roster: &Roster,
+) -> Vec<BalanceChange> {
+    let accounts: BTreeSet<&AccountId> = before.keys().chain(after.keys()).collect();
+    let mut changes: Vec<BalanceChange> = accounts
+        .into_iter()
+        .map(|account| BalanceChange {
+            account: account.clone(),
+            name: roster.name_of(account),
+            before: before.get(account).copied().unwrap_or_default(),
+            after: after.get(account).copied().unwrap_or_default(),
+        })
+        .filter(|change| change.before != change.after)
+        .collect();
+    changes.sort_by(|a, b| a.name.cmp(&b.name));
+    changes
+}
@@ -1,211 +1,703 @@
-use super::{Balance, StorageDao, Transaction};
+use super::activity::{self, Action};
+use super::correction;
+use super::ledger;
+use super::settlement;
+use super::timestamp;
+use super::{
+    page_limit, AccountId, Activity, ActivityFilter, AwardTable, Balance, Correction, GameId,
+    GameSummary, Money, NewGame, Payment, Player, PlayerId, PotPolicy, Replay, Roster, Split,
+    StakePolicy, StorageDao, StorageError, StorageResult, Transaction, TransactionId,
+    TransactionKind, TransactionPage, Void, DEFAULT_GAME_ID,
+};
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
-use std::time::SystemTime;
+use chrono::{TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct MemoryState {
+    games: BTreeMap<GameId, MemoryGame>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+struct MemoryGame {
+    #[serde(default = "default_game_name")]
+    name: String,
+    roster: Roster,
+    #[serde(default)]
+    stake: StakePolicy,
+    #[serde(default)]
+    pot_policy: PotPolicy,
+    #[serde(default)]
+    award_table: AwardTable,
+    transactions: Vec<Transaction>,
+    #[serde(default = "default_timezone")]
+    display_timezone: String,
+    /// Oldest first; games saved before the log existed start with an empty one.
+    #[serde(default)]
+    activity: Vec<Activity>,
+}
+
+fn default_timezone() -> String {
+    "UTC".to_string()
+}
+
+fn default_game_name() -> String {
+    "Demo".to_string()
+}
 
 pub struct MemoryDao {
-    transactions: Arc<Mutex<Vec<Transaction>>>,
-    split_awards: Arc<Mutex<HashMap<String, f64>>>,
-    names: Arc<Mutex<Vec<String>>>,
+    state: Mutex<MemoryState>,
+    file_path: Option<PathBuf>,
 }
 
 impl MemoryDao {
     pub fn new() -> Self {
-        let mut dao = Self {
-            transactions: Arc::new(Mutex::new(Vec::new())),
-            split_awards: Arc::new(Mutex::new(HashMap::new())),
-            names: Arc::new(Mutex::new(Vec::new())),
+        Self {
+            state: Mutex::new(MemoryState::with_test_data()),
+            file_path: None,
+        }
+    }
+
+    /// Loads the games from `path` if it exists, otherwise starts from the test data.
+    /// Every mutation is written back to the file.
+    pub fn with_file(path: PathBuf) -> StorageResult<Self> {
+        let state = if path.exists() {
+            let contents = fs::read_to_string(&path).map_err(|e| {
+                StorageError::BackendUnavailable(format!("Failed to read {:?}: {}", path, e))
+            })?;
+            serde_json::from_str(&contents).map_err(|e| {
+                StorageError::CorruptRecord(format!("Failed to parse {:?}: {}", path, e))
+            })?
+        } else {
+            MemoryState::with_test_data()
         };
-        
-        // Initialize with test data
-        dao.init_test_data();
-        dao
+
+        let dao = Self {
+            state: Mutex::new(state),
+            file_path: Some(path),
+        };
+        dao.save(&dao.state.lock().unwrap())?;
+        Ok(dao)
     }
-    
-    fn init_test_data(&mut self) {
-        // Initialize names
-        {
-            let mut names = self.names.lock().unwrap();
-            names.extend_from_slice(&[
-                "Alice".to_string(),
-                "Bob".to_string(), 
-                "Charlie".to_string(),
-                "Dana".to_string(),
-                "Pot".to_string(),
-            ]);
+
+    /// Applies `change` to a copy of `game` and keeps the copy only once it's saved, so
+    /// a failed write leaves the ledger as it was.
+    fn update<T>(
+        &self,
+        game: &GameId,
+        change: impl FnOnce(&mut MemoryGame) -> StorageResult<T>,
+    ) -> StorageResult<T> {
+        let mut state = self.state.lock().unwrap();
+        let mut updated = state.game(game)?.clone();
+        let result = change(&mut updated)?;
+        let previous = std::mem::replace(state.game_mut(game)?, updated);
+        if let Err(e) = self.save(&state) {
+            *state.game_mut(game)? = previous;
+            return Err(e);
         }
-        
-        // Initialize split awards
-        {
-            let mut awards = self.split_awards.lock().unwrap();
-            awards.insert("1-2-3".to_string(), 5.0);
-            awards.insert("2-3".to_string(), 7.5);
-            awards.insert("3-6".to_string(), 10.0);
-            awards.insert("4-5".to_string(), 12.5);
-            awards.insert("4-5-6".to_string(), 15.0);
-            awards.insert("5-6".to_string(), 17.5);
-            awards.insert("4-7-10".to_string(), 20.0);
-            awards.insert("6-7-10".to_string(), 22.5);
-            awards.insert("7-10".to_string(), 25.0);
+        Ok(result)
+    }
+
+    fn save(&self, state: &MemoryState) -> StorageResult<()> {
+        if let Some(path) = &self.file_path {
+            let contents = serde_json::to_string_pretty(state).map_err(|e| {
+                StorageError::CorruptRecord(format!("Failed to serialize ledger: {}", e))
+            })?;
+            fs::write(path, contents).map_err(|e| {
+                StorageError::BackendUnavailable(format!("Failed to write {:?}: {}", path, e))
+            })?;
         }
-        
-        // Add some test transactions
-        {
-            let mut transactions = self.transactions.lock().unwrap();
-            
-            // Add some initial splits
-            transactions.push(Transaction {
-                creditor: "Alice".to_string(),
-                debtor: "Pot".to_string(),
-                amount: 1.0,
-                split: "7-10".to_string(),
-                time: "1/15/2025, 2:30:00 PM UTC".to_string(),
-                pot_amount: 10.0,
-                date: "1/15/2025".to_string(),
-            });
-            
-            transactions.push(Transaction {
-                creditor: "Bob".to_string(),
-                debtor: "Pot".to_string(),
-                amount: 1.0,
-                split: "4-5".to_string(),
-                time: "1/15/2025, 2:45:00 PM UTC".to_string(),
-                pot_amount: 11.0,
-                date: "1/15/2025".to_string(),
-            });
-            
-            transactions.push(Transaction {
-                creditor: "Charlie".to_string(),
-                debtor: "Pot".to_string(),
-                amount: 1.0,
-                split: "2-3".to_string(),
-                time: "1/15/2025, 3:00:00 PM UTC".to_string(),
-                pot_amount: 12.0,
-                date: "1/15/2025".to_string(),
-            });
-            
-            // Add a conversion
-            transactions.push(Transaction {
-                creditor: "Pot".to_string(),
-                debtor: "Alice".to_string(),
-                amount: 3.0,  // 25% of 12.0
-                split: "7-10".to_string(),
-                time: "1/15/2025, 3:15:00 PM UTC".to_string(),
-                pot_amount: 12.0,
-                date: "1/15/2025".to_string(),
-            });
+        Ok(())
+    }
+}
+
+impl MemoryState {
+    fn with_test_data() -> Self {
+        let mut game = MemoryGame::default();
+        game.init_test_data();
+        Self {
+            games: BTreeMap::from([(GameId(DEFAULT_GAME_ID.to_string()), game)]),
         }
     }
-    
+
+    fn game(&self, game: &GameId) -> StorageResult<&MemoryGame> {
+        self.games
+            .get(game)
+            .ok_or_else(|| StorageError::NotFound(format!("No game found: {}", game)))
+    }
+
+    fn game_mut(&mut self, game: &GameId) -> StorageResult<&mut MemoryGame> {
+        self.games
+            .get_mut(game)
+            .ok_or_else(|| StorageError::NotFound(format!("No game found: {}", game)))
+    }
+}
+
+impl MemoryGame {
+    fn init_test_data(&mut self) {
+        self.name = default_game_name();
+        self.stake = StakePolicy::default();
+        self.pot_policy = PotPolicy::default();
+        self.display_timezone = default_timezone();
+
+        // Initialize players
+        self.roster = Roster::default();
+        let mut add =
+            |name: &str| AccountId::Player(self.roster.add(name).expect("test player is valid"));
+        let alice = add("Alice");
+        let bob = add("Bob");
+        let charlie = add("Charlie");
+        add("Dana");
+
+        let split = |pins: &str| pins.parse::<Split>().expect("test split is valid");
+
+        // Add some test transactions
+        let transactions = &mut self.transactions;
+
+        // Add some initial splits
+        transactions.push(Transaction {
+            id: TransactionId(1),
+            kind: TransactionKind::Split,
+            creditor: AccountId::Pot,
+            debtor: alice.clone(),
+            amount: Money::from_dollars(1),
+            split: Some(split("7-10")),
+            time: Utc.with_ymd_and_hms(2025, 1, 15, 14, 30, 0).unwrap(),
+            pot_amount: Money::ZERO,
+            stake: None,
+            award: None,
+            voided: None,
+        });
+
+        transactions.push(Transaction {
+            id: TransactionId(2),
+            kind: TransactionKind::Split,
+            creditor: AccountId::Pot,
+            debtor: bob,
+            amount: Money::from_dollars(1),
+            split: Some(split("4-5")),
+            time: Utc.with_ymd_and_hms(2025, 1, 15, 14, 45, 0).unwrap(),
+            pot_amount: Money::from_dollars(1),
+            stake: None,
+            award: None,
+            voided: None,
+        });
+
+        transactions.push(Transaction {
+            id: TransactionId(3),
+            kind: TransactionKind::Split,
+            creditor: AccountId::Pot,
+            debtor: charlie,
+            amount: Money::from_dollars(1),
+            split: Some(split("2-3")),
+            time: Utc.with_ymd_and_hms(2025, 1, 15, 15, 0, 0).unwrap(),
+            pot_amount: Money::from_dollars(2),
+            stake: None,
+            award: None,
+            voided: None,
+        });
+
+        // Add a conversion
+        transactions.push(Transaction {
+            id: TransactionId(4),
+            kind: TransactionKind::Conversion,
+            creditor: alice,
+            debtor: AccountId::Pot,
+            amount: Money::from_cents(150), // 50% of 3.00
+            split: Some(split("7-10")),
+            time: Utc.with_ymd_and_hms(2025, 1, 15, 15, 15, 0).unwrap(),
+            pot_amount: Money::from_dollars(3),
+            stake: None,
+            award: None,
+            voided: None,
+        });
+    }
+
     fn calculate_balances(&self) -> Vec<Balance> {
-        let transactions = self.transactions.lock().unwrap();
-        let mut balances: HashMap<String, f64> = HashMap::new();
-        
-        // Initialize all names with 0 balance
-        for name in self.names.lock().unwrap().iter() {
-            balances.insert(name.clone(), 0.0);
-        }
-        
-        // Calculate balances from transactions
-        for transaction in transactions.iter() {
-            // Creditor gets positive amount
-            *balances.entry(transaction.creditor.clone()).or_insert(0.0) += transaction.amount;
-            // Debtor gets negative amount
-            *balances.entry(transaction.debtor.clone()).or_insert(0.0) -= transaction.amount;
-        }
-        
-        // Convert to Balance structs
-        balances.into_iter()
-            .map(|(name, amount)| Balance {
-                name,
-                amount: format!("{:.2}", amount),
-            })
-            .collect()
+        ledger::to_balances(
+            ledger::fold(self.roster.active_accounts(), &self.transactions),
+            &self.roster,
+        )
+    }
+
+    fn balances(&self) -> HashMap<AccountId, Money> {
+        ledger::fold([], &self.transactions)
+    }
+
+    /// Appends `activity` to the log, numbered and with the balance changes since
+    /// `before`.
+    fn log(&mut self, mut activity: Activity, before: &HashMap<AccountId, Money>) {
+        activity.id = self.activity.len() as u64 + 1;
+        activity.balances = ledger::changes(before, &self.balances(), &self.roster);
+        self.activity.push(activity);
+    }
+
+    /// The transaction `id`, which must be in the ledger.
+    fn transaction_mut(&mut self, id: TransactionId) -> &mut Transaction {
+        self.transactions
+            .iter_mut()
+            .find(|t| t.id == id)
+            .expect("transaction is in the ledger")
+    }
+
+    fn next_id(&self) -> TransactionId {
+        TransactionId(self.transactions.last().map_or(1, |t| t.id.0 + 1))
+    }
+
+    fn pot_balance(&self) -> Money {
+        ledger::pot_balance(&ledger::fold([], &self.transactions))
     }
 }
 
 #[async_trait]
 impl StorageDao for MemoryDao {
-    async fn get_names(&self) -> Vec<String> {
-        self.names.lock().unwrap().clone()
-    }
-    
-    async fn get_balances(&self) -> Vec<Balance> {
-        self.calculate_balances()
-    }
-    
-    async fn get_last_n_transactions(&self, n: usize) -> Vec<Transaction> {
-        let transactions = self.transactions.lock().unwrap();
-        let start = transactions.len().saturating_sub(n);
-        transactions[start..].to_vec()
-    }
-    
-    async fn remove_last_transaction(&self) {
-        let mut transactions = self.transactions.lock().unwrap();
-        transactions.pop();
-    }
-    
-    async fn add_split(&self, name: String, split: String) {
-        let pot_balance = self.calculate_balances()
+    async fn list_games(&self) -> StorageResult<Vec<GameSummary>> {
+        let state = self.state.lock().unwrap();
+        let mut games: Vec<GameSummary> = state
+            .games
             .iter()
-            .find(|b| b.name == "Pot")
-            .map(|b| b.amount.parse::<f64>().unwrap_or(0.0))
-            .unwrap_or(0.0);
-            
-        let now = SystemTime::now();
-        let now: DateTime<Utc> = now.into();
-        let now_string = now.format("%-m/%-d/%Y, %l:%M:%S %p UTC").to_string();
-        let today_string = now.format("%-m/%-d/%Y").to_string();
-        
-        let transaction = Transaction {
-            creditor: name,
-            debtor: "Pot".to_string(),
-            amount: 1.0,
-            split,
-            time: now_string,
-            pot_amount: pot_balance,
-            date: today_string,
+            .map(|(id, game)| GameSummary {
+                id: id.clone(),
+                name: game.name.clone(),
+            })
+            .collect();
+        games.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(games)
+    }
+
+    async fn create_game(&self, game: NewGame, actor: &str) -> StorageResult<GameSummary> {
+        game.validate()?;
+        let mut state = self.state.lock().unwrap();
+        let id = GameId::generate(&game.name, Utc::now());
+        if state.games.contains_key(&id) {
+            return Err(StorageError::Conflict(format!(
+                "Game {} already exists",
+                id
+            )));
+        }
+
+        let mut created = MemoryGame {
+            name: game.name.clone(),
+            roster: game.roster()?,
+            stake: game.stake,
+            pot_policy: game.pot_policy,
+            award_table: game.award_table,
+            transactions: Vec::new(),
+            display_timezone: default_timezone(),
+            activity: Vec::new(),
         };
-        
-        let mut transactions = self.transactions.lock().unwrap();
-        transactions.push(transaction);
+        created.log(
+            Activity::new(actor, Action::CreateGame, Some(game.name.clone())),
+            &HashMap::new(),
+        );
+        state.games.insert(id.clone(), created);
+        if let Err(e) = self.save(&state) {
+            state.games.remove(&id);
+            return Err(e);
+        }
+        Ok(GameSummary {
+            id,
+            name: game.name,
+        })
     }
-    
-    async fn add_conversion(&self, name: String, split: String) {
-        let pot_balance = self.calculate_balances()
+
+    async fn get_players(&self, game: &GameId) -> StorageResult<Vec<Player>> {
+        Ok(self
+            .state
+            .lock()
+            .unwrap()
+            .game(game)?
+            .roster
+            .players
+            .clone())
+    }
+
+    async fn add_player(
+        &self,
+        game: &GameId,
+        name: String,
+        actor: &str,
+    ) -> StorageResult<PlayerId> {
+        self.update(game, |game| {
+            let before = game.balances();
+            let player = game.roster.add(&name)?;
+            game.log(Activity::new(actor, Action::AddPlayer, Some(name)), &before);
+            Ok(player)
+        })
+    }
+
+    async fn rename_player(
+        &self,
+        game: &GameId,
+        player: &PlayerId,
+        new_name: String,
+        actor: &str,
+    ) -> StorageResult<()> {
+        self.update(game, |game| {
+            let before = game.balances();
+            let renamed = activity::describe_rename(&game.roster, player, &new_name);
+            game.roster.rename(player, &new_name)?;
+            game.log(
+                Activity::new(actor, Action::RenamePlayer, Some(renamed)),
+                &before,
+            );
+            Ok(())
+        })
+    }
+
+    async fn retire_player(
+        &self,
+        game: &GameId,
+        player: &PlayerId,
+        actor: &str,
+    ) -> StorageResult<()> {
+        self.update(game, |game| {
+            let before = game.balances();
+            let retired = game.roster.name_of(&AccountId::Player(player.clone()));
+            game.roster.retire(player)?;
+            game.log(
+                Activity::new(actor, Action::RetirePlayer, Some(retired)),
+                &before,
+            );
+            Ok(())
+        })
+    }
+
+    async fn get_balances(&self, game: &GameId) -> StorageResult<Vec<Balance>> {
+        Ok(self.state.lock().unwrap().game(game)?.calculate_balances())
+    }
+
+    async fn get_transactions_page(
+        &self,
+        game: &GameId,
+        before: Option<TransactionId>,
+        limit: usize,
+    ) -> StorageResult<TransactionPage> {
+        let limit = page_limit(limit);
+        let state = self.state.lock().unwrap();
+        let newest_first = state
+            .game(game)?
+            .transactions
             .iter()
-            .find(|b| b.name == "Pot")
-            .map(|b| b.amount.parse::<f64>().unwrap_or(0.0))
-            .unwrap_or(0.0);
-            
-        let split_awards = self.split_awards.lock().unwrap();
-        let award_multiplier_percent = split_awards
-            .get(&split)
-            .expect(&format!("Invalid split: {}", split));
-        let award = (pot_balance * award_multiplier_percent).round() / 100.0;
-        
-        let now = SystemTime::now();
-        let now: DateTime<Utc> = now.into();
-        let now_string = now.format("%-m/%-d/%Y, %l:%M:%S %p UTC").to_string();
-        let today_string = now.format("%-m/%-d/%Y").to_string();
-        
-        let transaction = Transaction {
-            creditor: "Pot".to_string(),
-            debtor: name,
-            amount: award,
-            split,
-            time: now_string,
-            pot_amount: pot_balance,
-            date: today_string,
-        };
-        
-        let mut transactions = self.transactions.lock().unwrap();
-        transactions.push(transaction);
+            .rev()
+            .filter(|t| before.is_none_or(|before| t.id < before))
+            .take(limit + 1)
+            .cloned()
+            .collect();
+        Ok(TransactionPage::from_newest_first(newest_first, limit))
+    }
+
+    async fn remove_last_transaction(
+        &self,
+        game: &GameId,
+        void: Void,
+    ) -> StorageResult<Transaction> {
+        self.update(game, |game| {
+            let id = ledger::undo_candidate(game.transactions.iter().rev())
+                .ok_or_else(|| StorageError::NotFound("Nothing to undo".to_string()))?
+                .id;
+            let before = game.balances();
+            let mut activity = Activity::new(&void.by, Action::Undo, void.reason.clone());
+            activity.transactions = vec![id];
+            let transaction = game.transaction_mut(id);
+            transaction.voided = Some(void);
+            let transaction = transaction.clone();
+            game.log(activity, &before);
+            Ok(transaction)
+        })
+    }
+
+    async fn correct_transaction(
+        &self,
+        game: &GameId,
+        id: TransactionId,
+        correction: Correction,
+        actor: &str,
+    ) -> StorageResult<Replay> {
+        self.update(game, |game| {
+            let before = game.balances();
+            let rules = correction::Rules {
+                roster: &game.roster,
+                stake: &game.stake,
+                pot_policy: &game.pot_policy,
+                award_table: &game.award_table,
+            };
+            let replay = correction::replay(&game.transactions, id, &correction, &rules)?;
+            let mut activity = correction.activity(actor, &game.roster);
+            activity.transactions = replay.changed.iter().map(|t| t.id).collect();
+            for transaction in &replay.changed {
+                *game.transaction_mut(transaction.id) = transaction.clone();
+            }
+            game.log(activity, &before);
+            Ok(replay)
+        })
+    }
+
+    async fn redo_last_undo(&self, game: &GameId, actor: &str) -> StorageResult<Transaction> {
+        self.update(game, |game| {
+            let id = ledger::redo_candidate(game.transactions.iter().rev())
+                .ok_or_else(|| StorageError::NotFound("Nothing to redo".to_string()))?
+                .id;
+            let before = game.balances();
+            let transaction = game.transaction_mut(id);
+            transaction.voided = None;
+            let transaction = transaction.clone();
+            let mut activity = Activity::new(actor, Action::Redo, None);
+            activity.transactions = vec![id];
+            game.log(activity, &before);
+            Ok(transaction)
+        })
+    }
+
+    async fn add_split(
+        &self,
+        game: &GameId,
+        player: &PlayerId,
+        split: Split,
+        actor: &str,
+    ) -> StorageResult<()> {
+        self.update(game, |game| {
+            game.roster.require_active(player)?;
+            let pot_balance = game.pot_balance();
+            let now = Utc::now();
+            let (amount, stake) = game
+                .stake
+                .stake_for(player, now, game.transactions.iter().rev());
+
+            // The pot is credited with the stake, as in every backend
+            let transaction = Transaction {
+                id: game.next_id(),
+                kind: TransactionKind::Split,
+                creditor: AccountId::Pot,
+                debtor: AccountId::Player(player.clone()),
+                amount,
+                split: Some(split),
+                time: now,
+                pot_amount: pot_balance,
+                stake: Some(stake),
+                award: None,
+                voided: None,
+            };
+
+            let before = game.balances();
+            let detail = activity::describe_play(&game.roster, player, split);
+            let mut activity = Activity::new(actor, Action::Split, Some(detail));
+            activity.transactions = vec![transaction.id];
+            game.transactions.push(transaction);
+            game.log(activity, &before);
+            Ok(())
+        })
+    }
+
+    async fn add_conversion(
+        &self,
+        game: &GameId,
+        player: &PlayerId,
+        split: Split,
+        actor: &str,
+    ) -> StorageResult<()> {
+        self.update(game, |game| {
+            game.roster.require_active(player)?;
+            let pot_balance = game.pot_balance();
+
+            let award_multiplier_percent = game
+                .award_table
+                .percent_for(split)
+                .ok_or_else(|| StorageError::InvalidSplit(split.to_string()))?;
+            let (amount, award) = game.pot_policy.award(pot_balance, award_multiplier_percent);
+
+            let transaction = Transaction {
+                id: game.next_id(),
+                kind: TransactionKind::Conversion,
+                creditor: AccountId::Player(player.clone()),
+                debtor: AccountId::Pot,
+                amount,
+                split: Some(split),
+                time: Utc::now(),
+                pot_amount: pot_balance,
+                stake: None,
+                award: Some(award),
+                voided: None,
+            };
+
+            let before = game.balances();
+            let detail = activity::describe_play(&game.roster, player, split);
+            let mut activity = Activity::new(actor, Action::Conversion, Some(detail));
+            activity.transactions = vec![transaction.id];
+            game.transactions.push(transaction);
+            game.log(activity, &before);
+            Ok(())
+        })
+    }
+
+    async fn record_settlement(
+        &self,
+        game: &GameId,
+        payments: Vec<Payment>,
+        actor: &str,
+    ) -> StorageResult<Vec<Transaction>> {
+        self.update(game, |game| {
+            settlement::validate(&payments, &game.roster)?;
+            let transactions = settlement::to_transactions(
+                &payments,
+                game.next_id(),
+                Utc::now(),
+                game.pot_balance(),
+            );
+
+            let before = game.balances();
+            let mut activity = Activity::new(actor, Action::Settlement, None);
+            activity.transactions = transactions.iter().map(|t| t.id).collect();
+            game.transactions.extend(transactions.iter().cloned());
+            game.log(activity, &before);
+            Ok(transactions)
+        })
+    }
+
+    async fn get_award_table(&self, game: &GameId) -> StorageResult<AwardTable> {
+        Ok(self.state.lock().unwrap().game(game)?.award_table.clone())
+    }
+
+    async fn set_award_table(
+        &self,
+        game: &GameId,
+        table: AwardTable,
+        actor: &str,
+    ) -> StorageResult<()> {
+        table.validate()?;
+        self.update(game, |game| {
+            let before = game.balances();
+            game.award_table = table;
+            game.log(Activity::new(actor, Action::SetAwardTable, None), &before);
+            Ok(())
+        })
+    }
+
+    async fn get_stake_policy(&self, game: &GameId) -> StorageResult<StakePolicy> {
+        Ok(self.state.lock().unwrap().game(game)?.stake.clone())
     }
-    
-    async fn get_split_awards(&self) -> HashMap<String, f64> {
-        self.split_awards.lock().unwrap().clone()
+
+    async fn set_stake_policy(
+        &self,
+        game: &GameId,
+        policy: StakePolicy,
+        actor: &str,
+    ) -> StorageResult<()> {
+        policy.validate()?;
+        self.update(game, |game| {
+            let before = game.balances();
+            game.stake = policy;
+            game.log(Activity::new(actor, Action::SetStakePolicy, None), &before);
+            Ok(())
+        })
     }
-}
\ No newline at end of file
+
+    async fn get_pot_policy(&self, game: &GameId) -> StorageResult<PotPolicy> {
+        Ok(self.state.lock().unwrap().game(game)?.pot_policy.clone())
+    }
+
+    async fn set_pot_policy(
+        &self,
+        game: &GameId,
+        policy: PotPolicy,
+        actor: &str,
+    ) -> StorageResult<()> {
+        policy.validate()?;
+        self.update(game, |game| {
+            let before = game.balances();
+            game.pot_policy = policy;
+            game.log(Activity::new(actor, Action::SetPotPolicy, None), &before);
+            Ok(())
+        })
+    }
+
+    async fn get_display_timezone(&self, game: &GameId) -> StorageResult<String> {
+        Ok(self
+            .state
+            .lock()
+            .unwrap()
+            .game(game)?
+            .display_timezone
+            .clone())
+    }
+
+    async fn set_display_timezone(
+        &self,
+        game: &GameId,
+        timezone: String,
+        actor: &str,
+    ) -> StorageResult<()> {
+        timestamp::validate_timezone(&timezone)?;
+        self.update(game, |game| {
+            let before = game.balances();
+            let activity = Activity::new(actor, Action::SetDisplayTimezone, Some(timezone.clone()));
+            game.display_timezone = timezone;
+            game.log(activity, &before);
+            Ok(())
+        })
+    }
+
+    async fn get_activity_log(
+        &self,
+        game: &GameId,
+        filter: &ActivityFilter,
+    ) -> StorageResult<Vec<Activity>> {
+        let state = self.state.lock().unwrap();
+        Ok(filter.select(state.game(game)?.activity.iter().rev().cloned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn amounts(dao: &MemoryDao, game: &GameId) -> HashMap<AccountId, Money> {
+        let balances = dao.get_balances(game).await.unwrap();
+        balances
+            .into_iter()
+            .map(|b| (b.account, b.amount))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn failed_save_leaves_the_ledger_unchanged() {
+        let path = std::env::temp_dir().join(format!(
+            "split-happens-ledger-{}.json",
+            Utc::now().timestamp_nanos_opt().unwrap()
+        ));
+        let dao = MemoryDao::with_file(path.clone()).unwrap();
+        let game = GameId(DEFAULT_GAME_ID.to_string());
+        let players = dao.get_players(&game).await.unwrap();
+        let before = amounts(&dao, &game).await;
+
+        // A directory in the file's place makes every write fail
+        fs::remove_file(&path).unwrap();
+        fs::create_dir(&path).unwrap();
+        let split = "7-10".parse().unwrap();
+        let new_game = NewGame {
+            name: "League".to_string(),
+            players: vec!["Ann".to_string()],
+            stake: StakePolicy::default(),
+            pot_policy: PotPolicy::default(),
+            award_table: AwardTable::default(),
+        };
+        assert!(dao
+            .add_player(&game, "Eve".to_string(), "test")
+            .await
+            .is_err());
+        assert!(dao
+            .add_split(&game, &players[0].id, split, "test")
+            .await
+            .is_err());
+        assert!(dao.create_game(new_game, "test").await.is_err());
+        fs::remove_dir(&path).unwrap();
+
+        assert_eq!(dao.get_players(&game).await.unwrap(), players);
+        assert_eq!(amounts(&dao, &game).await, before);
+        assert_eq!(dao.list_games().await.unwrap().len(), 1);
+    }
+}
@@ -1,35 +1,469 @@
+// Not ported to the current StorageDao, so left out; see README_STORAGE.md.
 // pub mod sheets_dao;
+pub mod account;
+pub mod activity;
+pub mod award_table;
+pub mod config;
+#[cfg(test)]
+mod conformance;
+pub mod correction;
 pub mod dynamodb_dao;
+pub mod game;
+pub mod geometry;
+pub mod ledger;
 pub mod memory_dao;
+pub mod money;
+pub mod pot_policy;
+pub mod roster;
+pub mod settlement;
+pub mod split;
+pub mod split_rule;
+pub mod stake;
+pub mod stats;
+pub mod timestamp;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+
+pub use account::{AccountId, PlayerId};
+pub use activity::{Activity, ActivityFilter};
+pub use award_table::AwardTable;
+pub use correction::{Correction, Replay};
+pub use game::{GameId, GameSummary, NewGame, DEFAULT_GAME_ID};
+pub use money::Money;
+pub use pot_policy::{AwardBreakdown, PotPolicy};
+pub use roster::{Player, Roster};
+pub use settlement::{Payment, SettlementPlan};
+pub use split::Split;
+pub use stake::{StakeBreakdown, StakePolicy};
+
+/// Position of a transaction in its game's ledger; later transactions have larger ids.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(transparent)]
+pub struct TransactionId(pub u64);
+
+/// What a transaction records. Rows written before kinds were stored are classified by
+/// [`TransactionKind::infer`] when read.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionKind {
+    /// A player's stake paid into the pot for a split attempt.
+    Split,
+    /// An award paid from the pot for converting a split.
+    Conversion,
+    /// A payment squaring up balances; see [`settlement`].
+    Settlement,
+    /// A correction entered by hand.
+    ManualAdjustment,
+    /// Money a player puts into the pot outside of splits.
+    BuyIn,
+    /// Cancels the effect of an earlier transaction.
+    Reversal,
+}
+
+impl TransactionKind {
+    /// Classifies a transaction that predates stored kinds. Splits and conversions that
+    /// recorded a stake or award are known; otherwise the pot is credited with stakes
+    /// and debited for awards, and anything without a split is a settlement.
+    pub fn infer(transaction: &StoredTransaction) -> Self {
+        if transaction.split.is_none() {
+            return TransactionKind::Settlement;
+        }
+        if transaction.stake.is_some() {
+            return TransactionKind::Split;
+        }
+        if transaction.award.is_some() {
+            return TransactionKind::Conversion;
+        }
+        match (&transaction.creditor, &transaction.debtor) {
+            (AccountId::Pot, AccountId::Player(_)) => TransactionKind::Split,
+            (AccountId::Player(_), AccountId::Pot) => TransactionKind::Conversion,
+            _ => TransactionKind::ManualAdjustment,
+        }
+    }
+}
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(from = "StoredTransaction")]
 pub struct Transaction {
-    pub creditor: String,
-    pub debtor: String,
-    pub amount: f64,
-    pub split: String,
-    pub time: String,
-    pub pot_amount: f64,
-    pub date: String,
+    pub id: TransactionId,
+    pub kind: TransactionKind,
+    pub creditor: AccountId,
+    pub debtor: AccountId,
+    pub amount: Money,
+    /// The split attempted or converted; settlement payments have none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub split: Option<Split>,
+    /// When the transaction was recorded. Serialized as RFC 3339; legacy locale-formatted
+    /// strings and epoch millis are accepted when reading.
+    #[serde(deserialize_with = "timestamp::deserialize")]
+    pub time: DateTime<Utc>,
+    pub pot_amount: Money,
+    /// How the stake was worked out, on splits recorded since stakes could vary.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stake: Option<StakeBreakdown>,
+    /// How the award was worked out, on conversions recorded since pots had a policy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub award: Option<AwardBreakdown>,
+    /// Set when the transaction was undone; it stays in the history but no longer counts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub voided: Option<Void>,
+}
+
+/// Who voided a transaction, by undo or correction, when and why.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Void {
+    /// The device's name, as set in its settings.
+    pub by: String,
+    pub at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl Void {
+    /// A void made now by `by`.
+    pub fn now(by: String, reason: Option<String>) -> Self {
+        Self {
+            by,
+            at: Utc::now(),
+            reason,
+        }
+    }
+}
+
+impl Transaction {
+    /// Whether the transaction has been undone and no longer affects balances.
+    pub fn is_voided(&self) -> bool {
+        self.voided.is_some()
+    }
+
+    /// The player a split or conversion is for, whichever side of the ledger they are on.
+    /// `None` for transactions that don't involve exactly one player.
+    pub fn player(&self) -> Option<&PlayerId> {
+        match (self.creditor.player(), self.debtor.player()) {
+            (Some(player), None) | (None, Some(player)) => Some(player),
+            _ => None,
+        }
+    }
+}
+
+/// A transaction as stored, which may predate its `kind`.
+#[derive(Debug, Deserialize)]
+pub struct StoredTransaction {
+    pub id: TransactionId,
+    #[serde(default)]
+    pub kind: Option<TransactionKind>,
+    pub creditor: AccountId,
+    pub debtor: AccountId,
+    pub amount: Money,
+    #[serde(default)]
+    pub split: Option<Split>,
+    #[serde(deserialize_with = "timestamp::deserialize")]
+    pub time: DateTime<Utc>,
+    pub pot_amount: Money,
+    #[serde(default)]
+    pub stake: Option<StakeBreakdown>,
+    #[serde(default)]
+    pub award: Option<AwardBreakdown>,
+    #[serde(default)]
+    pub voided: Option<Void>,
+}
+
+impl From<StoredTransaction> for Transaction {
+    fn from(stored: StoredTransaction) -> Self {
+        let kind = stored
+            .kind
+            .unwrap_or_else(|| TransactionKind::infer(&stored));
+        Transaction {
+            id: stored.id,
+            kind,
+            creditor: stored.creditor,
+            debtor: stored.debtor,
+            amount: stored.amount,
+            split: stored.split,
+            time: stored.time,
+            pot_amount: stored.pot_amount,
+            stake: stored.stake,
+            award: stored.award,
+            voided: stored.voided,
+        }
+    }
+}
+
+/// A run of consecutive transactions in chronological order.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TransactionPage {
+    pub transactions: Vec<Transaction>,
+    /// Cursor for the next older page, or `None` when this page reaches the first
+    /// transaction of the game.
+    pub next_before: Option<TransactionId>,
+}
+
+impl TransactionPage {
+    /// Builds a page from up to `limit + 1` transactions fetched newest first; the extra
+    /// one only signals that older transactions exist. `limit` must be at least one.
+    pub fn from_newest_first(mut newest_first: Vec<Transaction>, limit: usize) -> Self {
+        let has_more = newest_first.len() > limit;
+        newest_first.truncate(limit);
+        newest_first.reverse();
+        let next_before = if has_more {
+            newest_first.first().map(|t| t.id)
+        } else {
+            None
+        };
+        Self {
+            transactions: newest_first,
+            next_before,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Balance {
+    pub account: AccountId,
+    /// The account's current display name.
     pub name: String,
-    pub amount: String,
+    pub amount: Money,
+}
+
+/// Failure of a storage operation, serialized to the frontend as
+/// `{ "kind": "...", "message": "..." }`.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(tag = "kind", content = "message", rename_all = "snake_case")]
+pub enum StorageError {
+    /// The game, player or transaction being operated on does not exist.
+    NotFound(String),
+    /// The pins don't form a split, or the split is not one the game pays out on.
+    InvalidSplit(String),
+    /// Another writer changed the record first; the operation can be retried.
+    Conflict(String),
+    /// The backend rejected our credentials.
+    Unauthorized(String),
+    /// The backend could not be reached or failed internally.
+    BackendUnavailable(String),
+    /// A stored record is missing fields or has values we can't parse.
+    CorruptRecord(String),
+    /// The requested backend or game settings are incomplete or inconsistent.
+    InvalidConfig(String),
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::NotFound(msg) => write!(f, "Not found: {}", msg),
+            StorageError::InvalidSplit(msg) => write!(f, "Invalid split: {}", msg),
+            StorageError::Conflict(msg) => write!(f, "Conflict: {}", msg),
+            StorageError::Unauthorized(msg) => write!(f, "Unauthorized: {}", msg),
+            StorageError::BackendUnavailable(msg) => write!(f, "Backend unavailable: {}", msg),
+            StorageError::CorruptRecord(msg) => write!(f, "Corrupt record: {}", msg),
+            StorageError::InvalidConfig(msg) => write!(f, "Invalid configuration: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {}
+
+pub type StorageResult<T> = Result<T, StorageError>;
+
+/// The most transactions one page holds. Larger page limits are clamped to it, as is a
+/// limit of zero to one.
+pub const MAX_PAGE_SIZE: usize = 200;
+
+/// `limit` clamped to what a page can hold, for backends to apply to the limit they are
+/// asked for.
+pub fn page_limit(limit: usize) -> usize {
+    limit.clamp(1, MAX_PAGE_SIZE)
 }
 
+/// Every method that changes a game takes the `actor` making the change, normally the
+/// device's name, and logs the change in the game's activity log in the same write; see
+/// [`activity`].
 #[async_trait]
 pub trait StorageDao: Send + Sync {
-    async fn get_names(&self) -> Vec<String>;
-    async fn get_balances(&self) -> Vec<Balance>;
-    async fn get_last_n_transactions(&self, n: usize) -> Vec<Transaction>;
-    async fn remove_last_transaction(&self);
-    async fn add_split(&self, name: String, split: String);
-    async fn add_conversion(&self, name: String, split: String);
-    async fn get_split_awards(&self) -> HashMap<String, f64>;
+    /// Every game in the backend, ordered by name.
+    async fn list_games(&self) -> StorageResult<Vec<GameSummary>>;
+    /// Validates `game` and creates it with an empty ledger, returning its new id.
+    async fn create_game(&self, game: NewGame, actor: &str) -> StorageResult<GameSummary>;
+    /// Fails with `StorageError::NotFound` if there is no game `game`.
+    async fn get_game(&self, game: &GameId) -> StorageResult<GameSummary> {
+        self.list_games()
+            .await?
+            .into_iter()
+            .find(|summary| &summary.id == game)
+            .ok_or_else(|| StorageError::NotFound(format!("No game found: {}", game)))
+    }
+    /// Everyone in the game, including retired players.
+    async fn get_players(&self, game: &GameId) -> StorageResult<Vec<Player>>;
+    /// Adds a player, or brings a retired player back, returning their id. See
+    /// [`Roster::add`].
+    async fn add_player(&self, game: &GameId, name: String, actor: &str)
+        -> StorageResult<PlayerId>;
+    /// Changes a player's display name; their id, balance and history are unaffected.
+    async fn rename_player(
+        &self,
+        game: &GameId,
+        player: &PlayerId,
+        new_name: String,
+        actor: &str,
+    ) -> StorageResult<()>;
+    /// Hides a player from new splits; their history and balance are kept.
+    async fn retire_player(
+        &self,
+        game: &GameId,
+        player: &PlayerId,
+        actor: &str,
+    ) -> StorageResult<()>;
+    async fn get_balances(&self, game: &GameId) -> StorageResult<Vec<Balance>>;
+    /// The most recent `n` transactions, oldest first, fetched a page at a time.
+    async fn get_last_n_transactions(
+        &self,
+        game: &GameId,
+        n: usize,
+    ) -> StorageResult<Vec<Transaction>> {
+        let mut pages = Vec::new();
+        let mut before = None;
+        let mut remaining = n;
+        while remaining > 0 {
+            let page = self
+                .get_transactions_page(game, before, remaining.min(MAX_PAGE_SIZE))
+                .await?;
+            remaining -= page.transactions.len();
+            before = page.next_before;
+            pages.push(page.transactions);
+            if before.is_none() {
+                break;
+            }
+        }
+        Ok(pages.into_iter().rev().flatten().collect())
+    }
+    /// Every transaction of the game, oldest first.
+    async fn get_all_transactions(&self, game: &GameId) -> StorageResult<Vec<Transaction>> {
+        self.get_last_n_transactions(game, usize::MAX).await
+    }
+    /// Up to `limit` transactions immediately older than `before` (or the latest ones when
+    /// `before` is `None`), oldest first. `limit` is clamped with [`page_limit`].
+    async fn get_transactions_page(
+        &self,
+        game: &GameId,
+        before: Option<TransactionId>,
+        limit: usize,
+    ) -> StorageResult<TransactionPage>;
+    /// Voids the most recent transaction that still counts, reversing its effect on
+    /// balances, and returns it. The transaction stays in the history marked with `void`.
+    /// Fails with `StorageError::NotFound` when there is nothing to undo. The undo is
+    /// logged as made by `void.by`.
+    async fn remove_last_transaction(
+        &self,
+        game: &GameId,
+        void: Void,
+    ) -> StorageResult<Transaction>;
+    /// What applying `correction` to transaction `id` would do, without changing anything.
+    /// See [`correction::replay`].
+    async fn preview_correction(
+        &self,
+        game: &GameId,
+        id: TransactionId,
+        correction: &Correction,
+    ) -> StorageResult<Replay> {
+        let history = self.get_all_transactions(game).await?;
+        let roster = Roster {
+            players: self.get_players(game).await?,
+            ..Roster::default()
+        };
+        let rules = correction::Rules {
+            roster: &roster,
+            stake: &self.get_stake_policy(game).await?,
+            pot_policy: &self.get_pot_policy(game).await?,
+            award_table: &self.get_award_table(game).await?,
+        };
+        correction::replay(&history, id, correction, &rules)
+    }
+    /// Applies `correction` to transaction `id` and rewrites every later transaction it
+    /// changes, all or none, returning what changed. Fails with `StorageError::NotFound`
+    /// for an unknown transaction, and otherwise as [`correction::replay`] does.
+    async fn correct_transaction(
+        &self,
+        game: &GameId,
+        id: TransactionId,
+        correction: Correction,
+        actor: &str,
+    ) -> StorageResult<Replay>;
+    /// Restores the transaction voided by the latest undo, provided nothing has been
+    /// recorded since, and returns it. See [`ledger::redo_candidate`]. Fails with
+    /// `StorageError::NotFound` when there is nothing to redo.
+    async fn redo_last_undo(&self, game: &GameId, actor: &str) -> StorageResult<Transaction>;
+    async fn add_split(
+        &self,
+        game: &GameId,
+        player: &PlayerId,
+        split: Split,
+        actor: &str,
+    ) -> StorageResult<()>;
+    async fn add_conversion(
+        &self,
+        game: &GameId,
+        player: &PlayerId,
+        split: Split,
+        actor: &str,
+    ) -> StorageResult<()>;
+    /// Records `payments` as settlement transactions, all or none, and returns them.
+    /// Fails with `StorageError::InvalidConfig` if they don't pass
+    /// [`settlement::validate`].
+    async fn record_settlement(
+        &self,
+        game: &GameId,
+        payments: Vec<Payment>,
+        actor: &str,
+    ) -> StorageResult<Vec<Transaction>>;
+    /// Award percentage for every split the game pays out on.
+    async fn get_split_awards(&self, game: &GameId) -> StorageResult<HashMap<Split, f64>> {
+        Ok(self.get_award_table(game).await?.to_map())
+    }
+    /// The game's award table, or [`AwardTable::standard`] if it has never been set.
+    async fn get_award_table(&self, game: &GameId) -> StorageResult<AwardTable>;
+    /// Replaces the game's award table; fails with `StorageError::InvalidConfig` if it
+    /// doesn't pass [`AwardTable::validate`].
+    async fn set_award_table(
+        &self,
+        game: &GameId,
+        table: AwardTable,
+        actor: &str,
+    ) -> StorageResult<()>;
+    /// The game's stake settings; see [`StakePolicy`].
+    async fn get_stake_policy(&self, game: &GameId) -> StorageResult<StakePolicy>;
+    /// Replaces the game's stake settings; fails with `StorageError::InvalidConfig` if
+    /// they don't pass [`StakePolicy::validate`]. Splits already recorded keep their stake.
+    async fn set_stake_policy(
+        &self,
+        game: &GameId,
+        policy: StakePolicy,
+        actor: &str,
+    ) -> StorageResult<()>;
+    /// The game's limits on conversion awards; see [`PotPolicy`].
+    async fn get_pot_policy(&self, game: &GameId) -> StorageResult<PotPolicy>;
+    /// Replaces the game's pot policy; fails with `StorageError::InvalidConfig` if it
+    /// doesn't pass [`PotPolicy::validate`]. Conversions already recorded keep their award.
+    async fn set_pot_policy(
+        &self,
+        game: &GameId,
+        policy: PotPolicy,
+        actor: &str,
+    ) -> StorageResult<()>;
+    /// IANA time zone used to display the game's timestamps, `"UTC"` unless set.
+    async fn get_display_timezone(&self, game: &GameId) -> StorageResult<String>;
+    async fn set_display_timezone(
+        &self,
+        game: &GameId,
+        timezone: String,
+        actor: &str,
+    ) -> StorageResult<()>;
+    /// The game's logged changes that match `filter`, newest first.
+    async fn get_activity_log(
+        &self,
+        game: &GameId,
+        filter: &ActivityFilter,
+    ) -> StorageResult<Vec<Activity>>;
 }
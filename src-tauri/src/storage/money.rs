@@ -0,0 +1,277 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+
+/// An exact amount of money, stored as whole cents.
+///
+/// Rounding policy: anything finer than a cent is rounded to the nearest cent with
+/// halves rounded away from zero. That applies to percentage awards
+/// ([`Money::percent`]) and to legacy stored values such as `0.30000000000000004`
+/// ([`Money::parse`]). All arithmetic between `Money` values is exact, so a ledger
+/// where every transaction credits and debits the same amount always sums to zero.
+///
+/// Serializes as a decimal string with two places (`"12.30"`) so the frontend and
+/// the stores never see binary floating point.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Money(i64);
+
+impl Money {
+    pub const ZERO: Money = Money(0);
+
+    pub const fn from_cents(cents: i64) -> Self {
+        Money(cents)
+    }
+
+    pub const fn from_dollars(dollars: i64) -> Self {
+        Money(dollars * 100)
+    }
+
+    pub fn cents(self) -> i64 {
+        self.0
+    }
+
+    pub fn abs(self) -> Self {
+        Money(self.0.abs())
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    /// `percent`% of this amount, rounded to the nearest cent (halves away from zero).
+    /// Percentages are honoured to a hundredth of a percent.
+    pub fn percent(self, percent: f64) -> Self {
+        let hundredths = (percent * 100.0).round() as i128;
+        Money(div_round(self.0 as i128 * hundredths, 10_000) as i64)
+    }
+
+    /// The nearest multiple of `increment` (halves away from zero). `increment` must be
+    /// positive.
+    pub fn round_to(self, increment: Money) -> Self {
+        Money((div_round(self.0 as i128, increment.0 as i128) * increment.0 as i128) as i64)
+    }
+
+    /// The largest multiple of `increment` no more than this amount. `increment` must be
+    /// positive.
+    pub fn floor_to(self, increment: Money) -> Self {
+        Money(self.0.div_euclid(increment.0) * increment.0)
+    }
+
+    /// Parses a decimal amount such as `"12"`, `"-3.5"` or `"0.30000000000000004"`.
+    /// Digits past the cent are rounded (halves away from zero).
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let (negative, digits) = match value.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, value.strip_prefix('+').unwrap_or(value)),
+        };
+        let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        let all_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+        if (whole.is_empty() && fraction.is_empty()) || !all_digits(whole) || !all_digits(fraction)
+        {
+            // Exponent forms like "1E+1" are not worth an exact parser
+            return value
+                .parse::<f64>()
+                .ok()
+                .filter(|v| v.is_finite())
+                .map(|v| Money((v * 100.0).round() as i64));
+        }
+
+        let whole: i64 = if whole.is_empty() {
+            0
+        } else {
+            whole.parse().ok()?
+        };
+        let mut fraction_digits = fraction.bytes().map(|b| (b - b'0') as i64);
+        let tenths = fraction_digits.next().unwrap_or(0);
+        let hundredths = fraction_digits.next().unwrap_or(0);
+        let round_up = fraction_digits.next().unwrap_or(0) >= 5;
+
+        let cents = whole.checked_mul(100)? + tenths * 10 + hundredths + round_up as i64;
+        Some(Money(if negative { -cents } else { cents }))
+    }
+}
+
+/// `numerator / denominator` rounded to the nearest integer, halves away from zero.
+fn div_round(numerator: i128, denominator: i128) -> i128 {
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+    if remainder.abs() * 2 >= denominator.abs() {
+        quotient + numerator.signum() * denominator.signum()
+    } else {
+        quotient
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let cents = self.0.unsigned_abs();
+        write!(f, "{}{}.{:02}", sign, cents / 100, cents % 100)
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, other: Money) -> Money {
+        Money(self.0 + other.0)
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, other: Money) -> Money {
+        Money(self.0 - other.0)
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        Money(-self.0)
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, other: Money) {
+        self.0 += other.0;
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, other: Money) {
+        self.0 -= other.0;
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::ZERO, Add::add)
+    }
+}
+
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Accept the old float representation as well as decimal strings
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Text(String),
+            Number(f64),
+        }
+        match Repr::deserialize(deserializer)? {
+            Repr::Text(text) => Money::parse(&text)
+                .ok_or_else(|| serde::de::Error::custom(format!("invalid amount: {}", text))),
+            Repr::Number(number) => Ok(Money((number * 100.0).round() as i64)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percent_rounds_halves_away_from_zero() {
+        assert_eq!(Money::from_cents(5).percent(50.0), Money::from_cents(3));
+        assert_eq!(Money::from_cents(-5).percent(50.0), Money::from_cents(-3));
+        assert_eq!(Money::from_cents(-4).percent(50.0), Money::from_cents(-2));
+        assert_eq!(Money::from_cents(-1).percent(49.0), Money::ZERO);
+        // Hundredths of a percent are kept
+        assert_eq!(
+            Money::from_dollars(100).percent(12.345),
+            Money::from_cents(1235)
+        );
+    }
+
+    #[test]
+    fn rounding_to_an_increment_is_symmetric_but_flooring_is_not() {
+        let fifty = Money::from_cents(50);
+        assert_eq!(
+            Money::from_cents(125).round_to(fifty),
+            Money::from_cents(150)
+        );
+        assert_eq!(
+            Money::from_cents(-125).round_to(fifty),
+            Money::from_cents(-150)
+        );
+        assert_eq!(
+            Money::from_cents(-124).round_to(fifty),
+            Money::from_cents(-100)
+        );
+
+        assert_eq!(
+            Money::from_cents(149).floor_to(fifty),
+            Money::from_cents(100)
+        );
+        assert_eq!(
+            Money::from_cents(-101).floor_to(fifty),
+            Money::from_cents(-150)
+        );
+        assert_eq!(
+            Money::from_cents(-100).floor_to(fifty),
+            Money::from_cents(-100)
+        );
+    }
+
+    #[test]
+    fn parse_reads_decimals_and_legacy_floats() {
+        for (text, cents) in [
+            ("12", 1200),
+            ("+2", 200),
+            (" -3.5 ", -350),
+            (".5", 50),
+            ("0.30000000000000004", 30),
+            ("0.005", 1),
+            ("-0.005", -1),
+            ("-0.004", 0),
+            ("1E+1", 1000),
+        ] {
+            assert_eq!(
+                Money::parse(text),
+                Some(Money::from_cents(cents)),
+                "{}",
+                text
+            );
+        }
+        for text in ["", ".", "-", "abc", "1.2.3", "NaN", "inf"] {
+            assert_eq!(Money::parse(text), None, "{}", text);
+        }
+    }
+
+    #[test]
+    fn serializes_as_a_two_place_string() {
+        assert_eq!(Money::from_cents(-5).to_string(), "-0.05");
+        assert_eq!(Money::from_cents(1230).to_string(), "12.30");
+        assert_eq!(
+            serde_json::to_string(&Money::from_cents(-1230)).unwrap(),
+            "\"-12.30\""
+        );
+    }
+
+    #[test]
+    fn deserializes_strings_and_legacy_numbers() {
+        let parsed: Vec<Money> =
+            serde_json::from_str(r#"["-12.30", 0.30000000000000004, -2.5, 7]"#).unwrap();
+        assert_eq!(
+            parsed,
+            [
+                Money::from_cents(-1230),
+                Money::from_cents(30),
+                Money::from_cents(-250),
+                Money::from_dollars(7),
+            ]
+        );
+        assert!(serde_json::from_str::<Money>(r#""twelve""#).is_err());
+    }
+}
@@ -0,0 +1,223 @@
+//! Limits on what a conversion pays out of the pot. Without a policy a conversion pays
+//! its award table percentage of the whole pot, to the cent.
+
+use super::{Money, StorageError, StorageResult};
+use serde::{Deserialize, Serialize};
+
+/// Per-game rules applied to every conversion award, in this order: round the percentage
+/// of the pot to `rounding`, raise it to `min_award`, cap it at `max_award`, then cap it
+/// at what the pot holds above `reserve`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct PotPolicy {
+    /// The least a conversion pays, as long as the pot can cover it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_award: Option<Money>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_award: Option<Money>,
+    /// Left in the pot whatever is converted, so the next night doesn't start empty.
+    #[serde(default)]
+    pub reserve: Money,
+    /// Awards are whole multiples of this, e.g. `"0.25"`; to the cent when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rounding: Option<Money>,
+}
+
+/// The rule that set a conversion's award, when it isn't just the rounded percentage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AwardLimit {
+    MinAward,
+    MaxAward,
+    Reserve,
+}
+
+/// How a conversion's award was worked out, recorded on its transaction.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AwardBreakdown {
+    /// The split's percentage in the award table.
+    pub percent: f64,
+    /// That percentage of the pot, before the policy was applied.
+    pub uncapped: Money,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limited_by: Option<AwardLimit>,
+}
+
+impl PotPolicy {
+    fn increment(&self) -> Money {
+        self.rounding.unwrap_or(Money::from_cents(1))
+    }
+
+    /// Rejects a rounding increment or award limit that isn't positive, a negative
+    /// reserve, a minimum above the maximum and limits that aren't a whole number of
+    /// increments.
+    pub fn validate(&self) -> StorageResult<()> {
+        let invalid = |message: &str| Err(StorageError::InvalidConfig(message.to_string()));
+        if self
+            .rounding
+            .is_some_and(|rounding| rounding <= Money::ZERO)
+        {
+            return invalid("The rounding increment must be more than zero");
+        }
+        if self.reserve < Money::ZERO {
+            return invalid("The pot reserve can't be negative");
+        }
+        for limit in [self.min_award, self.max_award].into_iter().flatten() {
+            if limit <= Money::ZERO {
+                return invalid("Award limits must be more than zero");
+            }
+            if limit.round_to(self.increment()) != limit {
+                return invalid("Award limits must be a whole number of rounding increments");
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min_award, self.max_award) {
+            if min > max {
+                return invalid("The minimum award can't be more than the maximum");
+            }
+        }
+        Ok(())
+    }
+
+    /// What converting a split worth `percent`% pays from a pot holding `pot`. Never
+    /// more than the pot holds above the reserve, so a small or empty pot pays nothing.
+    pub fn award(&self, pot: Money, percent: f64) -> (Money, AwardBreakdown) {
+        let uncapped = pot.percent(percent);
+        let mut amount = uncapped.round_to(self.increment());
+        let mut limited_by = None;
+        if let Some(min) = self.min_award.filter(|min| amount < *min) {
+            amount = min;
+            limited_by = Some(AwardLimit::MinAward);
+        }
+        if let Some(max) = self.max_award.filter(|max| amount > *max) {
+            amount = max;
+            limited_by = Some(AwardLimit::MaxAward);
+        }
+        let available = (pot - self.reserve)
+            .max(Money::ZERO)
+            .floor_to(self.increment());
+        if amount > available {
+            amount = available;
+            limited_by = Some(AwardLimit::Reserve);
+        }
+        (
+            amount,
+            AwardBreakdown {
+                percent,
+                uncapped,
+                limited_by,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cents(cents: i64) -> Money {
+        Money::from_cents(cents)
+    }
+
+    #[test]
+    fn no_policy_pays_the_percentage_to_the_cent() {
+        // 50% of $10.05 is $5.025, and halves round up
+        let (amount, breakdown) = PotPolicy::default().award(cents(1005), 50.0);
+        assert_eq!(amount, cents(503));
+        assert_eq!(breakdown.uncapped, cents(503));
+        assert_eq!(breakdown.limited_by, None);
+    }
+
+    #[test]
+    fn rounds_to_the_increment() {
+        let policy = PotPolicy {
+            rounding: Some(cents(25)),
+            ..PotPolicy::default()
+        };
+        // $3.30 is nearer $3.25 than $3.50
+        assert_eq!(policy.award(cents(1000), 33.0).0, cents(325));
+        // $3.375 rounds to $3.38, then up to $3.50
+        assert_eq!(policy.award(cents(1000), 33.75).0, cents(350));
+    }
+
+    #[test]
+    fn minimum_and_maximum_bound_the_award() {
+        let policy = PotPolicy {
+            min_award: Some(cents(100)),
+            max_award: Some(cents(2000)),
+            ..PotPolicy::default()
+        };
+        let (amount, breakdown) = policy.award(cents(1000), 5.0);
+        assert_eq!(amount, cents(100));
+        assert_eq!(breakdown.uncapped, cents(50));
+        assert_eq!(breakdown.limited_by, Some(AwardLimit::MinAward));
+
+        let (amount, breakdown) = policy.award(cents(10000), 50.0);
+        assert_eq!(amount, cents(2000));
+        assert_eq!(breakdown.limited_by, Some(AwardLimit::MaxAward));
+
+        // Exactly at a limit isn't limited by it
+        let (amount, breakdown) = policy.award(cents(4000), 50.0);
+        assert_eq!(amount, cents(2000));
+        assert_eq!(breakdown.limited_by, None);
+    }
+
+    #[test]
+    fn reserve_is_never_paid_out() {
+        let policy = PotPolicy {
+            reserve: cents(500),
+            ..PotPolicy::default()
+        };
+        // Leaves exactly the reserve
+        let (amount, breakdown) = policy.award(cents(1000), 50.0);
+        assert_eq!(amount, cents(500));
+        assert_eq!(breakdown.limited_by, None);
+
+        let (amount, breakdown) = policy.award(cents(1200), 100.0);
+        assert_eq!(amount, cents(700));
+        assert_eq!(breakdown.limited_by, Some(AwardLimit::Reserve));
+
+        // A pot below the reserve pays nothing
+        assert_eq!(policy.award(cents(300), 100.0).0, Money::ZERO);
+    }
+
+    #[test]
+    fn pot_limit_wins_over_minimum_and_rounds_down() {
+        let policy = PotPolicy {
+            min_award: Some(cents(100)),
+            rounding: Some(cents(25)),
+            ..PotPolicy::default()
+        };
+        let (amount, breakdown) = policy.award(Money::ZERO, 50.0);
+        assert_eq!(amount, Money::ZERO);
+        assert_eq!(breakdown.limited_by, Some(AwardLimit::Reserve));
+
+        // $7.20 rounds to $7.25, more than the pot, so it's floored to $7.00
+        let (amount, breakdown) = policy.award(cents(720), 100.0);
+        assert_eq!(amount, cents(700));
+        assert_eq!(breakdown.limited_by, Some(AwardLimit::Reserve));
+    }
+
+    #[test]
+    fn validate_rejects_inconsistent_limits() {
+        let invalid =
+            |policy: PotPolicy| matches!(policy.validate(), Err(StorageError::InvalidConfig(_)));
+        assert!(invalid(PotPolicy {
+            min_award: Some(cents(500)),
+            max_award: Some(cents(100)),
+            ..PotPolicy::default()
+        }));
+        assert!(invalid(PotPolicy {
+            min_award: Some(cents(110)),
+            rounding: Some(cents(25)),
+            ..PotPolicy::default()
+        }));
+        assert!(invalid(PotPolicy {
+            reserve: cents(-1),
+            ..PotPolicy::default()
+        }));
+        assert!(invalid(PotPolicy {
+            rounding: Some(Money::ZERO),
+            ..PotPolicy::default()
+        }));
+        assert!(PotPolicy::default().validate().is_ok());
+    }
+}
@@ -0,0 +1,212 @@
+//! Who plays in a game. Players are known by a permanent [`PlayerId`]; their name is
+//! display metadata that can change at any time.
+
+use super::ledger::POT;
+use super::{AccountId, PlayerId, StorageError, StorageResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Player {
+    pub id: PlayerId,
+    pub name: String,
+    #[serde(default)]
+    pub retired: bool,
+    #[serde(default)]
+    pub former_names: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct Roster {
+    /// Everyone who has been in the game, retired or not.
+    pub players: Vec<Player>,
+    /// The account behind each name found in transactions recorded before players had
+    /// ids. Filled in once, when the game is migrated.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub legacy_names: BTreeMap<String, AccountId>,
+}
+
+/// Player names must be non-blank and free of surrounding whitespace.
+fn validate_name(name: &str) -> StorageResult<()> {
+    if name.trim().is_empty() {
+        return Err(StorageError::InvalidConfig(
+            "Player names can't be blank".to_string(),
+        ));
+    }
+    if name.trim() != name {
+        return Err(StorageError::InvalidConfig(format!(
+            "\"{}\" has leading or trailing spaces",
+            name
+        )));
+    }
+    Ok(())
+}
+
+impl Roster {
+    /// Builds a roster from the name-based layout used before players had ids: the
+    /// listed players, which of them retired, former names (former name to current
+    /// name) and any other name seen in the ledger, who is added as retired.
+    pub fn from_legacy(
+        players: Vec<String>,
+        retired: BTreeSet<String>,
+        aliases: BTreeMap<String, String>,
+        seen: impl IntoIterator<Item = String>,
+    ) -> Self {
+        let mut roster = Roster::default();
+        let add = |roster: &mut Roster, name: String, retired: bool| {
+            let id = roster.next_id();
+            roster
+                .legacy_names
+                .insert(name.clone(), AccountId::Player(id.clone()));
+            roster.players.push(Player {
+                id,
+                name,
+                retired,
+                former_names: Vec::new(),
+            });
+        };
+        for name in players {
+            if name != POT && !roster.legacy_names.contains_key(&name) {
+                let is_retired = retired.contains(&name);
+                add(&mut roster, name, is_retired);
+            }
+        }
+        for (former, current) in aliases {
+            if let Some(AccountId::Player(id)) = roster.legacy_names.get(&current).cloned() {
+                if let Some(player) = roster.players.iter_mut().find(|p| p.id == id) {
+                    player.former_names.push(former.clone());
+                }
+                roster.legacy_names.insert(former, AccountId::Player(id));
+            }
+        }
+        for name in seen {
+            if name != POT && !roster.legacy_names.contains_key(&name) {
+                add(&mut roster, name, true);
+            }
+        }
+        roster.legacy_names.insert(POT.to_string(), AccountId::Pot);
+        roster
+    }
+
+    fn next_id(&self) -> PlayerId {
+        let mut number = self.players.len() + 1;
+        loop {
+            let id = PlayerId(format!("p{}", number));
+            if !self.players.iter().any(|player| player.id == id) {
+                return id;
+            }
+            number += 1;
+        }
+    }
+
+    pub fn player(&self, id: &PlayerId) -> StorageResult<&Player> {
+        self.players
+            .iter()
+            .find(|player| &player.id == id)
+            .ok_or_else(|| StorageError::NotFound(format!("No player {}", id)))
+    }
+
+    fn player_mut(&mut self, id: &PlayerId) -> StorageResult<&mut Player> {
+        self.players
+            .iter_mut()
+            .find(|player| &player.id == id)
+            .ok_or_else(|| StorageError::NotFound(format!("No player {}", id)))
+    }
+
+    /// The pot and every player who hasn't retired.
+    pub fn active_accounts(&self) -> Vec<AccountId> {
+        std::iter::once(AccountId::Pot)
+            .chain(
+                self.players
+                    .iter()
+                    .filter(|player| !player.retired)
+                    .map(|player| AccountId::Player(player.id.clone())),
+            )
+            .collect()
+    }
+
+    /// Display name of an account; unknown players are shown by id.
+    pub fn name_of(&self, account: &AccountId) -> String {
+        match account {
+            AccountId::Pot => POT.to_string(),
+            AccountId::Player(id) => self
+                .player(id)
+                .map_or_else(|_| id.0.clone(), |player| player.name.clone()),
+        }
+    }
+
+    /// The account a name in a pre-migration transaction refers to.
+    pub fn legacy_account(&self, name: &str) -> AccountId {
+        self.legacy_names
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| AccountId::Player(PlayerId(name.to_string())))
+    }
+
+    /// Fails unless `id` is a player who hasn't retired.
+    pub fn require_active(&self, id: &PlayerId) -> StorageResult<()> {
+        let player = self.player(id)?;
+        if player.retired {
+            return Err(StorageError::InvalidConfig(format!(
+                "{} has retired",
+                player.name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Rejects `name` if a player other than `except` already goes by it (ignoring case).
+    fn check_available(&self, name: &str, except: Option<&PlayerId>) -> StorageResult<()> {
+        match self
+            .players
+            .iter()
+            .find(|player| player.name.eq_ignore_ascii_case(name) && Some(&player.id) != except)
+        {
+            Some(player) => Err(StorageError::InvalidConfig(format!(
+                "There is already a player named {}",
+                player.name
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Adds a newcomer, or brings back a retired player of exactly that name.
+    pub fn add(&mut self, name: &str) -> StorageResult<PlayerId> {
+        validate_name(name)?;
+        if let Some(player) = self
+            .players
+            .iter_mut()
+            .find(|player| player.retired && player.name == name)
+        {
+            player.retired = false;
+            return Ok(player.id.clone());
+        }
+        self.check_available(name, None)?;
+        let id = self.next_id();
+        self.players.push(Player {
+            id: id.clone(),
+            name: name.to_string(),
+            retired: false,
+            former_names: Vec::new(),
+        });
+        Ok(id)
+    }
+
+    pub fn rename(&mut self, id: &PlayerId, new_name: &str) -> StorageResult<()> {
+        validate_name(new_name)?;
+        self.check_available(new_name, Some(id))?;
+        let player = self.player_mut(id)?;
+        if player.name == new_name {
+            return Ok(());
+        }
+        let old_name = std::mem::replace(&mut player.name, new_name.to_string());
+        player.former_names.retain(|name| name != new_name);
+        player.former_names.push(old_name);
+        Ok(())
+    }
+
+    pub fn retire(&mut self, id: &PlayerId) -> StorageResult<()> {
+        self.player_mut(id)?.retired = true;
+        Ok(())
+    }
+}
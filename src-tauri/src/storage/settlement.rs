@@ -0,0 +1,358 @@
+//! Squaring up: the payments that bring a game's balances back to zero.
+
+use super::ledger;
+use super::{
+    AccountId, Balance, Money, Roster, StorageError, StorageResult, Transaction, TransactionId,
+    TransactionKind,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// `from` hands `amount` to `to`. Recorded as a transaction with `from` as creditor,
+/// since paying raises the payer's balance.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Payment {
+    pub from: AccountId,
+    pub to: AccountId,
+    pub amount: Money,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SettlementPlan {
+    pub payments: Vec<Payment>,
+    /// Balances still open once the payments are made: none, unless the pot was kept,
+    /// in which case these are what players owe to or are owed by the pot.
+    pub remaining: Vec<Balance>,
+}
+
+/// Above this many accounts with a balance, [`zero_sum_groups`] stops searching and
+/// treats everyone as one group; its search doubles with every account.
+const MAX_SEARCHED_ACCOUNTS: usize = 16;
+
+/// The fewest payments that zero `balances`, or everyone but the pot when `keep_pot` is
+/// set.
+///
+/// A group of accounts whose balances sum to zero can settle among themselves in one
+/// payment fewer than there are of them, so the accounts are split into as many such
+/// groups as possible and each group is settled on its own. With `keep_pot`, the group
+/// holding the pot settles as far as it can without it.
+pub fn plan(balances: &[Balance], keep_pot: bool) -> SettlementPlan {
+    let mut open: Vec<Balance> = balances
+        .iter()
+        .filter(|balance| !balance.amount.is_zero())
+        .cloned()
+        .collect();
+    // Largest credits first, ties in name order so plans don't shuffle between calls
+    open.sort_by(|a, b| b.amount.cmp(&a.amount).then_with(|| a.name.cmp(&b.name)));
+
+    let amounts: Vec<Money> = open.iter().map(|balance| balance.amount).collect();
+    let mut payments = Vec::new();
+    let mut remaining = Vec::new();
+    for group in zero_sum_groups(&amounts) {
+        let members = group
+            .into_iter()
+            .map(|index| open[index].clone())
+            .filter(|balance| !(keep_pot && balance.account == AccountId::Pot))
+            .collect();
+        let (group_payments, group_remaining) = settle(members);
+        payments.extend(group_payments);
+        remaining.extend(group_remaining);
+    }
+    SettlementPlan {
+        payments,
+        remaining,
+    }
+}
+
+/// Splits `amounts` into as many groups summing to zero as possible, as lists of
+/// indices in ascending order, with the groups ordered by their first index. Amounts
+/// that don't sum to zero overall leave one group that doesn't either.
+///
+/// `best[mask]` is the most zero-sum groups the accounts in `mask` can be split into,
+/// built up one account at a time: each subset whose sum is zero closes a group.
+fn zero_sum_groups(amounts: &[Money]) -> Vec<Vec<usize>> {
+    let count = amounts.len();
+    if count > MAX_SEARCHED_ACCOUNTS {
+        return vec![(0..count).collect()];
+    }
+    let subsets = 1usize << count;
+    let mut sums = vec![Money::ZERO; subsets];
+    let mut best = vec![0u8; subsets];
+    // The account removed from each subset on the way to its best split
+    let mut last = vec![0usize; subsets];
+    for mask in 1..subsets {
+        let lowest = mask.trailing_zeros() as usize;
+        sums[mask] = sums[mask & (mask - 1)] + amounts[lowest];
+        let (groups, removed) = (0..count)
+            .filter(|index| mask & (1 << index) != 0)
+            .map(|index| (best[mask ^ (1 << index)], index))
+            // Earliest of equals, so plans are stable
+            .max_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)))
+            .expect("non-empty subset");
+        best[mask] = groups + u8::from(sums[mask].is_zero());
+        last[mask] = removed;
+    }
+
+    let mut groups = Vec::new();
+    let mut group = Vec::new();
+    let mut mask = subsets - 1;
+    while mask != 0 {
+        group.push(last[mask]);
+        mask ^= 1 << last[mask];
+        if sums[mask].is_zero() {
+            group.sort_unstable();
+            groups.push(std::mem::take(&mut group));
+        }
+    }
+    groups.sort_unstable();
+    groups
+}
+
+/// Settles `open` as far as it can, pairing equal and opposite balances first and then
+/// repeatedly paying the largest debt to the largest credit. A group summing to zero
+/// takes at most one payment fewer than it has members; otherwise what's left over is
+/// returned.
+fn settle(mut open: Vec<Balance>) -> (Vec<Payment>, Vec<Balance>) {
+    let mut payments = Vec::new();
+    let mut pay = |open: &mut Vec<Balance>, debtor: usize, creditor: usize, amount: Money| {
+        payments.push(Payment {
+            from: open[debtor].account.clone(),
+            to: open[creditor].account.clone(),
+            amount,
+        });
+        open[debtor].amount += amount;
+        open[creditor].amount -= amount;
+    };
+
+    for creditor in 0..open.len() {
+        let amount = open[creditor].amount;
+        if amount <= Money::ZERO {
+            continue;
+        }
+        if let Some(debtor) = open.iter().position(|balance| balance.amount == -amount) {
+            pay(&mut open, debtor, creditor, amount);
+        }
+    }
+
+    loop {
+        // Earliest of equals, so ties go in name order
+        let creditor = open
+            .iter()
+            .enumerate()
+            .filter(|(_, balance)| balance.amount > Money::ZERO)
+            .min_by_key(|(_, balance)| -balance.amount)
+            .map(|(index, _)| index);
+        let debtor = open
+            .iter()
+            .enumerate()
+            .filter(|(_, balance)| balance.amount < Money::ZERO)
+            .min_by_key(|(_, balance)| balance.amount)
+            .map(|(index, _)| index);
+        let (Some(creditor), Some(debtor)) = (creditor, debtor) else {
+            break;
+        };
+        let amount = open[creditor].amount.min(-open[debtor].amount);
+        pay(&mut open, debtor, creditor, amount);
+    }
+
+    open.retain(|balance| !balance.amount.is_zero());
+    (payments, open)
+}
+
+/// Rejects an empty settlement, payments that aren't positive or go nowhere, and
+/// accounts that aren't in the game. Retired players can still settle up.
+pub fn validate(payments: &[Payment], roster: &Roster) -> StorageResult<()> {
+    if payments.is_empty() {
+        return Err(StorageError::InvalidConfig(
+            "A settlement needs at least one payment".to_string(),
+        ));
+    }
+    for payment in payments {
+        if payment.amount <= Money::ZERO {
+            return Err(StorageError::InvalidConfig(
+                "Payments must be more than zero".to_string(),
+            ));
+        }
+        if payment.from == payment.to {
+            return Err(StorageError::InvalidConfig(format!(
+                "{} can't pay themselves",
+                roster.name_of(&payment.from)
+            )));
+        }
+        for account in [&payment.from, &payment.to] {
+            if let Some(player) = account.player() {
+                roster.player(player)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The transactions recording `payments` at `time`, numbered on from `first_id`, given
+/// the pot held `pot` beforehand.
+pub fn to_transactions(
+    payments: &[Payment],
+    first_id: TransactionId,
+    time: DateTime<Utc>,
+    mut pot: Money,
+) -> Vec<Transaction> {
+    payments
+        .iter()
+        .zip(first_id.0..)
+        .map(|(payment, id)| {
+            let transaction = Transaction {
+                id: TransactionId(id),
+                kind: TransactionKind::Settlement,
+                creditor: payment.from.clone(),
+                debtor: payment.to.clone(),
+                amount: payment.amount,
+                split: None,
+                time,
+                pot_amount: pot,
+                stake: None,
+                award: None,
+                voided: None,
+            };
+            let mut balances = HashMap::from([(AccountId::Pot, pot)]);
+            ledger::apply(&mut balances, &transaction);
+            pot = ledger::pot_balance(&balances);
+            transaction
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::PlayerId;
+
+    fn balance(name: &str, dollars: i64) -> Balance {
+        let account = if name == "Pot" {
+            AccountId::Pot
+        } else {
+            AccountId::Player(PlayerId(name.to_string()))
+        };
+        Balance {
+            account,
+            name: name.to_string(),
+            amount: Money::from_dollars(dollars),
+        }
+    }
+
+    /// The balances once `plan`'s payments are made.
+    fn after(balances: &[Balance], plan: &SettlementPlan) -> HashMap<AccountId, Money> {
+        let mut after: HashMap<_, _> = balances
+            .iter()
+            .map(|balance| (balance.account.clone(), balance.amount))
+            .collect();
+        for payment in &plan.payments {
+            *after.get_mut(&payment.from).unwrap() += payment.amount;
+            *after.get_mut(&payment.to).unwrap() -= payment.amount;
+        }
+        after
+    }
+
+    #[test]
+    fn settles_zero_sum_groups_separately() {
+        // {Ann, Bob, Cat} and {Dan, Eve, Fay} each sum to zero; settling everyone as
+        // one group largest-first takes five payments
+        let balances = [
+            balance("Ann", 3),
+            balance("Bob", 2),
+            balance("Cat", -5),
+            balance("Dan", 4),
+            balance("Eve", 4),
+            balance("Fay", -8),
+        ];
+        let plan = plan(&balances, false);
+
+        assert_eq!(plan.payments.len(), 4);
+        assert!(plan.remaining.is_empty());
+        assert!(after(&balances, &plan)
+            .values()
+            .all(|amount| amount.is_zero()));
+    }
+
+    #[test]
+    fn pairs_equal_and_opposite_balances() {
+        let balances = [
+            balance("Ann", 7),
+            balance("Bob", -7),
+            balance("Cat", 1),
+            balance("Dan", -1),
+        ];
+        let plan = plan(&balances, false);
+
+        assert_eq!(
+            plan.payments,
+            [
+                Payment {
+                    from: balances[1].account.clone(),
+                    to: balances[0].account.clone(),
+                    amount: Money::from_dollars(7),
+                },
+                Payment {
+                    from: balances[3].account.clone(),
+                    to: balances[2].account.clone(),
+                    amount: Money::from_dollars(1),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn keeping_the_pot_leaves_it_intact() {
+        let balances = [
+            balance("Pot", 10),
+            balance("Ann", -4),
+            balance("Bob", -8),
+            balance("Cat", 2),
+            balance("Dan", 3),
+            balance("Eve", -3),
+        ];
+        let plan = plan(&balances, true);
+
+        // Eve pays Dan, and Cat is paid by one of the pot's debtors
+        assert_eq!(plan.payments.len(), 2);
+        assert!(plan
+            .payments
+            .iter()
+            .all(|payment| payment.from != AccountId::Pot && payment.to != AccountId::Pot));
+        assert!(plan
+            .remaining
+            .iter()
+            .all(|balance| balance.amount < Money::ZERO));
+        assert_eq!(
+            plan.remaining
+                .iter()
+                .map(|balance| balance.amount)
+                .sum::<Money>(),
+            Money::from_dollars(-10)
+        );
+        assert_eq!(
+            after(&balances, &plan)[&AccountId::Pot],
+            Money::from_dollars(10)
+        );
+    }
+
+    #[test]
+    fn settling_everyone_includes_the_pot() {
+        let balances = [
+            balance("Pot", 10),
+            balance("Ann", -4),
+            balance("Bob", -8),
+            balance("Cat", 2),
+            balance("Dan", 3),
+            balance("Eve", -3),
+        ];
+        let plan = plan(&balances, false);
+
+        // {Dan, Eve} and {Pot, Ann, Bob, Cat}
+        assert_eq!(plan.payments.len(), 4);
+        assert!(plan.remaining.is_empty());
+        assert!(after(&balances, &plan)
+            .values()
+            .all(|amount| amount.is_zero()));
+    }
+}
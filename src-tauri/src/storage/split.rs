@@ -0,0 +1,189 @@
+use super::geometry;
+use super::{StorageError, StorageResult};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// The pins left standing after the first ball, as a bitmask where bit `n - 1` is pin `n`.
+///
+/// Only real splits can be constructed: at least two pins, all between 1 and 10, with the
+/// headpin down. The canonical text form lists the pins in ascending order joined by
+/// dashes (`"7-10"`), which is also how a split is serialized; parsing accepts any order,
+/// spacing and `-`/`,` separators, and deserializing also accepts an array of pins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Split(u16);
+
+pub const HEADPIN: u8 = 1;
+pub const PIN_COUNT: u8 = 10;
+
+impl Split {
+    pub fn from_pins(pins: impl IntoIterator<Item = u8>) -> StorageResult<Self> {
+        let mut mask = 0u16;
+        for pin in pins {
+            if !(1..=PIN_COUNT).contains(&pin) {
+                return Err(StorageError::InvalidSplit(format!(
+                    "Pin {} is not between 1 and {}",
+                    pin, PIN_COUNT
+                )));
+            }
+            mask |= 1 << (pin - 1);
+        }
+        Self::from_mask(mask)
+    }
+
+    fn from_mask(mask: u16) -> StorageResult<Self> {
+        let split = Split(mask);
+        if split.contains(HEADPIN) {
+            return Err(StorageError::InvalidSplit(format!(
+                "{} leaves the headpin standing",
+                split
+            )));
+        }
+        if split.pin_count() < 2 {
+            return Err(StorageError::InvalidSplit(format!(
+                "A split needs at least two pins, got {}",
+                if split.pin_count() == 0 {
+                    "none".to_string()
+                } else {
+                    split.to_string()
+                }
+            )));
+        }
+        Ok(split)
+    }
+
+    pub fn contains(self, pin: u8) -> bool {
+        (1..=PIN_COUNT).contains(&pin) && self.0 & (1 << (pin - 1)) != 0
+    }
+
+    pub fn pin_count(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Standing pins in ascending order.
+    pub fn pins(self) -> impl Iterator<Item = u8> {
+        (1..=PIN_COUNT).filter(move |&pin| self.contains(pin))
+    }
+
+    /// Every valid split, in mask order.
+    pub fn all() -> impl Iterator<Item = Split> {
+        (0..1u16 << PIN_COUNT).filter_map(|mask| Split::from_mask(mask).ok())
+    }
+
+    /// See [`geometry::lateral_gap`].
+    pub fn lateral_gap(self) -> u8 {
+        geometry::lateral_gap(&self.pins().collect::<Vec<_>>())
+    }
+
+    /// See [`geometry::row_span`].
+    pub fn row_span(self) -> u8 {
+        geometry::row_span(&self.pins().collect::<Vec<_>>())
+    }
+
+    /// See [`geometry::is_cluster`]. The standard awards don't pay for clusters.
+    pub fn is_cluster(self) -> bool {
+        geometry::is_cluster(&self.pins().collect::<Vec<_>>())
+    }
+}
+
+impl FromStr for Split {
+    type Err = StorageError;
+
+    fn from_str(value: &str) -> StorageResult<Self> {
+        let pins = value
+            .split(|c: char| c == '-' || c == ',' || c.is_whitespace())
+            .filter(|part| !part.is_empty())
+            .map(|part| {
+                part.parse::<u8>().map_err(|_| {
+                    StorageError::InvalidSplit(format!("{:?} is not a pin number", part))
+                })
+            })
+            .collect::<StorageResult<Vec<u8>>>()?;
+        Split::from_pins(pins)
+    }
+}
+
+impl fmt::Display for Split {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pins: Vec<String> = self.pins().map(|pin| pin.to_string()).collect();
+        write!(f, "{}", pins.join("-"))
+    }
+}
+
+impl Serialize for Split {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Split {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Text(String),
+            Pins(Vec<u8>),
+        }
+        match Repr::deserialize(deserializer)? {
+            Repr::Text(text) => text.parse(),
+            Repr::Pins(pins) => Split::from_pins(pins),
+        }
+        .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_any_order_and_separator_to_canonical_form() {
+        for text in ["7-10", "10-7", "10, 7", " 7 10 ", "7-7-10"] {
+            let split: Split = text.parse().unwrap();
+            assert_eq!(split.to_string(), "7-10", "{:?}", text);
+        }
+        assert_eq!(
+            Split::from_pins([10, 4, 7, 4])
+                .unwrap()
+                .pins()
+                .collect::<Vec<_>>(),
+            [4, 7, 10]
+        );
+    }
+
+    #[test]
+    fn rejects_a_standing_headpin() {
+        assert!(matches!(
+            "1-7".parse::<Split>(),
+            Err(StorageError::InvalidSplit(message)) if message.contains("headpin")
+        ));
+        assert!(Split::all().all(|split| !split.contains(HEADPIN)));
+    }
+
+    #[test]
+    fn rejects_too_few_or_unknown_pins() {
+        for text in ["", "7", "7-7", "0-7", "7-11", "7-x", "-"] {
+            assert!(
+                matches!(text.parse::<Split>(), Err(StorageError::InvalidSplit(_))),
+                "{:?}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn every_split_has_at_least_two_pins() {
+        // Two or more of the nine pins behind the headpin
+        assert_eq!(Split::all().count(), (1 << 9) - 1 - 9);
+        assert!(Split::all().all(|split| split.pin_count() >= 2));
+    }
+
+    #[test]
+    fn serde_round_trips_the_canonical_string_and_reads_pin_lists() {
+        let split: Split = "7-10".parse().unwrap();
+        assert_eq!(serde_json::to_string(&split).unwrap(), "\"7-10\"");
+        assert_eq!(serde_json::from_str::<Split>("\"10-7\"").unwrap(), split);
+        assert_eq!(serde_json::from_str::<Split>("[10, 7]").unwrap(), split);
+        assert!(serde_json::from_str::<Split>("[1, 7]").is_err());
+    }
+}
@@ -0,0 +1,99 @@
+use super::split::PIN_COUNT;
+use super::{Split, StorageError, StorageResult};
+use serde::{Deserialize, Serialize};
+
+/// Inclusive bounds on a measurement; a missing end is unbounded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub struct Bounds {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<u8>,
+}
+
+impl Bounds {
+    pub fn contains(self, value: u8) -> bool {
+        self.min.is_none_or(|min| value >= min) && self.max.is_none_or(|max| value <= max)
+    }
+}
+
+/// A predicate over the pins left standing, used to decide which award tier a split
+/// falls in. Serialized externally tagged, e.g. `{ "contains_all": [7, 10] }` or
+/// `{ "not": "cluster" }`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SplitRule {
+    /// Every listed pin is standing.
+    ContainsAll(Vec<u8>),
+    /// At least one listed pin is standing.
+    ContainsAny(Vec<u8>),
+    /// No pin outside the list is standing.
+    Within(Vec<u8>),
+    PinCount(Bounds),
+    /// See [`Split::lateral_gap`].
+    LateralGap(Bounds),
+    /// See [`Split::row_span`].
+    RowSpan(Bounds),
+    /// One of these exact leaves.
+    Exact(Vec<Split>),
+    /// See [`Split::is_cluster`].
+    Cluster,
+    All(Vec<SplitRule>),
+    Any(Vec<SplitRule>),
+    Not(Box<SplitRule>),
+}
+
+impl SplitRule {
+    pub fn matches(&self, split: Split) -> bool {
+        match self {
+            SplitRule::ContainsAll(pins) => pins.iter().all(|&pin| split.contains(pin)),
+            SplitRule::ContainsAny(pins) => pins.iter().any(|&pin| split.contains(pin)),
+            SplitRule::Within(pins) => split.pins().all(|pin| pins.contains(&pin)),
+            SplitRule::PinCount(bounds) => bounds.contains(split.pin_count() as u8),
+            SplitRule::LateralGap(bounds) => bounds.contains(split.lateral_gap()),
+            SplitRule::RowSpan(bounds) => bounds.contains(split.row_span()),
+            SplitRule::Exact(splits) => splits.contains(&split),
+            SplitRule::Cluster => split.is_cluster(),
+            SplitRule::All(rules) => rules.iter().all(|rule| rule.matches(split)),
+            SplitRule::Any(rules) => rules.iter().any(|rule| rule.matches(split)),
+            SplitRule::Not(rule) => !rule.matches(split),
+        }
+    }
+
+    /// Rejects pins outside 1–10, inverted bounds and empty `all`/`any` lists, which are
+    /// almost certainly editing mistakes rather than intended always/never rules.
+    pub fn validate(&self) -> StorageResult<()> {
+        match self {
+            SplitRule::ContainsAll(pins)
+            | SplitRule::ContainsAny(pins)
+            | SplitRule::Within(pins) => {
+                match pins.iter().find(|pin| !(1..=PIN_COUNT).contains(*pin)) {
+                    Some(pin) => Err(StorageError::InvalidConfig(format!(
+                        "Pin {} is not between 1 and {}",
+                        pin, PIN_COUNT
+                    ))),
+                    None => Ok(()),
+                }
+            }
+            SplitRule::PinCount(bounds)
+            | SplitRule::LateralGap(bounds)
+            | SplitRule::RowSpan(bounds) => match (bounds.min, bounds.max) {
+                (Some(min), Some(max)) if min > max => Err(StorageError::InvalidConfig(format!(
+                    "Minimum {} is above maximum {}",
+                    min, max
+                ))),
+                _ => Ok(()),
+            },
+            SplitRule::Exact(_) | SplitRule::Cluster => Ok(()),
+            SplitRule::All(rules) | SplitRule::Any(rules) => {
+                if rules.is_empty() {
+                    return Err(StorageError::InvalidConfig(
+                        "A combined rule has no rules".to_string(),
+                    ));
+                }
+                rules.iter().try_for_each(SplitRule::validate)
+            }
+            SplitRule::Not(rule) => rule.validate(),
+        }
+    }
+}
@@ -0,0 +1,319 @@
+//! What a player pays into the pot for a split attempt. The stake is a game setting that
+//! can vary by player and climb with each split a player records in the same night.
+
+use super::ledger::SPLIT_STAKE;
+use super::{Money, PlayerId, StorageError, StorageResult, Transaction, TransactionKind};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// How far back a player's earlier splits count toward escalation.
+const NIGHT_HOURS: i64 = 12;
+
+/// How the stake grows with each earlier split the player recorded that night.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Escalation {
+    /// Adds `step` per earlier split: $1, $1.50, $2, ...
+    Add { step: Money },
+    /// Multiplies by `factor` per earlier split: $1, $2, $4, ...
+    Multiply { factor: f64 },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StakePolicy {
+    /// A player's first split of the night, before their multiplier.
+    pub base: Money,
+    /// Scales the stake for particular players, e.g. `0.5` for juniors. Anyone not listed
+    /// pays the full stake.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub player_multipliers: BTreeMap<PlayerId, f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub escalation: Option<Escalation>,
+    /// No stake is larger than this, however far it has escalated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_stake: Option<Money>,
+}
+
+/// Games store either a full policy or, from before stakes could vary, a bare amount.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredStake {
+    Flat(Money),
+    Policy {
+        base: Money,
+        #[serde(default)]
+        player_multipliers: BTreeMap<PlayerId, f64>,
+        #[serde(default)]
+        escalation: Option<Escalation>,
+        #[serde(default)]
+        max_stake: Option<Money>,
+    },
+}
+
+impl From<StoredStake> for StakePolicy {
+    fn from(stored: StoredStake) -> Self {
+        match stored {
+            StoredStake::Flat(base) => StakePolicy::flat(base),
+            StoredStake::Policy {
+                base,
+                player_multipliers,
+                escalation,
+                max_stake,
+            } => StakePolicy {
+                base,
+                player_multipliers,
+                escalation,
+                max_stake,
+            },
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for StakePolicy {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        StoredStake::deserialize(deserializer).map(Into::into)
+    }
+}
+
+impl Default for StakePolicy {
+    fn default() -> Self {
+        StakePolicy::flat(SPLIT_STAKE)
+    }
+}
+
+/// How a split's stake was worked out, recorded on its transaction.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct StakeBreakdown {
+    pub base: Money,
+    /// The player's multiplier, 1 unless the policy lists them.
+    pub multiplier: f64,
+    /// 1 for the player's first split of the night, 2 for the second, ...
+    pub attempt: u32,
+}
+
+/// Start of the window in which a player's splits count as the same night.
+pub fn night_start(now: DateTime<Utc>) -> DateTime<Utc> {
+    now - Duration::hours(NIGHT_HOURS)
+}
+
+/// Whether `transaction` is `player` paying a stake into the pot. Undone splits don't
+/// count toward escalation.
+fn is_stake_paid_by(transaction: &Transaction, player: &PlayerId) -> bool {
+    transaction.kind == TransactionKind::Split
+        && transaction.player() == Some(player)
+        && !transaction.is_voided()
+}
+
+impl StakePolicy {
+    /// The same stake for everyone, every time.
+    pub fn flat(base: Money) -> Self {
+        Self {
+            base,
+            player_multipliers: BTreeMap::new(),
+            escalation: None,
+            max_stake: None,
+        }
+    }
+
+    /// Rejects a stake, multiplier or cap that isn't positive, a negative step and a
+    /// factor below 1.
+    pub fn validate(&self) -> StorageResult<()> {
+        let invalid = |message: String| Err(StorageError::InvalidConfig(message));
+        if self.base <= Money::ZERO {
+            return invalid("The stake must be more than zero".to_string());
+        }
+        for (player, multiplier) in &self.player_multipliers {
+            if !multiplier.is_finite() || *multiplier <= 0.0 {
+                return invalid(format!(
+                    "The multiplier for {} must be more than zero",
+                    player
+                ));
+            }
+        }
+        match &self.escalation {
+            Some(Escalation::Add { step }) if *step < Money::ZERO => {
+                return invalid("The escalation step can't be negative".to_string());
+            }
+            Some(Escalation::Multiply { factor }) if !factor.is_finite() || *factor < 1.0 => {
+                return invalid("The escalation factor must be at least 1".to_string());
+            }
+            _ => {}
+        }
+        if self.max_stake.is_some_and(|max| max <= Money::ZERO) {
+            return invalid("The maximum stake must be more than zero".to_string());
+        }
+        Ok(())
+    }
+
+    /// The stake `player` pays for a split now, given the game's transactions since
+    /// [`night_start`]. Older transactions are ignored, so callers may pass more.
+    pub fn stake_for<'a>(
+        &self,
+        player: &PlayerId,
+        now: DateTime<Utc>,
+        recent: impl IntoIterator<Item = &'a Transaction>,
+    ) -> (Money, StakeBreakdown) {
+        let since = night_start(now);
+        let earlier = recent
+            .into_iter()
+            .filter(|t| t.time >= since && is_stake_paid_by(t, player))
+            .count() as u32;
+
+        let escalated = match &self.escalation {
+            None => self.base,
+            Some(Escalation::Add { step }) => {
+                self.base + Money::from_cents(step.cents() * earlier as i64)
+            }
+            Some(Escalation::Multiply { factor }) => {
+                self.base.percent(factor.powi(earlier as i32) * 100.0)
+            }
+        };
+        let multiplier = self.player_multipliers.get(player).copied().unwrap_or(1.0);
+        let mut amount = escalated.percent(multiplier * 100.0);
+        if let Some(max) = self.max_stake {
+            amount = amount.min(max);
+        }
+        (
+            amount,
+            StakeBreakdown {
+                base: self.base,
+                multiplier,
+                attempt: earlier + 1,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{AccountId, TransactionId, Void};
+    use chrono::TimeZone;
+
+    fn ann() -> PlayerId {
+        PlayerId("p1".to_string())
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 15, 22, 0, 0).unwrap()
+    }
+
+    /// A split `player` recorded at `time`.
+    fn split_by(player: &PlayerId, time: DateTime<Utc>) -> Transaction {
+        Transaction {
+            id: TransactionId(1),
+            kind: TransactionKind::Split,
+            creditor: AccountId::Pot,
+            debtor: AccountId::Player(player.clone()),
+            amount: Money::from_dollars(1),
+            split: Some("7-10".parse().unwrap()),
+            time,
+            pot_amount: Money::ZERO,
+            stake: None,
+            award: None,
+            voided: None,
+        }
+    }
+
+    #[test]
+    fn add_escalation_steps_per_earlier_split() {
+        let policy = StakePolicy {
+            escalation: Some(Escalation::Add {
+                step: Money::from_cents(50),
+            }),
+            ..StakePolicy::flat(Money::from_dollars(1))
+        };
+        let earlier = [
+            split_by(&ann(), now() - Duration::hours(2)),
+            split_by(&ann(), now() - Duration::hours(1)),
+        ];
+
+        let (amount, breakdown) = policy.stake_for(&ann(), now(), &earlier);
+        assert_eq!(amount, Money::from_dollars(2));
+        assert_eq!(breakdown.attempt, 3);
+        assert_eq!(breakdown.multiplier, 1.0);
+    }
+
+    #[test]
+    fn multiplier_applies_after_escalation_and_before_the_cap() {
+        let policy = StakePolicy {
+            base: Money::from_dollars(1),
+            player_multipliers: BTreeMap::from([(ann(), 0.5)]),
+            escalation: Some(Escalation::Multiply { factor: 2.0 }),
+            max_stake: None,
+        };
+        let earlier: Vec<_> = (1..=3)
+            .map(|hours| split_by(&ann(), now() - Duration::hours(hours)))
+            .collect();
+
+        // $1 doubled three times, halved
+        let (amount, _) = policy.stake_for(&ann(), now(), &earlier);
+        assert_eq!(amount, Money::from_dollars(4));
+
+        let capped = StakePolicy {
+            max_stake: Some(Money::from_dollars(3)),
+            ..policy
+        };
+        let (amount, breakdown) = capped.stake_for(&ann(), now(), &earlier);
+        assert_eq!(amount, Money::from_dollars(3));
+        assert_eq!(breakdown.base, Money::from_dollars(1));
+    }
+
+    #[test]
+    fn night_window_includes_its_start_only() {
+        let policy = StakePolicy {
+            escalation: Some(Escalation::Add {
+                step: Money::from_dollars(1),
+            }),
+            ..StakePolicy::flat(Money::from_dollars(1))
+        };
+        let start = night_start(now());
+        assert_eq!(start, now() - Duration::hours(12));
+
+        let at_start = [split_by(&ann(), start)];
+        assert_eq!(policy.stake_for(&ann(), now(), &at_start).1.attempt, 2);
+
+        let before_start = [split_by(&ann(), start - Duration::seconds(1))];
+        assert_eq!(policy.stake_for(&ann(), now(), &before_start).1.attempt, 1);
+    }
+
+    #[test]
+    fn only_the_players_counted_splits_escalate() {
+        let policy = StakePolicy {
+            escalation: Some(Escalation::Add {
+                step: Money::from_dollars(1),
+            }),
+            ..StakePolicy::flat(Money::from_dollars(1))
+        };
+        let mut undone = split_by(&ann(), now() - Duration::hours(1));
+        undone.voided = Some(Void::now("Lane 3".to_string(), None));
+        let mut conversion = split_by(&ann(), now() - Duration::hours(1));
+        conversion.kind = TransactionKind::Conversion;
+        let someone_else = split_by(&PlayerId("p2".to_string()), now() - Duration::hours(1));
+
+        let (amount, breakdown) =
+            policy.stake_for(&ann(), now(), &[undone, conversion, someone_else]);
+        assert_eq!(amount, Money::from_dollars(1));
+        assert_eq!(breakdown.attempt, 1);
+    }
+
+    #[test]
+    fn bare_amount_reads_as_flat_stake() {
+        let policy: StakePolicy = serde_json::from_str(r#""2.00""#).unwrap();
+        assert_eq!(policy, StakePolicy::flat(Money::from_dollars(2)));
+    }
+
+    #[test]
+    fn validate_rejects_shrinking_escalation() {
+        let policy = StakePolicy {
+            escalation: Some(Escalation::Multiply { factor: 0.5 }),
+            ..StakePolicy::default()
+        };
+        assert!(matches!(
+            policy.validate(),
+            Err(StorageError::InvalidConfig(_))
+        ));
+        assert!(StakePolicy::default().validate().is_ok());
+    }
+}
@@ -0,0 +1,250 @@
+//! Conversion rates derived from the ledger. A split transaction (stake paid into the
+//! pot) is an attempt and a conversion transaction (award paid out of it) is a make,
+//! both tagged with the split, so no extra bookkeeping is needed.
+
+use super::{AccountId, PlayerId, Roster, Split, Transaction, TransactionKind};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// How many attempts' worth of weight the broader average gets when smoothing a rate.
+const PRIOR_WEIGHT: f64 = 5.0;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Tally {
+    attempts: u32,
+    conversions: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ConversionRate {
+    pub attempts: u32,
+    pub conversions: u32,
+    /// `conversions / attempts`, `None` before the first attempt.
+    pub rate: Option<f64>,
+    /// The rate shrunk toward a broader average so rarely seen splits don't read as 0%
+    /// or 100%: `(conversions + w * prior) / (attempts + w)`.
+    pub smoothed_rate: f64,
+}
+
+impl ConversionRate {
+    fn new(tally: Tally, prior: f64) -> Self {
+        let attempts = tally.attempts as f64;
+        let conversions = tally.conversions as f64;
+        Self {
+            attempts: tally.attempts,
+            conversions: tally.conversions,
+            rate: (tally.attempts > 0).then(|| conversions / attempts),
+            smoothed_rate: (conversions + PRIOR_WEIGHT * prior) / (attempts + PRIOR_WEIGHT),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SplitRecord {
+    pub split: Split,
+    #[serde(flatten)]
+    pub stats: ConversionRate,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlayerRecord {
+    pub player: PlayerId,
+    /// The player's current name.
+    pub name: String,
+    #[serde(flatten)]
+    pub stats: ConversionRate,
+    /// Each split the player has faced; rates are smoothed toward the split's overall rate.
+    pub splits: Vec<SplitRecord>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SplitStats {
+    pub overall: ConversionRate,
+    /// Smoothed toward the overall rate.
+    pub by_split: Vec<SplitRecord>,
+    /// Smoothed toward the overall rate.
+    pub by_player: Vec<PlayerRecord>,
+}
+
+/// Attempts and conversions for every split and player in `transactions`, with players
+/// named from `roster`. Voided transactions are left out.
+pub fn split_stats(transactions: &[Transaction], roster: &Roster) -> SplitStats {
+    let mut tallies: HashMap<(&PlayerId, Split), Tally> = HashMap::new();
+    for transaction in transactions.iter().filter(|t| !t.is_voided()) {
+        let (Some(split), Some(player)) = (transaction.split, transaction.player()) else {
+            continue;
+        };
+        match transaction.kind {
+            TransactionKind::Split => {
+                tallies.entry((player, split)).or_default().attempts += 1;
+            }
+            TransactionKind::Conversion => {
+                tallies.entry((player, split)).or_default().conversions += 1;
+            }
+            _ => {}
+        }
+    }
+
+    let mut overall = Tally::default();
+    let mut per_split: BTreeMap<Split, Tally> = BTreeMap::new();
+    let mut per_player: BTreeMap<&PlayerId, Tally> = BTreeMap::new();
+    for (&(player, split), tally) in &tallies {
+        for total in [
+            &mut overall,
+            per_split.entry(split).or_default(),
+            per_player.entry(player).or_default(),
+        ] {
+            total.attempts += tally.attempts;
+            total.conversions += tally.conversions;
+        }
+    }
+
+    // With little history, assume a coin flip
+    let overall = ConversionRate::new(overall, 0.5);
+    let split_rates: BTreeMap<Split, ConversionRate> = per_split
+        .into_iter()
+        .map(|(split, tally)| (split, ConversionRate::new(tally, overall.smoothed_rate)))
+        .collect();
+
+    let mut by_player: Vec<PlayerRecord> = per_player
+        .into_iter()
+        .map(|(player, tally)| {
+            let mut splits: Vec<SplitRecord> = tallies
+                .iter()
+                .filter(|((id, _), _)| *id == player)
+                .map(|(&(_, split), &tally)| SplitRecord {
+                    split,
+                    stats: ConversionRate::new(tally, split_rates[&split].smoothed_rate),
+                })
+                .collect();
+            splits.sort_by_key(|record| record.split);
+            PlayerRecord {
+                player: player.clone(),
+                name: roster.name_of(&AccountId::Player(player.clone())),
+                stats: ConversionRate::new(tally, overall.smoothed_rate),
+                splits,
+            }
+        })
+        .collect();
+    by_player.sort_by(|a, b| a.name.cmp(&b.name));
+
+    SplitStats {
+        overall,
+        by_split: split_rates
+            .into_iter()
+            .map(|(split, stats)| SplitRecord { split, stats })
+            .collect(),
+        by_player,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{Money, Player, TransactionId, Void};
+    use chrono::Utc;
+
+    fn player(id: &str) -> PlayerId {
+        PlayerId(id.to_string())
+    }
+
+    fn roster() -> Roster {
+        let player = |id: &str, name: &str| Player {
+            id: player(id),
+            name: name.to_string(),
+            retired: false,
+            former_names: Vec::new(),
+        };
+        Roster {
+            players: vec![player("p1", "Zed"), player("p2", "Ann")],
+            ..Roster::default()
+        }
+    }
+
+    fn transaction(kind: TransactionKind, id: &str, split: &str) -> Transaction {
+        let account = AccountId::Player(player(id));
+        let (creditor, debtor) = match kind {
+            TransactionKind::Split => (AccountId::Pot, account),
+            _ => (account, AccountId::Pot),
+        };
+        Transaction {
+            id: TransactionId(1),
+            kind,
+            creditor,
+            debtor,
+            amount: Money::from_dollars(1),
+            split: Some(split.parse().unwrap()),
+            time: Utc::now(),
+            pot_amount: Money::ZERO,
+            stake: None,
+            award: None,
+            voided: None,
+        }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn no_history_reads_as_a_coin_flip() {
+        let stats = split_stats(&[], &roster());
+        assert_eq!(stats.overall.attempts, 0);
+        assert_eq!(stats.overall.rate, None);
+        assert_close(stats.overall.smoothed_rate, 0.5);
+        assert!(stats.by_split.is_empty());
+        assert!(stats.by_player.is_empty());
+    }
+
+    #[test]
+    fn one_make_is_shrunk_toward_the_broader_rate() {
+        let transactions = [
+            transaction(TransactionKind::Split, "p1", "7-10"),
+            transaction(TransactionKind::Conversion, "p1", "7-10"),
+        ];
+        let stats = split_stats(&transactions, &roster());
+
+        // (1 + 5 * 0.5) / (1 + 5)
+        let overall = 3.5 / 6.0;
+        assert_eq!(stats.overall.rate, Some(1.0));
+        assert_close(stats.overall.smoothed_rate, overall);
+        // The split is smoothed toward the overall rate, not toward a coin flip
+        let split = (1.0 + PRIOR_WEIGHT * overall) / (1.0 + PRIOR_WEIGHT);
+        assert_close(stats.by_split[0].stats.smoothed_rate, split);
+        let player_split = &stats.by_player[0].splits[0];
+        assert_close(
+            player_split.stats.smoothed_rate,
+            (1.0 + PRIOR_WEIGHT * split) / (1.0 + PRIOR_WEIGHT),
+        );
+    }
+
+    #[test]
+    fn counts_only_splits_and_conversions_that_stand() {
+        let mut undone = transaction(TransactionKind::Split, "p1", "4-5");
+        undone.voided = Some(Void::now("Lane 3".to_string(), None));
+        let mut settlement = transaction(TransactionKind::Settlement, "p1", "4-5");
+        settlement.split = None;
+        let transactions = [
+            transaction(TransactionKind::Split, "p1", "4-5"),
+            transaction(TransactionKind::Split, "p2", "4-5"),
+            transaction(TransactionKind::Conversion, "p2", "4-5"),
+            transaction(TransactionKind::ManualAdjustment, "p2", "4-5"),
+            undone,
+            settlement,
+        ];
+        let stats = split_stats(&transactions, &roster());
+
+        assert_eq!(stats.overall.attempts, 2);
+        assert_eq!(stats.overall.conversions, 1);
+        // Sorted by current name
+        let names: Vec<_> = stats.by_player.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["Ann", "Zed"]);
+        assert_eq!(stats.by_player[0].stats.rate, Some(1.0));
+        assert_eq!(stats.by_player[1].stats.rate, Some(0.0));
+    }
+}
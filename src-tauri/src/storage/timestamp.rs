@@ -0,0 +1,135 @@
+//! Parsing for transaction timestamps, including the locale-formatted strings older
+//! versions of the app wrote to DynamoDB and Sheets.
+
+use super::{StorageError, StorageResult};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Deserializer};
+
+/// The format every DAO used for `time` before timestamps were structured, e.g.
+/// `"1/15/2025,  2:30:00 PM UTC"`.
+const LEGACY_TIME_FORMAT: &str = "%m/%d/%Y, %l:%M:%S %p UTC";
+/// The matching legacy `date` column, e.g. `"1/15/2025"`.
+const LEGACY_DATE_FORMAT: &str = "%m/%d/%Y";
+
+/// Reads an RFC 3339 timestamp, epoch milliseconds, or one of the legacy formats.
+pub fn parse(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Some(time.with_timezone(&Utc));
+    }
+    if let Ok(millis) = value.parse::<i64>() {
+        return DateTime::from_timestamp_millis(millis);
+    }
+    if let Ok(time) = NaiveDateTime::parse_from_str(value, LEGACY_TIME_FORMAT) {
+        return Some(time.and_utc());
+    }
+    NaiveDate::parse_from_str(value, LEGACY_DATE_FORMAT)
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|time| time.and_utc())
+}
+
+/// Serde `deserialize_with` counterpart of [`parse`] that also accepts bare epoch millis.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DateTime<Utc>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+        Text(String),
+        Millis(i64),
+    }
+    match Repr::deserialize(deserializer)? {
+        Repr::Text(text) => parse(&text)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid timestamp: {}", text))),
+        Repr::Millis(millis) => DateTime::from_timestamp_millis(millis)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid timestamp: {}", millis))),
+    }
+}
+
+/// Checks `timezone` is an IANA zone name (`"America/Chicago"`, `"UTC"`). The frontend
+/// formats every timestamp in it, so an unknown name would break the whole history.
+pub fn validate_timezone(timezone: &str) -> StorageResult<()> {
+    match timezone.parse::<Tz>() {
+        Ok(_) => Ok(()),
+        Err(_) => Err(StorageError::InvalidConfig(format!(
+            "Unknown time zone: {}",
+            timezone
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn parses_legacy_locale_strings() {
+        // The hour may be space-padded, unpadded or zero-padded
+        for text in [
+            "1/15/2025,  2:30:00 PM UTC",
+            "1/15/2025, 2:30:00 PM UTC",
+            "01/15/2025, 02:30:00 PM UTC",
+        ] {
+            assert_eq!(parse(text), Some(utc(2025, 1, 15, 14, 30)), "{}", text);
+        }
+        assert_eq!(
+            parse("12/31/2024, 12:05:00 AM UTC"),
+            Some(utc(2024, 12, 31, 0, 5))
+        );
+        assert_eq!(parse("1/15/2025"), Some(utc(2025, 1, 15, 0, 0)));
+    }
+
+    #[test]
+    fn parses_rfc_3339_and_epoch_millis() {
+        let expected = utc(2025, 1, 15, 14, 30);
+        assert_eq!(parse("2025-01-15T14:30:00Z"), Some(expected));
+        assert_eq!(parse("2025-01-15T08:30:00-06:00"), Some(expected));
+        assert_eq!(parse("1736951400000"), Some(expected));
+    }
+
+    #[test]
+    fn rejects_what_isnt_a_time() {
+        for text in ["", "yesterday", "13/45/2025", "2025-01-15 14:30"] {
+            assert_eq!(parse(text), None, "{}", text);
+        }
+    }
+
+    #[test]
+    fn deserializes_text_and_bare_millis() {
+        #[derive(Deserialize)]
+        struct Row {
+            #[serde(deserialize_with = "deserialize")]
+            time: DateTime<Utc>,
+        }
+        let expected = utc(2025, 1, 15, 14, 30);
+        for json in [
+            r#"{"time": "1/15/2025,  2:30:00 PM UTC"}"#,
+            r#"{"time": 1736951400000}"#,
+        ] {
+            let row: Row = serde_json::from_str(json).unwrap();
+            assert_eq!(row.time, expected, "{}", json);
+        }
+        assert!(serde_json::from_str::<Row>(r#"{"time": "soon"}"#).is_err());
+    }
+
+    #[test]
+    fn validate_timezone_accepts_only_known_zones() {
+        for timezone in [
+            "UTC",
+            "America/Chicago",
+            "America/Argentina/Buenos_Aires",
+            "Etc/GMT+6",
+        ] {
+            assert!(validate_timezone(timezone).is_ok(), "{}", timezone);
+        }
+        for timezone in ["", "Foo/Bar", "America/", "Central Time", "America/Chicag"] {
+            assert!(validate_timezone(timezone).is_err(), "{}", timezone);
+        }
+    }
+}
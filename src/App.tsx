@@ -1,21 +1,49 @@
 import { useState, useEffect } from "react";
 import { useTauri } from "./hooks/useTauri";
-import { Balance, Transaction } from "./types";
+import {
+  Balance,
+  GameSummary,
+  NewGame,
+  Player,
+  Transaction,
+  describeError,
+  splitPins,
+  transactionPlayer,
+} from "./types";
 import { TransactionList } from "./components/TransactionList";
 import { SplitForm } from "./components/SplitForm";
 import { Settings } from "./components/Settings";
+import { BackendSettings } from "./components/BackendSettings";
+import { SplitStatsPanel } from "./components/SplitStatsPanel";
+import { SettleUpPanel } from "./components/SettleUpPanel";
+import { ActivityLogPanel } from "./components/ActivityLogPanel";
 import { BalanceTicker } from "./components/BalanceTicker";
+import { GamePicker } from "./components/GamePicker";
+import { NewGameForm } from "./components/NewGameForm";
+import { UndoForm } from "./components/UndoForm";
+import { CorrectionForm } from "./components/CorrectionForm";
 import { Modal } from "./components/Modal";
 
+const PAGE_SIZE = 5;
+
 function App() {
+  const [games, setGames] = useState<GameSummary[]>([]);
+  const [currentGameId, setCurrentGameId] = useState<string | null>(null);
   const [balances, setBalances] = useState<Balance[]>([]);
   const [transactions, setTransactions] = useState<Transaction[]>([]);
-  const [names, setNames] = useState<string[]>([]);
+  const [nextBefore, setNextBefore] = useState<number | null>(null);
+  const [loadingMore, setLoadingMore] = useState(false);
+  const [players, setPlayers] = useState<Player[]>([]);
   const [validSplits, setValidSplits] = useState<string[]>([]);
+  const [timeZone, setTimeZone] = useState("UTC");
   const [loading, setLoading] = useState(true);
   const [refreshing, setRefreshing] = useState(false);
   const [error, setError] = useState<string | null>(null);
+  const [actionError, setActionError] = useState<string | null>(null);
   const [isModalOpen, setIsModalOpen] = useState(false);
+  const [isNewGameOpen, setIsNewGameOpen] = useState(false);
+  const [isUndoOpen, setIsUndoOpen] = useState(false);
+  const [correcting, setCorrecting] = useState<Transaction | null>(null);
 
   const tauri = useTauri();
 
@@ -27,21 +55,29 @@ function App() {
         setLoading(true);
       }
 
-      const [balancesData, transactionsData, namesData, splitsData] =
+      // Listed first so the picker is available even if the current game is gone
+      setGames(await tauri.listGames());
+      const currentGame = await tauri.getCurrentGame();
+      setCurrentGameId(currentGame.id);
+
+      const [balancesData, transactionsPage, playersData, splitsData, zone] =
         await Promise.all([
           tauri.getBalances(),
-          tauri.getTransactions(10),
-          tauri.getNames(),
+          tauri.getTransactionsPage(null, PAGE_SIZE),
+          tauri.getPlayers(),
           tauri.getValidSplits(),
+          tauri.getDisplayTimezone(),
         ]);
 
       setBalances(balancesData);
-      setTransactions(transactionsData);
-      setNames(namesData);
+      setTransactions(transactionsPage.transactions);
+      setNextBefore(transactionsPage.next_before);
+      setPlayers(playersData);
       setValidSplits(splitsData);
+      setTimeZone(zone);
       setError(null);
     } catch (err) {
-      setError("Failed to load data. Please check your connection.");
+      setError(`Failed to load data: ${describeError(err)}`);
       console.error("Error loading data:", err);
     } finally {
       setLoading(false);
@@ -53,8 +89,46 @@ function App() {
     loadData();
   }, []);
 
-  const handleCreateSplit = async (name: string, split: string) => {
-    await tauri.createSplit(name, split);
+  const handleLoadMore = async () => {
+    if (nextBefore === null) return;
+    setLoadingMore(true);
+    try {
+      const page = await tauri.getTransactionsPage(nextBefore, PAGE_SIZE);
+      setTransactions((current) => [...current, ...page.transactions]);
+      setNextBefore(page.next_before);
+    } catch (err) {
+      setActionError(`Couldn't load older transactions: ${describeError(err)}`);
+    } finally {
+      setLoadingMore(false);
+    }
+  };
+
+  const handleSelectGame = async (gameId: string) => {
+    try {
+      await tauri.selectGame(gameId);
+      setActionError(null);
+    } catch (err) {
+      setActionError(`Couldn't switch games: ${describeError(err)}`);
+    }
+    await loadData(true);
+  };
+
+  // Errors surface in the form, which stays open so nothing typed is lost
+  const handleCreateGame = async (game: NewGame) => {
+    const created = await tauri.createGame(game);
+    await tauri.selectGame(created.id);
+    setIsNewGameOpen(false);
+    await loadData(true);
+  };
+
+  const handleCreateSplit = async (player: string, pins: number[]) => {
+    try {
+      await tauri.createSplit(player, pins);
+    } catch (err) {
+      setActionError(`Split not recorded: ${describeError(err)}`);
+      throw err;
+    }
+    setActionError(null);
     await loadData(true);
     setIsModalOpen(false);
   };
@@ -64,12 +138,39 @@ function App() {
   };
 
   const handleConvertFromTransaction = async (transaction: Transaction) => {
-    await tauri.convertSplit(transaction.debtor, transaction.split);
+    try {
+      await tauri.convertSplit(
+        transactionPlayer(transaction),
+        splitPins(transaction.split ?? "")
+      );
+      setActionError(null);
+    } catch (err) {
+      setActionError(`Conversion not recorded: ${describeError(err)}`);
+    }
     await loadData(true);
   };
 
-  const handleRemoveLastTransaction = async () => {
-    await tauri.removeLastTransaction();
+  // Errors surface in the form, which stays open so the reason isn't lost
+  const handleRemoveLastTransaction = async (reason: string) => {
+    await tauri.removeLastTransaction(reason || undefined);
+    setIsUndoOpen(false);
+    setActionError(null);
+    await loadData(true);
+  };
+
+  const handleCorrected = async () => {
+    setCorrecting(null);
+    setActionError(null);
+    await loadData(true);
+  };
+
+  const handleRedoLastUndo = async () => {
+    try {
+      await tauri.redoLastUndo();
+      setActionError(null);
+    } catch (err) {
+      setActionError(`Redo failed: ${describeError(err)}`);
+    }
     await loadData(true);
   };
 
@@ -105,6 +206,13 @@ function App() {
           >
             {error}
           </p>
+          <div className="mb-4">
+            <GamePicker
+              games={games}
+              currentGameId={currentGameId}
+              onSelectGame={handleSelectGame}
+            />
+          </div>
           <button
             onClick={() => loadData()}
             className="bg-primary-hover text-primary px-4 py-2 rounded-md"
@@ -112,6 +220,16 @@ function App() {
           >
             Try Again
           </button>
+          {/* A backend that failed to start can only be fixed from here */}
+          <div className="mt-6 text-left">
+            <BackendSettings
+              getBackendConfig={tauri.getBackendConfig}
+              onBackendConfigChange={async (config) => {
+                await tauri.setBackendConfig(config);
+                loadData();
+              }}
+            />
+          </div>
         </div>
       </div>
     );
@@ -125,6 +243,36 @@ function App() {
             <h1 className="text-4xl font-bold text-neutral mb-2">
               Split Happens
             </h1>
+            <div className="flex items-center justify-center gap-2">
+              <GamePicker
+                games={games}
+                currentGameId={currentGameId}
+                onSelectGame={handleSelectGame}
+              />
+              <button
+                onClick={() => setIsNewGameOpen(true)}
+                className="bg-secondary-hover text-secondary rounded-md w-8 h-8 flex items-center justify-center"
+                title="New Game"
+              >
+                +
+              </button>
+            </div>
+          </div>
+
+          <div className="absolute top-0 left-0 flex gap-2">
+            <SplitStatsPanel getSplitStats={tauri.getSplitStats} />
+            <SettleUpPanel
+              players={players}
+              planSettlement={tauri.planSettlement}
+              onRecordSettlement={async (plan) => {
+                await tauri.recordSettlement(plan.payments);
+                loadData(true);
+              }}
+            />
+            <ActivityLogPanel
+              timeZone={timeZone}
+              getActivityLog={tauri.getActivityLog}
+            />
           </div>
 
           <div className="absolute top-0 right-0">
@@ -138,6 +286,41 @@ function App() {
                 loadData(true);
               }}
               getCurrentSheetId={tauri.getSheetId}
+              timeZone={timeZone}
+              onTimeZoneChange={async (zone) => {
+                await tauri.setDisplayTimezone(zone);
+                setTimeZone(zone);
+              }}
+              getDeviceName={tauri.getDeviceName}
+              onDeviceNameChange={tauri.setDeviceName}
+              getBackendConfig={tauri.getBackendConfig}
+              onBackendConfigChange={async (config) => {
+                await tauri.setBackendConfig(config);
+                loadData(true);
+              }}
+              getAwardTable={tauri.getAwardTable}
+              getSuggestedAwardTable={tauri.getSuggestedAwardTable}
+              onAwardTableChange={async (table) => {
+                await tauri.setAwardTable(table);
+                loadData(true);
+              }}
+              getStakePolicy={tauri.getStakePolicy}
+              onStakePolicyChange={tauri.setStakePolicy}
+              getPotPolicy={tauri.getPotPolicy}
+              onPotPolicyChange={tauri.setPotPolicy}
+              getPlayers={tauri.getPlayers}
+              onAddPlayer={async (name) => {
+                await tauri.addPlayer(name);
+                loadData(true);
+              }}
+              onRenamePlayer={async (player, newName) => {
+                await tauri.renamePlayer(player, newName);
+                loadData(true);
+              }}
+              onRetirePlayer={async (player) => {
+                await tauri.retirePlayer(player);
+                loadData(true);
+              }}
             />
           </div>
         </header>
@@ -154,13 +337,36 @@ function App() {
             </button>
           </div>
 
+          {actionError && (
+            <div
+              className="bg-negative text-negative rounded-lg p-3 mb-4 flex justify-between items-center"
+              role="alert"
+            >
+              <span>{actionError}</span>
+              <button
+                onClick={() => setActionError(null)}
+                className="text-2xl leading-none ml-4"
+                title="Dismiss"
+              >
+                ×
+              </button>
+            </div>
+          )}
+
           {/* Transactions List */}
           <TransactionList
             transactions={transactions}
-            onUndo={handleRemoveLastTransaction}
+            players={players}
+            timeZone={timeZone}
+            onUndo={() => setIsUndoOpen(true)}
+            onRedo={handleRedoLastUndo}
             onConvert={handleConvertFromTransaction}
+            onCorrect={setCorrecting}
             onRefresh={() => loadData(true)}
             refreshing={refreshing}
+            hasMore={nextBefore !== null}
+            onLoadMore={handleLoadMore}
+            loadingMore={loadingMore}
           />
         </div>
 
@@ -173,9 +379,48 @@ function App() {
           title="Add Split"
         >
           <SplitForm
-            names={names}
+            players={players.filter((player) => !player.retired)}
             validSplits={validSplits}
             onCreateSplit={handleCreateSplit}
+            classifySplit={tauri.classifySplit}
+          />
+        </Modal>
+
+        <Modal
+          isOpen={isUndoOpen}
+          onClose={() => setIsUndoOpen(false)}
+          title="Undo"
+        >
+          <UndoForm onUndo={handleRemoveLastTransaction} />
+        </Modal>
+
+        <Modal
+          isOpen={correcting !== null}
+          onClose={() => setCorrecting(null)}
+          title="Correct Transaction"
+        >
+          {correcting && (
+            <CorrectionForm
+              key={correcting.id}
+              transaction={correcting}
+              players={players}
+              validSplits={validSplits}
+              voidTransaction={tauri.voidTransaction}
+              amendTransaction={tauri.amendTransaction}
+              onCorrected={handleCorrected}
+            />
+          )}
+        </Modal>
+
+        <Modal
+          isOpen={isNewGameOpen}
+          onClose={() => setIsNewGameOpen(false)}
+          title="New Game"
+        >
+          <NewGameForm
+            getAwardTable={tauri.getAwardTable}
+            getSuggestedAwardTable={tauri.getSuggestedAwardTable}
+            onCreateGame={handleCreateGame}
           />
         </Modal>
       </div>
@@ -0,0 +1,138 @@
+import { useState, useEffect } from "react";
+import { Modal } from "./Modal";
+import {
+  ACTIVITY_ACTION_LABELS,
+  Activity,
+  ActivityAction,
+  ActivityFilter,
+  describeError,
+  formatTime,
+} from "../types";
+
+interface ActivityLogPanelProps {
+  timeZone: string;
+  getActivityLog: (filter: ActivityFilter) => Promise<Activity[]>;
+}
+
+const LIMIT = 50;
+
+const inputClass =
+  "w-full px-3 py-2 border border-neutral rounded-md bg-neutral text-neutral focus:outline-none focus-ring focus:ring-2";
+
+export const ActivityLogPanel = ({
+  timeZone,
+  getActivityLog,
+}: ActivityLogPanelProps) => {
+  const [isOpen, setIsOpen] = useState(false);
+  const [action, setAction] = useState<ActivityAction | "">("");
+  const [actor, setActor] = useState("");
+  const [entries, setEntries] = useState<Activity[] | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (!isOpen) {
+      return;
+    }
+    let cancelled = false;
+    const filter: ActivityFilter = {
+      actions: action ? [action] : [],
+      actor: actor.trim() || undefined,
+      limit: LIMIT,
+    };
+    getActivityLog(filter)
+      .then((result) => {
+        if (!cancelled) {
+          setEntries(result);
+          setError(null);
+        }
+      })
+      .catch((err) => {
+        if (!cancelled) {
+          setError(describeError(err));
+        }
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [isOpen, action, actor]);
+
+  return (
+    <>
+      <button
+        onClick={() => setIsOpen(true)}
+        className="bg-secondary-hover text-secondary p-2 rounded-md transition-colors w-10 h-10 flex items-center justify-center"
+        title="Activity"
+      >
+        📜
+      </button>
+
+      <Modal isOpen={isOpen} onClose={() => setIsOpen(false)} title="Activity">
+        <div className="space-y-4 text-neutral">
+          <div className="flex gap-2">
+            <select
+              value={action}
+              onChange={(e) => setAction(e.target.value as ActivityAction | "")}
+              className={inputClass}
+            >
+              <option value="">Everything</option>
+              {Object.entries(ACTIVITY_ACTION_LABELS).map(([value, label]) => (
+                <option key={value} value={value}>
+                  {label}
+                </option>
+              ))}
+            </select>
+            <input
+              type="text"
+              value={actor}
+              onChange={(e) => setActor(e.target.value)}
+              placeholder="Any device"
+              className={inputClass}
+            />
+          </div>
+
+          {error && <p className="text-sm text-negative">{error}</p>}
+          {entries && entries.length === 0 && (
+            <p className="text-sm opacity-80">Nothing logged yet.</p>
+          )}
+          {entries && entries.length > 0 && (
+            <ul className="space-y-3">
+              {entries.map((entry) => (
+                <li key={entry.id} className="text-sm">
+                  <div className="flex justify-between">
+                    <span className="font-medium">
+                      {ACTIVITY_ACTION_LABELS[entry.action]}
+                      {entry.detail ? `: ${entry.detail}` : ""}
+                    </span>
+                    <span className="opacity-80">
+                      {formatTime(entry.at, timeZone)}
+                    </span>
+                  </div>
+                  <div className="opacity-80">by {entry.actor}</div>
+                  {entry.balances && entry.balances.length > 0 && (
+                    <table className="w-full opacity-80">
+                      <tbody>
+                        {entry.balances.map((change) => (
+                          <tr key={change.account}>
+                            <td>{change.name}</td>
+                            <td className="text-right">${change.before}</td>
+                            <td className="text-center">→</td>
+                            <td className="text-right">${change.after}</td>
+                          </tr>
+                        ))}
+                      </tbody>
+                    </table>
+                  )}
+                </li>
+              ))}
+            </ul>
+          )}
+          {entries && entries.length === LIMIT && (
+            <p className="text-sm opacity-80">
+              Showing the {LIMIT} most recent; filter to see older entries.
+            </p>
+          )}
+        </div>
+      </Modal>
+    </>
+  );
+};
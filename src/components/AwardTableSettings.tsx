@@ -0,0 +1,188 @@
+import { useState, useEffect } from "react";
+import { AwardTable, Bounds, SplitRule, describeError } from "../types";
+
+interface AwardTableSettingsProps {
+  getAwardTable: () => Promise<AwardTable>;
+  getSuggestedAwardTable: () => Promise<AwardTable>;
+  onAwardTableChange: (table: AwardTable) => Promise<void>;
+}
+
+// Splits are edited as whitespace-separated lists like "2-3 4-5 7-10"; rules are
+// shown but kept as loaded.
+interface TierDraft {
+  percent: string;
+  splits: string;
+  rule?: SplitRule;
+}
+
+const describeBounds = (bounds: Bounds): string => {
+  if (bounds.min !== undefined && bounds.min === bounds.max) {
+    return `${bounds.min}`;
+  }
+  if (bounds.max === undefined) return `at least ${bounds.min ?? 0}`;
+  if (bounds.min === undefined) return `at most ${bounds.max}`;
+  return `${bounds.min}–${bounds.max}`;
+};
+
+const describeRule = (rule: SplitRule): string => {
+  if (rule === "cluster") return "a cluster shape";
+  if ("contains_all" in rule) {
+    return `pins ${rule.contains_all.join(", ")} standing`;
+  }
+  if ("contains_any" in rule) {
+    return `any of pins ${rule.contains_any.join(", ")} standing`;
+  }
+  if ("within" in rule) return `only pins ${rule.within.join(", ")}`;
+  if ("pin_count" in rule) return `${describeBounds(rule.pin_count)} pins`;
+  if ("lateral_gap" in rule) {
+    return `gap of ${describeBounds(rule.lateral_gap)} pins`;
+  }
+  if ("row_span" in rule) {
+    return `${describeBounds(rule.row_span)} rows between pins`;
+  }
+  if ("exact" in rule) return rule.exact.join(" ");
+  if ("all" in rule) return rule.all.map(describeRule).join(" and ");
+  if ("any" in rule) return rule.any.map(describeRule).join(" or ");
+  return `no ${describeRule(rule.not).replace(/^an? /, "")}`;
+};
+
+const inputClass =
+  "px-3 py-2 border border-neutral rounded-md bg-neutral text-neutral focus:outline-none focus-ring focus:ring-2";
+
+export const AwardTableSettings = ({
+  getAwardTable,
+  getSuggestedAwardTable,
+  onAwardTableChange,
+}: AwardTableSettingsProps) => {
+  const [name, setName] = useState("");
+  const [tiers, setTiers] = useState<TierDraft[] | null>(null);
+  const [isSaving, setIsSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const loadDraft = (table: AwardTable) => {
+    setName(table.name);
+    setTiers(
+      table.tiers.map((tier) => ({
+        percent: tier.percent.toString(),
+        splits: (tier.splits ?? []).join(" "),
+        rule: tier.rule,
+      }))
+    );
+  };
+
+  // Load once per open; re-fetching on parent re-renders would discard edits.
+  useEffect(() => {
+    getAwardTable().then(loadDraft);
+  }, []);
+
+  if (!tiers) return null;
+
+  const updateTier = (index: number, fields: Partial<TierDraft>) => {
+    setTiers(
+      tiers.map((tier, i) => (i === index ? { ...tier, ...fields } : tier))
+    );
+  };
+
+  const handleSubmit = async (e: React.FormEvent) => {
+    e.preventDefault();
+    setIsSaving(true);
+    try {
+      await onAwardTableChange({
+        name: name.trim(),
+        tiers: tiers.map((tier) => ({
+          percent: Number(tier.percent),
+          splits: tier.splits.split(/\s+/).filter((split) => split !== ""),
+          rule: tier.rule,
+        })),
+      });
+      setError(null);
+    } catch (err) {
+      setError(describeError(err));
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  return (
+    <form onSubmit={handleSubmit} className="space-y-3 mb-6">
+      <label
+        htmlFor="award-table-name"
+        className="block text-sm font-medium text-neutral mb-2"
+      >
+        Split Awards
+      </label>
+      <input
+        id="award-table-name"
+        type="text"
+        value={name}
+        onChange={(e) => setName(e.target.value)}
+        placeholder="Table name, e.g. 2026 Season"
+        className={`w-full ${inputClass}`}
+      />
+
+      {tiers.map((tier, index) => (
+        <div key={index} className="flex space-x-2 items-start">
+          <input
+            type="number"
+            min="0"
+            max="100"
+            step="0.01"
+            value={tier.percent}
+            onChange={(e) => updateTier(index, { percent: e.target.value })}
+            aria-label="Percent of pot"
+            className={`w-20 ${inputClass}`}
+          />
+          <div className="flex-1 space-y-1">
+            <textarea
+              value={tier.splits}
+              onChange={(e) => updateTier(index, { splits: e.target.value })}
+              aria-label="Splits in this tier"
+              rows={2}
+              className={`w-full ${inputClass}`}
+            />
+            {tier.rule && (
+              <p className="text-xs text-neutral opacity-80">
+                Plus any leave with {describeRule(tier.rule)}
+              </p>
+            )}
+          </div>
+          <button
+            type="button"
+            onClick={() => setTiers(tiers.filter((_, i) => i !== index))}
+            className="bg-secondary-hover text-secondary px-3 py-2 rounded-md"
+            title="Remove tier"
+          >
+            ✕
+          </button>
+        </div>
+      ))}
+
+      {error && <p className="text-sm text-negative">{error}</p>}
+
+      <div className="flex space-x-2">
+        <button
+          type="button"
+          onClick={() => getSuggestedAwardTable().then(loadDraft)}
+          className="flex-1 bg-secondary-hover text-secondary py-2 px-4 rounded-md font-medium"
+          title="Replace the form with tiers suggested by split difficulty"
+        >
+          Suggest
+        </button>
+        <button
+          type="button"
+          onClick={() => setTiers([...tiers, { percent: "", splits: "" }])}
+          className="flex-1 bg-secondary-hover text-secondary py-2 px-4 rounded-md font-medium"
+        >
+          Add Tier
+        </button>
+        <button
+          type="submit"
+          disabled={isSaving}
+          className="flex-1 bg-primary-hover text-primary py-2 px-4 rounded-md font-medium transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+        >
+          {isSaving ? "Saving..." : "Save Awards"}
+        </button>
+      </div>
+    </form>
+  );
+};
@@ -0,0 +1,128 @@
+import { useState, useEffect } from "react";
+import { BackendConfig, describeError } from "../types";
+
+interface BackendSettingsProps {
+  getBackendConfig: () => Promise<BackendConfig>;
+  onBackendConfigChange: (config: BackendConfig) => Promise<void>;
+}
+
+const DEFAULT_CONFIGS: Record<BackendConfig["kind"], BackendConfig> = {
+  memory: { kind: "memory", file_path: null },
+  dynamo_db: {
+    kind: "dynamo_db",
+    region: "us-east-1",
+    games_table: "split-happens-games",
+    transactions_table: "split-happens-transactions",
+    activity_table: "split-happens-activity",
+    endpoint: null,
+  },
+  sheets: { kind: "sheets", sheet_id: "" },
+};
+
+const inputClass =
+  "w-full px-3 py-2 border border-neutral rounded-md bg-neutral text-neutral focus:outline-none focus-ring focus:ring-2";
+
+export const BackendSettings = ({
+  getBackendConfig,
+  onBackendConfigChange,
+}: BackendSettingsProps) => {
+  const [config, setConfig] = useState<BackendConfig | null>(null);
+  const [isSaving, setIsSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  // Load once per open; re-fetching on parent re-renders would discard edits.
+  useEffect(() => {
+    getBackendConfig()
+      .then(setConfig)
+      .catch((err) => {
+        // An unreadable saved config can still be replaced, starting from the demo
+        setError(describeError(err));
+        setConfig(DEFAULT_CONFIGS.memory);
+      });
+  }, []);
+
+  if (!config) return null;
+
+  const update = (fields: Record<string, string>) => {
+    const cleaned = Object.fromEntries(
+      Object.entries(fields).map(([k, v]) => [k, v === "" ? null : v])
+    );
+    setConfig({ ...config, ...cleaned } as BackendConfig);
+  };
+
+  const handleSubmit = async (e: React.FormEvent) => {
+    e.preventDefault();
+    setIsSaving(true);
+    try {
+      await onBackendConfigChange(config);
+      setError(null);
+    } catch (err) {
+      setError(describeError(err));
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  const renderField = (field: string, label: string, placeholder = "") => (
+    <div key={field}>
+      <label
+        htmlFor={`backend-${field}`}
+        className="block text-sm font-medium text-neutral mb-1"
+      >
+        {label}
+      </label>
+      <input
+        id={`backend-${field}`}
+        type="text"
+        value={(config as Record<string, unknown>)[field]?.toString() ?? ""}
+        placeholder={placeholder}
+        onChange={(e) => update({ [field]: e.target.value })}
+        className={inputClass}
+      />
+    </div>
+  );
+
+  return (
+    <form onSubmit={handleSubmit} className="space-y-3 mb-6">
+      <label
+        htmlFor="backend-kind"
+        className="block text-sm font-medium text-neutral mb-2"
+      >
+        Storage Backend
+      </label>
+      <select
+        id="backend-kind"
+        value={config.kind}
+        onChange={(e) =>
+          setConfig(DEFAULT_CONFIGS[e.target.value as BackendConfig["kind"]])
+        }
+        className={inputClass}
+      >
+        <option value="dynamo_db">DynamoDB</option>
+        <option value="memory">Demo (in memory)</option>
+        <option value="sheets">Google Sheets</option>
+      </select>
+
+      {config.kind === "memory" &&
+        renderField("file_path", "Save File", "Leave empty to not persist")}
+      {config.kind === "dynamo_db" && [
+        renderField("region", "Region"),
+        renderField("games_table", "Games Table"),
+        renderField("transactions_table", "Transactions Table"),
+        renderField("activity_table", "Activity Table"),
+        renderField("endpoint", "Endpoint", "Default AWS endpoint"),
+      ]}
+      {config.kind === "sheets" && renderField("sheet_id", "Sheet ID")}
+
+      {error && <p className="text-sm text-negative">{error}</p>}
+
+      <button
+        type="submit"
+        disabled={isSaving}
+        className="w-full bg-primary-hover text-primary py-2 px-4 rounded-md font-medium transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+      >
+        {isSaving ? "Connecting..." : "Use Backend"}
+      </button>
+    </form>
+  );
+};
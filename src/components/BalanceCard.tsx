@@ -1,4 +1,4 @@
-import { Balance } from '../types';
+import { Balance, POT_ACCOUNT } from '../types';
 
 interface BalanceCardProps {
   balance: Balance;
@@ -7,7 +7,7 @@ interface BalanceCardProps {
 export const BalanceCard = ({ balance }: BalanceCardProps) => {
   const amount = parseFloat(balance.amount);
   const isPositive = amount >= 0;
-  const isPot = balance.name === 'Pot';
+  const isPot = balance.account === POT_ACCOUNT;
 
   return (
     <div className={`bg-neutral bg-opacity-90 rounded-lg shadow-md p-4 border-l-4 ${
@@ -1,4 +1,4 @@
-import { Balance } from "../types";
+import { Balance, POT_ACCOUNT } from "../types";
 
 interface BalanceTickerProps {
   balances: Balance[];
@@ -10,7 +10,7 @@ export const BalanceTicker = ({ balances }: BalanceTickerProps) => {
   const formatBalance = (balance: Balance) => {
     const amount = parseFloat(balance.amount);
     const isPositive = amount >= 0;
-    const isPot = balance.name === "Pot";
+    const isPot = balance.account === POT_ACCOUNT;
 
     if (isPot) {
       return `POT: $${Math.abs(amount).toFixed(2)}`;
@@ -22,7 +22,7 @@ export const BalanceTicker = ({ balances }: BalanceTickerProps) => {
 
   const getBalanceClass = (balance: Balance) => {
     const amount = parseFloat(balance.amount);
-    const isPot = balance.name === "Pot";
+    const isPot = balance.account === POT_ACCOUNT;
 
     if (isPot) return "";
     return amount >= 0 ? "positive-balance" : "negative-balance";
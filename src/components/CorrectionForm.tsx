@@ -0,0 +1,229 @@
+import { useState, useEffect } from "react";
+import {
+  Player,
+  Replay,
+  Transaction,
+  describeError,
+  splitPins,
+  transactionPlayer,
+} from "../types";
+
+interface CorrectionFormProps {
+  transaction: Transaction;
+  players: Player[];
+  validSplits: string[];
+  voidTransaction: (
+    id: number,
+    reason: string | undefined,
+    preview: boolean
+  ) => Promise<Replay>;
+  amendTransaction: (
+    id: number,
+    player: string,
+    pins: number[],
+    preview: boolean
+  ) => Promise<Replay>;
+  onCorrected: () => Promise<void>;
+}
+
+export const CorrectionForm = ({
+  transaction,
+  players,
+  validSplits,
+  voidTransaction,
+  amendTransaction,
+  onCorrected,
+}: CorrectionFormProps) => {
+  const canAmend =
+    transaction.kind === "split" || transaction.kind === "conversion";
+  const [mode, setMode] = useState<"void" | "amend">(
+    canAmend ? "amend" : "void"
+  );
+  const [reason, setReason] = useState("");
+  const [player, setPlayer] = useState(transactionPlayer(transaction));
+  const [split, setSplit] = useState(transaction.split ?? "");
+  const [preview, setPreview] = useState<Replay | null>(null);
+  const [isBusy, setIsBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  // Any edit invalidates the preview, so only what was previewed can be committed
+  useEffect(() => {
+    setPreview(null);
+  }, [mode, reason, player, split]);
+
+  const run = (commit: boolean): Promise<Replay> =>
+    mode === "void"
+      ? voidTransaction(transaction.id, reason.trim() || undefined, !commit)
+      : amendTransaction(transaction.id, player, splitPins(split), !commit);
+
+  const handlePreview = async () => {
+    setIsBusy(true);
+    try {
+      setPreview(await run(false));
+      setError(null);
+    } catch (err) {
+      setError(describeError(err));
+    } finally {
+      setIsBusy(false);
+    }
+  };
+
+  const handleConfirm = async () => {
+    setIsBusy(true);
+    try {
+      await run(true);
+      await onCorrected();
+    } catch (err) {
+      setError(describeError(err));
+      setPreview(null);
+    } finally {
+      setIsBusy(false);
+    }
+  };
+
+  // Conversions can only be moved to splits the award table pays
+  const splitOptions =
+    transaction.kind === "conversion" ? validSplits : undefined;
+
+  return (
+    <div className="space-y-4">
+      {canAmend && (
+        <div className="flex gap-4 text-sm text-neutral">
+          <label className="flex items-center gap-1">
+            <input
+              type="radio"
+              checked={mode === "amend"}
+              onChange={() => setMode("amend")}
+            />
+            Amend
+          </label>
+          <label className="flex items-center gap-1">
+            <input
+              type="radio"
+              checked={mode === "void"}
+              onChange={() => setMode("void")}
+            />
+            Void
+          </label>
+        </div>
+      )}
+
+      {mode === "amend" ? (
+        <>
+          <div>
+            <label
+              htmlFor="correction-player"
+              className="block text-sm font-medium text-neutral mb-2"
+            >
+              Player
+            </label>
+            <select
+              id="correction-player"
+              value={player}
+              onChange={(e) => setPlayer(e.target.value)}
+              className="w-full px-3 py-2 border border-neutral rounded-md bg-neutral text-neutral focus:outline-none focus-ring focus:ring-2"
+            >
+              {players.map((option) => (
+                <option key={option.id} value={option.id}>
+                  {option.name}
+                  {option.retired ? " (retired)" : ""}
+                </option>
+              ))}
+            </select>
+          </div>
+          <div>
+            <label
+              htmlFor="correction-split"
+              className="block text-sm font-medium text-neutral mb-2"
+            >
+              Split
+            </label>
+            <input
+              id="correction-split"
+              type="text"
+              value={split}
+              onChange={(e) => setSplit(e.target.value)}
+              placeholder="e.g. 7-10"
+              list={splitOptions && "correction-splits"}
+              className="w-full px-3 py-2 border border-neutral rounded-md bg-neutral text-neutral focus:outline-none focus-ring focus:ring-2"
+            />
+            {splitOptions && (
+              <datalist id="correction-splits">
+                {splitOptions.map((option) => (
+                  <option key={option} value={option} />
+                ))}
+              </datalist>
+            )}
+          </div>
+        </>
+      ) : (
+        <div>
+          <label
+            htmlFor="correction-reason"
+            className="block text-sm font-medium text-neutral mb-2"
+          >
+            Reason (optional)
+          </label>
+          <input
+            id="correction-reason"
+            type="text"
+            value={reason}
+            onChange={(e) => setReason(e.target.value)}
+            placeholder="e.g. Logged twice"
+            className="w-full px-3 py-2 border border-neutral rounded-md bg-neutral text-neutral focus:outline-none focus-ring focus:ring-2"
+          />
+        </div>
+      )}
+
+      {preview && (
+        <div className="text-sm text-neutral">
+          <p className="mb-2">
+            {preview.changed.length === 1
+              ? "Only this transaction changes."
+              : `This transaction and ${
+                  preview.changed.length - 1
+                } later ones change.`}
+          </p>
+          {preview.balances.length === 0 ? (
+            <p>No balances change.</p>
+          ) : (
+            <table className="w-full">
+              <tbody>
+                {preview.balances.map((change) => (
+                  <tr key={change.account}>
+                    <td>{change.name}</td>
+                    <td className="text-right">${change.before}</td>
+                    <td className="text-center">→</td>
+                    <td className="text-right font-semibold">
+                      ${change.after}
+                    </td>
+                  </tr>
+                ))}
+              </tbody>
+            </table>
+          )}
+        </div>
+      )}
+
+      {error && <p className="text-sm text-negative">{error}</p>}
+
+      {preview ? (
+        <button
+          onClick={handleConfirm}
+          disabled={isBusy}
+          className="w-full bg-negative-hover text-negative py-2 px-4 rounded-md font-medium transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+        >
+          {isBusy ? "Saving..." : "Confirm"}
+        </button>
+      ) : (
+        <button
+          onClick={handlePreview}
+          disabled={isBusy || (mode === "amend" && !split.trim())}
+          className="w-full bg-primary-hover text-primary py-2 px-4 rounded-md font-medium transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+        >
+          {isBusy ? "Working it out..." : "Preview"}
+        </button>
+      )}
+    </div>
+  );
+};
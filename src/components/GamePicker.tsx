@@ -0,0 +1,41 @@
+import { GameSummary } from "../types";
+
+interface GamePickerProps {
+  games: GameSummary[];
+  currentGameId: string | null;
+  onSelectGame: (gameId: string) => Promise<void>;
+}
+
+export const GamePicker = ({
+  games,
+  currentGameId,
+  onSelectGame,
+}: GamePickerProps) => {
+  if (games.length === 0) {
+    return null;
+  }
+  // The stored game may be missing from this backend, e.g. after switching backends
+  const selected = games.some((game) => game.id === currentGameId)
+    ? currentGameId
+    : null;
+
+  return (
+    <select
+      aria-label="Game"
+      value={selected ?? ""}
+      onChange={(e) => onSelectGame(e.target.value)}
+      className="px-3 py-1 border border-neutral rounded-md bg-neutral text-neutral focus:outline-none focus-ring focus:ring-2"
+    >
+      {selected === null && (
+        <option value="" disabled>
+          Choose a game
+        </option>
+      )}
+      {games.map((game) => (
+        <option key={game.id} value={game.id}>
+          {game.name}
+        </option>
+      ))}
+    </select>
+  );
+};
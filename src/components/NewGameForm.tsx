@@ -0,0 +1,124 @@
+import { useState } from "react";
+import { AwardTable, NewGame, describeError } from "../types";
+
+interface NewGameFormProps {
+  getAwardTable: () => Promise<AwardTable>;
+  getSuggestedAwardTable: () => Promise<AwardTable>;
+  onCreateGame: (game: NewGame) => Promise<void>;
+}
+
+type AwardChoice = "standard" | "suggested" | "current";
+
+const inputClass =
+  "w-full px-3 py-2 border border-neutral rounded-md bg-neutral text-neutral focus:outline-none focus-ring focus:ring-2";
+const labelClass = "block text-sm font-medium text-neutral mb-1";
+
+export const NewGameForm = ({
+  getAwardTable,
+  getSuggestedAwardTable,
+  onCreateGame,
+}: NewGameFormProps) => {
+  const [name, setName] = useState("");
+  const [players, setPlayers] = useState("");
+  const [stake, setStake] = useState("1.00");
+  const [awards, setAwards] = useState<AwardChoice>("standard");
+  const [isSaving, setIsSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const handleSubmit = async (e: React.FormEvent) => {
+    e.preventDefault();
+    setIsSaving(true);
+    try {
+      const game: NewGame = {
+        name: name.trim(),
+        players: players
+          .split(/[,\n]/)
+          .map((player) => player.trim())
+          .filter((player) => player !== ""),
+        stake: stake.trim(),
+      };
+      if (awards === "suggested") {
+        game.award_table = await getSuggestedAwardTable();
+      } else if (awards === "current") {
+        game.award_table = await getAwardTable();
+      }
+      await onCreateGame(game);
+      setError(null);
+    } catch (err) {
+      setError(describeError(err));
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  return (
+    <form onSubmit={handleSubmit} className="space-y-3">
+      <div>
+        <label htmlFor="game-name" className={labelClass}>
+          Name
+        </label>
+        <input
+          id="game-name"
+          type="text"
+          value={name}
+          placeholder="Tuesday League"
+          onChange={(e) => setName(e.target.value)}
+          className={inputClass}
+        />
+      </div>
+
+      <div>
+        <label htmlFor="game-players" className={labelClass}>
+          Players (one per line or comma separated)
+        </label>
+        <textarea
+          id="game-players"
+          rows={4}
+          value={players}
+          onChange={(e) => setPlayers(e.target.value)}
+          className={inputClass}
+        />
+      </div>
+
+      <div>
+        <label htmlFor="game-stake" className={labelClass}>
+          Stake per Split ($)
+        </label>
+        <input
+          id="game-stake"
+          type="text"
+          inputMode="decimal"
+          value={stake}
+          onChange={(e) => setStake(e.target.value)}
+          className={inputClass}
+        />
+      </div>
+
+      <div>
+        <label htmlFor="game-awards" className={labelClass}>
+          Award Table
+        </label>
+        <select
+          id="game-awards"
+          value={awards}
+          onChange={(e) => setAwards(e.target.value as AwardChoice)}
+          className={inputClass}
+        >
+          <option value="standard">Standard</option>
+          <option value="suggested">Suggested by difficulty</option>
+          <option value="current">Copy this game's table</option>
+        </select>
+      </div>
+
+      {error && <p className="text-sm text-negative">{error}</p>}
+
+      <button
+        type="submit"
+        disabled={isSaving}
+        className="w-full bg-primary-hover text-primary py-2 px-4 rounded-md font-medium transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+      >
+        {isSaving ? "Creating..." : "Create Game"}
+      </button>
+    </form>
+  );
+};
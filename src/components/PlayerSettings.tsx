@@ -0,0 +1,155 @@
+import { useState, useEffect } from "react";
+import { Player, describeError } from "../types";
+
+interface PlayerSettingsProps {
+  getPlayers: () => Promise<Player[]>;
+  onAddPlayer: (name: string) => Promise<void>;
+  onRenamePlayer: (player: string, newName: string) => Promise<void>;
+  onRetirePlayer: (player: string) => Promise<void>;
+}
+
+const inputClass =
+  "flex-1 px-3 py-2 border border-neutral rounded-md bg-neutral text-neutral focus:outline-none focus-ring focus:ring-2";
+
+export const PlayerSettings = ({
+  getPlayers,
+  onAddPlayer,
+  onRenamePlayer,
+  onRetirePlayer,
+}: PlayerSettingsProps) => {
+  const [players, setPlayers] = useState<Player[]>([]);
+  const [newName, setNewName] = useState("");
+  const [renaming, setRenaming] = useState<string | null>(null);
+  const [renameTo, setRenameTo] = useState("");
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = async () => {
+    setPlayers(await getPlayers());
+  };
+
+  useEffect(() => {
+    refresh();
+  }, []);
+
+  const run = async (action: () => Promise<void>) => {
+    try {
+      await action();
+      setError(null);
+      await refresh();
+    } catch (err) {
+      setError(describeError(err));
+    }
+  };
+
+  const handleAdd = async (e: React.FormEvent) => {
+    e.preventDefault();
+    await run(async () => {
+      await onAddPlayer(newName.trim());
+      setNewName("");
+    });
+  };
+
+  const handleRename = async (e: React.FormEvent) => {
+    e.preventDefault();
+    if (renaming === null) return;
+    await run(async () => {
+      await onRenamePlayer(renaming, renameTo.trim());
+      setRenaming(null);
+    });
+  };
+
+  return (
+    <div className="mb-6">
+      <label className="block text-sm font-medium text-neutral mb-2">
+        Players
+      </label>
+      <ul className="space-y-1 mb-2">
+        {players.map((player) => (
+          <li key={player.id} className="text-neutral">
+            {renaming === player.id ? (
+              <form onSubmit={handleRename} className="flex space-x-2">
+                <input
+                  type="text"
+                  value={renameTo}
+                  onChange={(e) => setRenameTo(e.target.value)}
+                  className={inputClass}
+                  autoFocus
+                />
+                <button
+                  type="submit"
+                  disabled={!renameTo.trim()}
+                  className="bg-primary-hover text-primary px-3 rounded-md disabled:opacity-50"
+                >
+                  Save
+                </button>
+                <button
+                  type="button"
+                  onClick={() => setRenaming(null)}
+                  className="bg-secondary-hover text-secondary px-3 rounded-md"
+                >
+                  Cancel
+                </button>
+              </form>
+            ) : (
+              <div className="flex items-center justify-between">
+                <span className={player.retired ? "opacity-50" : ""}>
+                  {player.name}
+                  {player.retired && " (retired)"}
+                  {player.former_names.length > 0 && (
+                    <span className="text-sm opacity-70">
+                      {" "}
+                      formerly {player.former_names.join(", ")}
+                    </span>
+                  )}
+                </span>
+                <span className="flex space-x-2 text-sm">
+                  <button
+                    onClick={() => {
+                      setRenaming(player.id);
+                      setRenameTo(player.name);
+                    }}
+                    className="underline"
+                  >
+                    Rename
+                  </button>
+                  {player.retired ? (
+                    <button
+                      onClick={() => run(() => onAddPlayer(player.name))}
+                      className="underline"
+                    >
+                      Bring Back
+                    </button>
+                  ) : (
+                    <button
+                      onClick={() => run(() => onRetirePlayer(player.id))}
+                      className="underline"
+                    >
+                      Retire
+                    </button>
+                  )}
+                </span>
+              </div>
+            )}
+          </li>
+        ))}
+      </ul>
+      <form onSubmit={handleAdd} className="flex space-x-2">
+        <input
+          type="text"
+          value={newName}
+          onChange={(e) => setNewName(e.target.value)}
+          placeholder="New player"
+          className={inputClass}
+        />
+        <button
+          type="submit"
+          disabled={!newName.trim()}
+          className="bg-primary-hover text-primary px-4 rounded-md font-medium disabled:opacity-50 disabled:cursor-not-allowed"
+        >
+          Add
+        </button>
+      </form>
+      {error && <p className="text-sm text-negative mt-1">{error}</p>}
+    </div>
+  );
+};
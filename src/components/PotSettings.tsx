@@ -0,0 +1,95 @@
+import { useState, useEffect } from "react";
+import { PotPolicy, describeError } from "../types";
+
+interface PotSettingsProps {
+  getPotPolicy: () => Promise<PotPolicy>;
+  onPotPolicyChange: (policy: PotPolicy) => Promise<void>;
+}
+
+const inputClass =
+  "w-full px-3 py-2 border border-neutral rounded-md bg-neutral text-neutral focus:outline-none focus-ring focus:ring-2";
+
+// Blank fields mean no limit
+const fields: { key: keyof PotPolicy; label: string; placeholder: string }[] = [
+  { key: "min_award", label: "Minimum award", placeholder: "None" },
+  { key: "max_award", label: "Maximum award", placeholder: "None" },
+  { key: "reserve", label: "Always leave in pot", placeholder: "0.00" },
+  { key: "rounding", label: "Round awards to", placeholder: "0.01" },
+];
+
+export const PotSettings = ({
+  getPotPolicy,
+  onPotPolicyChange,
+}: PotSettingsProps) => {
+  const [values, setValues] = useState<Record<keyof PotPolicy, string> | null>(
+    null
+  );
+  const [isSaving, setIsSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  // Load once per open; re-fetching on parent re-renders would discard edits.
+  useEffect(() => {
+    getPotPolicy().then((policy) =>
+      setValues({
+        min_award: policy.min_award ?? "",
+        max_award: policy.max_award ?? "",
+        reserve: policy.reserve === "0.00" ? "" : policy.reserve,
+        rounding: policy.rounding ?? "",
+      })
+    );
+  }, []);
+
+  if (!values) return null;
+
+  const handleSubmit = async (e: React.FormEvent) => {
+    e.preventDefault();
+    setIsSaving(true);
+    try {
+      await onPotPolicyChange({
+        min_award: values.min_award.trim() || undefined,
+        max_award: values.max_award.trim() || undefined,
+        reserve: values.reserve.trim() || "0.00",
+        rounding: values.rounding.trim() || undefined,
+      });
+      setError(null);
+    } catch (err) {
+      setError(describeError(err));
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  return (
+    <form onSubmit={handleSubmit} className="space-y-3 mb-6">
+      <label className="block text-sm font-medium text-neutral mb-2">
+        Conversion Payouts
+      </label>
+      <div className="grid grid-cols-2 gap-2">
+        {fields.map(({ key, label, placeholder }) => (
+          <label key={key} className="text-sm text-neutral">
+            {label}
+            <input
+              type="number"
+              min="0"
+              step="0.01"
+              value={values[key]}
+              onChange={(e) => setValues({ ...values, [key]: e.target.value })}
+              placeholder={placeholder}
+              className={`mt-1 ${inputClass}`}
+            />
+          </label>
+        ))}
+      </div>
+
+      {error && <p className="text-sm text-negative">{error}</p>}
+
+      <button
+        type="submit"
+        disabled={isSaving}
+        className="w-full bg-primary-hover text-primary py-2 px-4 rounded-md font-medium transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+      >
+        {isSaving ? "Saving..." : "Save Payouts"}
+      </button>
+    </form>
+  );
+};
@@ -1,22 +1,106 @@
 import { useState, useEffect } from "react";
 import { ThemeSwitcher } from "./ThemeSwitcher";
 import { Modal } from "./Modal";
+import { BackendSettings } from "./BackendSettings";
+import { AwardTableSettings } from "./AwardTableSettings";
+import { PlayerSettings } from "./PlayerSettings";
+import { StakeSettings } from "./StakeSettings";
+import { PotSettings } from "./PotSettings";
+import {
+  AwardTable,
+  BackendConfig,
+  Player,
+  PotPolicy,
+  StakePolicy,
+  describeError,
+} from "../types";
 
 interface SettingsProps {
   onSheetIdChange: (sheetId: string) => Promise<void>;
   onSetDemoSheetId: () => Promise<void>;
   getCurrentSheetId: () => Promise<string>;
+  timeZone: string;
+  onTimeZoneChange: (timeZone: string) => Promise<void>;
+  getDeviceName: () => Promise<string>;
+  onDeviceNameChange: (name: string) => Promise<void>;
+  getBackendConfig: () => Promise<BackendConfig>;
+  onBackendConfigChange: (config: BackendConfig) => Promise<void>;
+  getAwardTable: () => Promise<AwardTable>;
+  getSuggestedAwardTable: () => Promise<AwardTable>;
+  onAwardTableChange: (table: AwardTable) => Promise<void>;
+  getStakePolicy: () => Promise<StakePolicy>;
+  onStakePolicyChange: (policy: StakePolicy) => Promise<void>;
+  getPotPolicy: () => Promise<PotPolicy>;
+  onPotPolicyChange: (policy: PotPolicy) => Promise<void>;
+  getPlayers: () => Promise<Player[]>;
+  onAddPlayer: (name: string) => Promise<void>;
+  onRenamePlayer: (player: string, newName: string) => Promise<void>;
+  onRetirePlayer: (player: string) => Promise<void>;
 }
 
 export const Settings = ({
   onSheetIdChange,
   onSetDemoSheetId,
   getCurrentSheetId,
+  timeZone,
+  onTimeZoneChange,
+  getDeviceName,
+  onDeviceNameChange,
+  getBackendConfig,
+  onBackendConfigChange,
+  getAwardTable,
+  getSuggestedAwardTable,
+  onAwardTableChange,
+  getStakePolicy,
+  onStakePolicyChange,
+  getPotPolicy,
+  onPotPolicyChange,
+  getPlayers,
+  onAddPlayer,
+  onRenamePlayer,
+  onRetirePlayer,
 }: SettingsProps) => {
   const [sheetId, setSheetId] = useState("");
   const [currentSheetId, setCurrentSheetId] = useState("");
   const [isOpen, setIsOpen] = useState(false);
   const [isSaving, setIsSaving] = useState(false);
+  const [zoneInput, setZoneInput] = useState(timeZone);
+  const [zoneError, setZoneError] = useState<string | null>(null);
+  const [deviceName, setDeviceName] = useState("");
+  const [deviceNameInput, setDeviceNameInput] = useState("");
+  const [deviceNameError, setDeviceNameError] = useState<string | null>(null);
+
+  useEffect(() => {
+    setZoneInput(timeZone);
+  }, [timeZone]);
+
+  const handleZoneSubmit = async (e: React.FormEvent) => {
+    e.preventDefault();
+    try {
+      await onTimeZoneChange(zoneInput.trim());
+      setZoneError(null);
+    } catch (error) {
+      setZoneError(describeError(error));
+    }
+  };
+
+  useEffect(() => {
+    getDeviceName().then((name) => {
+      setDeviceName(name);
+      setDeviceNameInput(name);
+    });
+  }, [getDeviceName]);
+
+  const handleDeviceNameSubmit = async (e: React.FormEvent) => {
+    e.preventDefault();
+    try {
+      await onDeviceNameChange(deviceNameInput.trim());
+      setDeviceName(deviceNameInput.trim());
+      setDeviceNameError(null);
+    } catch (error) {
+      setDeviceNameError(describeError(error));
+    }
+  };
 
   useEffect(() => {
     const loadCurrentSheetId = async () => {
@@ -76,6 +160,112 @@ export const Settings = ({
           <ThemeSwitcher />
         </div>
 
+        <form onSubmit={handleZoneSubmit} className="mb-6">
+          <label
+            htmlFor="time-zone"
+            className="block text-sm font-medium text-neutral mb-2"
+          >
+            Game Time Zone
+          </label>
+          <div className="flex space-x-2">
+            <input
+              id="time-zone"
+              type="text"
+              value={zoneInput}
+              onChange={(e) => setZoneInput(e.target.value)}
+              placeholder="e.g. America/Chicago"
+              className="flex-1 px-3 py-2 border border-neutral rounded-md bg-neutral text-neutral focus:outline-none focus-ring focus:ring-2"
+            />
+            <button
+              type="button"
+              onClick={() =>
+                setZoneInput(Intl.DateTimeFormat().resolvedOptions().timeZone)
+              }
+              className="bg-secondary-hover text-secondary px-3 rounded-md"
+              title="Use this device's time zone"
+            >
+              📍
+            </button>
+            <button
+              type="submit"
+              disabled={!zoneInput.trim() || zoneInput === timeZone}
+              className="bg-primary-hover text-primary px-4 rounded-md font-medium disabled:opacity-50 disabled:cursor-not-allowed"
+            >
+              Save
+            </button>
+          </div>
+          {zoneError && (
+            <p className="text-sm text-negative mt-1">{zoneError}</p>
+          )}
+        </form>
+
+        <form onSubmit={handleDeviceNameSubmit} className="mb-6">
+          <label
+            htmlFor="device-name"
+            className="block text-sm font-medium text-neutral mb-2"
+          >
+            Device Name
+          </label>
+          <div className="flex space-x-2">
+            <input
+              id="device-name"
+              type="text"
+              value={deviceNameInput}
+              onChange={(e) => setDeviceNameInput(e.target.value)}
+              placeholder="e.g. Lane 3 tablet"
+              className="flex-1 px-3 py-2 border border-neutral rounded-md bg-neutral text-neutral focus:outline-none focus-ring focus:ring-2"
+            />
+            <button
+              type="submit"
+              disabled={
+                !deviceNameInput.trim() ||
+                deviceNameInput.trim() === deviceName
+              }
+              className="bg-primary-hover text-primary px-4 rounded-md font-medium disabled:opacity-50 disabled:cursor-not-allowed"
+            >
+              Save
+            </button>
+          </div>
+          <p className="text-xs text-neutral opacity-70 mt-1">
+            Shown on transactions undone from this device
+          </p>
+          {deviceNameError && (
+            <p className="text-sm text-negative mt-1">{deviceNameError}</p>
+          )}
+        </form>
+
+        <PlayerSettings
+          getPlayers={getPlayers}
+          onAddPlayer={onAddPlayer}
+          onRenamePlayer={onRenamePlayer}
+          onRetirePlayer={onRetirePlayer}
+        />
+
+        <StakeSettings
+          getStakePolicy={getStakePolicy}
+          getPlayers={getPlayers}
+          onStakePolicyChange={onStakePolicyChange}
+        />
+
+        <PotSettings
+          getPotPolicy={getPotPolicy}
+          onPotPolicyChange={onPotPolicyChange}
+        />
+
+        <AwardTableSettings
+          getAwardTable={getAwardTable}
+          getSuggestedAwardTable={getSuggestedAwardTable}
+          onAwardTableChange={onAwardTableChange}
+        />
+
+        <BackendSettings
+          getBackendConfig={getBackendConfig}
+          onBackendConfigChange={async (config) => {
+            await onBackendConfigChange(config);
+            setIsOpen(false);
+          }}
+        />
+
         <div className="mb-4">
           <label className="block text-sm font-medium text-neutral mb-1">
             Current Sheet ID:
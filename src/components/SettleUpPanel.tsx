@@ -0,0 +1,122 @@
+import { useState } from "react";
+import { Modal } from "./Modal";
+import { Player, SettlementPlan, accountName, describeError } from "../types";
+
+interface SettleUpPanelProps {
+  players: Player[];
+  planSettlement: (keepPot: boolean) => Promise<SettlementPlan>;
+  onRecordSettlement: (plan: SettlementPlan) => Promise<void>;
+}
+
+export const SettleUpPanel = ({
+  players,
+  planSettlement,
+  onRecordSettlement,
+}: SettleUpPanelProps) => {
+  const [isOpen, setIsOpen] = useState(false);
+  const [keepPot, setKeepPot] = useState(true);
+  const [plan, setPlan] = useState<SettlementPlan | null>(null);
+  const [isRecording, setIsRecording] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = async (keep: boolean) => {
+    try {
+      setPlan(await planSettlement(keep));
+      setError(null);
+    } catch (err) {
+      setError(describeError(err));
+    }
+  };
+
+  const open = async () => {
+    setIsOpen(true);
+    await refresh(keepPot);
+  };
+
+  const toggleKeepPot = async (keep: boolean) => {
+    setKeepPot(keep);
+    await refresh(keep);
+  };
+
+  const record = async () => {
+    if (!plan) return;
+    setIsRecording(true);
+    try {
+      await onRecordSettlement(plan);
+      setIsOpen(false);
+    } catch (err) {
+      setError(describeError(err));
+    } finally {
+      setIsRecording(false);
+    }
+  };
+
+  return (
+    <>
+      <button
+        onClick={open}
+        className="bg-secondary-hover text-secondary p-2 rounded-md transition-colors w-10 h-10 flex items-center justify-center"
+        title="Settle Up"
+      >
+        💸
+      </button>
+
+      <Modal
+        isOpen={isOpen}
+        onClose={() => setIsOpen(false)}
+        title="Settle Up"
+      >
+        {error && <p className="text-sm text-negative mb-4">{error}</p>}
+        <label className="flex items-center space-x-2 text-neutral mb-4">
+          <input
+            type="checkbox"
+            checked={keepPot}
+            onChange={(e) => toggleKeepPot(e.target.checked)}
+          />
+          <span>Leave the pot for next time</span>
+        </label>
+        {plan && (
+          <div className="space-y-4 text-neutral">
+            {plan.payments.length === 0 ? (
+              <p className="text-sm opacity-80">Nobody owes anybody.</p>
+            ) : (
+              <ul className="space-y-1">
+                {plan.payments.map((payment, index) => (
+                  <li key={index} className="flex justify-between">
+                    <span>
+                      {accountName(payment.from, players)} pays{" "}
+                      {accountName(payment.to, players)}
+                    </span>
+                    <span>${payment.amount}</span>
+                  </li>
+                ))}
+              </ul>
+            )}
+
+            {plan.remaining.length > 0 && (
+              <div>
+                <h3 className="font-semibold mb-1">Left with the pot</h3>
+                <ul className="space-y-1 text-sm opacity-80">
+                  {plan.remaining.map((balance) => (
+                    <li key={balance.account} className="flex justify-between">
+                      <span>{balance.name}</span>
+                      <span>${balance.amount}</span>
+                    </li>
+                  ))}
+                </ul>
+              </div>
+            )}
+
+            <button
+              onClick={record}
+              disabled={isRecording || plan.payments.length === 0}
+              className="w-full bg-primary-hover text-primary py-2 px-4 rounded-md font-medium transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+            >
+              {isRecording ? "Recording..." : "Record Payments"}
+            </button>
+          </div>
+        )}
+      </Modal>
+    </>
+  );
+};
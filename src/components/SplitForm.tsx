@@ -1,36 +1,38 @@
 import { useState, useEffect } from "react";
 import { BowlingPinSelector } from "./BowlingPinSelector";
+import { Player, SplitGeometry } from "../types";
 
 interface SplitFormProps {
-  names: string[];
+  // Players who can record a split, i.e. not retired
+  players: Player[];
   validSplits: string[];
-  onCreateSplit: (name: string, split: string) => Promise<void>;
-  initialName?: string;
+  onCreateSplit: (player: string, pins: number[]) => Promise<void>;
+  classifySplit: (pins: number[]) => Promise<SplitGeometry>;
+  initialPlayer?: string;
   initialSplit?: string;
 }
 
 export const SplitForm = ({
-  names,
+  players,
   validSplits,
   onCreateSplit,
-  initialName = "",
+  classifySplit,
+  initialPlayer = "",
   initialSplit = "",
 }: SplitFormProps) => {
-  const [selectedName, setSelectedName] = useState(initialName);
+  const [selectedPlayer, setSelectedPlayer] = useState(initialPlayer);
   const [selectedSplit, setSelectedSplit] = useState(initialSplit);
   const [selectedPins, setSelectedPins] = useState<number[]>([]);
   const [isSubmitting, setIsSubmitting] = useState(false);
-
-  // Filter out "Pot" from names
-  const playerNames = names.filter((name) => name !== "Pot");
+  const [geometry, setGeometry] = useState<SplitGeometry | null>(null);
 
   useEffect(() => {
-    if (initialName) {
-      setSelectedName(initialName);
-    } else if (playerNames.length > 0 && !selectedName) {
-      setSelectedName(playerNames[0]);
+    if (initialPlayer) {
+      setSelectedPlayer(initialPlayer);
+    } else if (players.length > 0 && !selectedPlayer) {
+      setSelectedPlayer(players[0].id);
     }
-  }, [playerNames, selectedName, initialName]);
+  }, [players, selectedPlayer, initialPlayer]);
 
   useEffect(() => {
     if (initialSplit) {
@@ -40,9 +42,32 @@ export const SplitForm = ({
     }
   }, [validSplits, selectedSplit, initialSplit]);
 
+  // Matches the backend's canonical form: ascending pins joined by dashes
+  useEffect(() => {
+    if (selectedPins.length < 2) {
+      setGeometry(null);
+      return;
+    }
+    classifySplit(selectedPins)
+      .then(setGeometry)
+      .catch(() => setGeometry(null));
+  }, [selectedPins]);
+
+  const describeGeometry = (g: SplitGeometry): string => {
+    const labels = [
+      g.kind === "cluster" && "Not a split",
+      g.kind === "washout" && "Washout",
+      g.baby_split && "Baby split",
+      g.wide_split && "Wide split",
+      g.bucket && "Bucket",
+      g.sleepers.length > 0 && `Sleeper ${g.sleepers.join(", ")}`,
+    ].filter(Boolean);
+    return [`Difficulty ${g.difficulty.toFixed(1)}/10`, ...labels].join(" · ");
+  };
+
   const createSplitString = (pins: number[]): string => {
     if (pins.length === 0) return "";
-    return pins.join("-");
+    return [...pins].sort((a, b) => a - b).join("-");
   };
 
   const isValidSplit = (pins: number[]): boolean => {
@@ -54,7 +79,7 @@ export const SplitForm = ({
   const handleSubmit = async (e: React.FormEvent) => {
     e.preventDefault();
 
-    if (!selectedName || selectedPins.length === 0) return;
+    if (!selectedPlayer || selectedPins.length === 0) return;
 
     // Validate the split
     if (!isValidSplit(selectedPins)) {
@@ -62,11 +87,9 @@ export const SplitForm = ({
       return;
     }
 
-    const splitString = createSplitString(selectedPins);
-
     setIsSubmitting(true);
     try {
-      await onCreateSplit(selectedName, splitString);
+      await onCreateSplit(selectedPlayer, selectedPins);
     } catch (error) {
       console.error("Error submitting split:", error);
     } finally {
@@ -86,13 +109,13 @@ export const SplitForm = ({
           </label>
           <select
             id="name"
-            value={selectedName}
-            onChange={(e) => setSelectedName(e.target.value)}
+            value={selectedPlayer}
+            onChange={(e) => setSelectedPlayer(e.target.value)}
             className="w-full px-3 py-2 border border-neutral rounded-md bg-neutral text-neutral focus:outline-none focus-ring focus:ring-2"
           >
-            {playerNames.map((name) => (
-              <option key={name} value={name}>
-                {name}
+            {players.map((player) => (
+              <option key={player.id} value={player.id}>
+                {player.name}
               </option>
             ))}
           </select>
@@ -103,11 +126,17 @@ export const SplitForm = ({
           onPinsChange={setSelectedPins}
         />
 
+        {geometry && (
+          <p className="text-sm text-neutral opacity-80 text-center">
+            {describeGeometry(geometry)}
+          </p>
+        )}
+
         <button
           type="submit"
           disabled={
             isSubmitting ||
-            !selectedName ||
+            !selectedPlayer ||
             selectedPins.length === 0 ||
             !isValidSplit(selectedPins)
           }
@@ -0,0 +1,104 @@
+import { useState } from "react";
+import { Modal } from "./Modal";
+import { ConversionRate, SplitStats, describeError } from "../types";
+
+interface SplitStatsPanelProps {
+  getSplitStats: () => Promise<SplitStats>;
+}
+
+const formatRate = (stats: ConversionRate): string =>
+  `${stats.conversions}/${stats.attempts} (~${Math.round(
+    stats.smoothed_rate * 100
+  )}%)`;
+
+export const SplitStatsPanel = ({ getSplitStats }: SplitStatsPanelProps) => {
+  const [isOpen, setIsOpen] = useState(false);
+  const [stats, setStats] = useState<SplitStats | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [expandedPlayer, setExpandedPlayer] = useState<string | null>(null);
+
+  const open = async () => {
+    setIsOpen(true);
+    try {
+      setStats(await getSplitStats());
+      setError(null);
+    } catch (err) {
+      setError(describeError(err));
+    }
+  };
+
+  const mostSeen = stats
+    ? [...stats.by_split].sort((a, b) => b.attempts - a.attempts).slice(0, 10)
+    : [];
+
+  return (
+    <>
+      <button
+        onClick={open}
+        className="bg-secondary-hover text-secondary p-2 rounded-md transition-colors w-10 h-10 flex items-center justify-center"
+        title="Conversion Stats"
+      >
+        📊
+      </button>
+
+      <Modal
+        isOpen={isOpen}
+        onClose={() => setIsOpen(false)}
+        title="Conversion Stats"
+      >
+        {error && <p className="text-sm text-negative mb-4">{error}</p>}
+        {stats && (
+          <div className="space-y-6 text-neutral">
+            <p className="text-sm opacity-80">
+              Conversions/attempts this game. Percentages are smoothed so a
+              split seen once or twice doesn't read as 0% or 100%.
+            </p>
+
+            <div>
+              <h3 className="font-semibold mb-2">Players</h3>
+              {stats.by_player.map((player) => (
+                <div key={player.player} className="mb-1">
+                  <button
+                    onClick={() =>
+                      setExpandedPlayer(
+                        expandedPlayer === player.player ? null : player.player
+                      )
+                    }
+                    className="w-full flex justify-between text-left"
+                  >
+                    <span>{player.name}</span>
+                    <span>{formatRate(player)}</span>
+                  </button>
+                  {expandedPlayer === player.player && (
+                    <ul className="text-sm opacity-80 pl-4">
+                      {player.splits.map((split) => (
+                        <li key={split.split} className="flex justify-between">
+                          <span>{split.split}</span>
+                          <span>{formatRate(split)}</span>
+                        </li>
+                      ))}
+                    </ul>
+                  )}
+                </div>
+              ))}
+              <div className="flex justify-between border-t border-neutral mt-2 pt-1 font-medium">
+                <span>Everyone</span>
+                <span>{formatRate(stats.overall)}</span>
+              </div>
+            </div>
+
+            <div>
+              <h3 className="font-semibold mb-2">Most Common Splits</h3>
+              {mostSeen.map((split) => (
+                <div key={split.split} className="flex justify-between">
+                  <span>{split.split}</span>
+                  <span>{formatRate(split)}</span>
+                </div>
+              ))}
+            </div>
+          </div>
+        )}
+      </Modal>
+    </>
+  );
+};
@@ -0,0 +1,172 @@
+import { useState, useEffect } from "react";
+import { Player, StakePolicy, describeError } from "../types";
+
+interface StakeSettingsProps {
+  getStakePolicy: () => Promise<StakePolicy>;
+  getPlayers: () => Promise<Player[]>;
+  onStakePolicyChange: (policy: StakePolicy) => Promise<void>;
+}
+
+type EscalationKind = "none" | "add" | "multiply";
+
+const inputClass =
+  "px-3 py-2 border border-neutral rounded-md bg-neutral text-neutral focus:outline-none focus-ring focus:ring-2";
+
+export const StakeSettings = ({
+  getStakePolicy,
+  getPlayers,
+  onStakePolicyChange,
+}: StakeSettingsProps) => {
+  const [loaded, setLoaded] = useState(false);
+  const [base, setBase] = useState("");
+  const [escalationKind, setEscalationKind] = useState<EscalationKind>("none");
+  const [escalationValue, setEscalationValue] = useState("");
+  const [maxStake, setMaxStake] = useState("");
+  // Blank means the full stake
+  const [multipliers, setMultipliers] = useState<Record<string, string>>({});
+  const [players, setPlayers] = useState<Player[]>([]);
+  const [isSaving, setIsSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  // Load once per open; re-fetching on parent re-renders would discard edits.
+  useEffect(() => {
+    Promise.all([getStakePolicy(), getPlayers()]).then(([policy, roster]) => {
+      setBase(policy.base);
+      setEscalationKind(policy.escalation?.kind ?? "none");
+      setEscalationValue(
+        policy.escalation?.kind === "add"
+          ? policy.escalation.step
+          : policy.escalation?.kind === "multiply"
+          ? policy.escalation.factor.toString()
+          : ""
+      );
+      setMaxStake(policy.max_stake ?? "");
+      setMultipliers(
+        Object.fromEntries(
+          Object.entries(policy.player_multipliers ?? {}).map(
+            ([player, multiplier]) => [player, multiplier.toString()]
+          )
+        )
+      );
+      setPlayers(roster.filter((player) => !player.retired));
+      setLoaded(true);
+    });
+  }, []);
+
+  if (!loaded) return null;
+
+  const handleSubmit = async (e: React.FormEvent) => {
+    e.preventDefault();
+    setIsSaving(true);
+    try {
+      await onStakePolicyChange({
+        base: base.trim(),
+        player_multipliers: Object.fromEntries(
+          Object.entries(multipliers)
+            .filter(([, multiplier]) => multiplier.trim() !== "")
+            .map(([player, multiplier]) => [player, Number(multiplier)])
+        ),
+        escalation:
+          escalationKind === "add"
+            ? { kind: "add", step: escalationValue.trim() }
+            : escalationKind === "multiply"
+            ? { kind: "multiply", factor: Number(escalationValue) }
+            : undefined,
+        max_stake: maxStake.trim() || undefined,
+      });
+      setError(null);
+    } catch (err) {
+      setError(describeError(err));
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  return (
+    <form onSubmit={handleSubmit} className="space-y-3 mb-6">
+      <label
+        htmlFor="stake-base"
+        className="block text-sm font-medium text-neutral mb-2"
+      >
+        Stake Per Split
+      </label>
+      <div className="flex space-x-2">
+        <input
+          id="stake-base"
+          type="number"
+          min="0.01"
+          step="0.01"
+          value={base}
+          onChange={(e) => setBase(e.target.value)}
+          aria-label="Stake"
+          className={`flex-1 ${inputClass}`}
+        />
+        <input
+          type="number"
+          min="0.01"
+          step="0.01"
+          value={maxStake}
+          onChange={(e) => setMaxStake(e.target.value)}
+          placeholder="No maximum"
+          aria-label="Maximum stake"
+          className={`flex-1 ${inputClass}`}
+        />
+      </div>
+
+      <div className="flex space-x-2">
+        <select
+          value={escalationKind}
+          onChange={(e) => setEscalationKind(e.target.value as EscalationKind)}
+          aria-label="Repeated splits in a night"
+          className={`flex-1 ${inputClass}`}
+        >
+          <option value="none">Same stake every split</option>
+          <option value="add">Add per earlier split tonight</option>
+          <option value="multiply">Multiply per earlier split tonight</option>
+        </select>
+        {escalationKind !== "none" && (
+          <input
+            type="number"
+            min={escalationKind === "add" ? "0" : "1"}
+            step="0.01"
+            value={escalationValue}
+            onChange={(e) => setEscalationValue(e.target.value)}
+            placeholder={escalationKind === "add" ? "e.g. 0.50" : "e.g. 2"}
+            aria-label={
+              escalationKind === "add" ? "Amount added" : "Multiplier"
+            }
+            className={`w-24 ${inputClass}`}
+          />
+        )}
+      </div>
+
+      {players.map((player) => (
+        <div key={player.id} className="flex items-center space-x-2">
+          <span className="flex-1 text-neutral">{player.name}</span>
+          <input
+            type="number"
+            min="0.01"
+            step="0.01"
+            value={multipliers[player.id] ?? ""}
+            onChange={(e) =>
+              setMultipliers({ ...multipliers, [player.id]: e.target.value })
+            }
+            placeholder="×1"
+            aria-label={`Stake multiplier for ${player.name}`}
+            className={`w-24 ${inputClass}`}
+          />
+        </div>
+      ))}
+
+      {error && <p className="text-sm text-negative">{error}</p>}
+
+      <button
+        type="submit"
+        disabled={isSaving || !base.trim()}
+        className="w-full bg-primary-hover text-primary py-2 px-4 rounded-md font-medium transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+      >
+        {isSaving ? "Saving..." : "Save Stake"}
+      </button>
+    </form>
+  );
+};
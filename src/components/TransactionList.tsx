@@ -1,93 +1,205 @@
-import { Transaction } from "../types";
+import {
+  AwardBreakdown,
+  Player,
+  StakeBreakdown,
+  TRANSACTION_KIND_LABELS,
+  Transaction,
+  Void,
+  accountName,
+  formatTime,
+} from "../types";
+
+// Only the parts that differ from a plain first split are worth showing
+const describeStake = (stake: StakeBreakdown): string =>
+  [
+    stake.multiplier !== 1 && `×${stake.multiplier}`,
+    stake.attempt > 1 && `split #${stake.attempt} tonight`,
+  ]
+    .filter(Boolean)
+    .join(", ");
+
+const awardLimitLabels = {
+  min_award: "minimum award",
+  max_award: "maximum award",
+  reserve: "pot reserve",
+};
+
+const describeAward = (award: AwardBreakdown): string =>
+  award.limited_by
+    ? `${award.percent}% would be ${award.uncapped}; ${
+        awardLimitLabels[award.limited_by]
+      }`
+    : `${award.percent}%`;
+
+// Undo and corrections both void transactions, so this doesn't say which
+const describeVoid = (voided: Void, timeZone: string): string =>
+  `Voided by ${voided.by} ${formatTime(voided.at, timeZone)}${
+    voided.reason ? `: ${voided.reason}` : ""
+  }`;
+
+// Settlement payments credit the payer, since paying clears what they owed;
+// otherwise the money moves from debtor to creditor.
+const payer = (transaction: Transaction): string =>
+  transaction.kind === "settlement" ? transaction.creditor : transaction.debtor;
+
+const payee = (transaction: Transaction): string =>
+  transaction.kind === "settlement" ? transaction.debtor : transaction.creditor;
 
 interface TransactionListProps {
   transactions: Transaction[];
+  players: Player[];
+  timeZone: string;
   onUndo: () => void;
+  onRedo: () => void;
   onConvert: (transaction: Transaction) => void;
+  onCorrect: (transaction: Transaction) => void;
   onRefresh: () => void;
   refreshing: boolean;
+  hasMore: boolean;
+  onLoadMore: () => void;
+  loadingMore: boolean;
 }
 
 export const TransactionList = ({
   transactions,
+  players,
+  timeZone,
   onUndo,
+  onRedo,
   onConvert,
+  onCorrect,
   onRefresh,
   refreshing,
+  hasMore,
+  onLoadMore,
+  loadingMore,
 }: TransactionListProps) => {
-  // Only show the 5 most recent transactions
-  const recentTransactions = transactions.slice(0, 5);
-
   const isSplit = (transaction: Transaction): boolean => {
-    return transaction.creditor === "Pot" && transaction.debtor !== "Pot";
+    return transaction.kind === "split";
   };
 
+  const canConvert = (transaction: Transaction): boolean =>
+    isSplit(transaction) && !transaction.voided && !refreshing;
+
+  const canCorrect = (transaction: Transaction): boolean =>
+    !transaction.voided && !refreshing;
+
+  // Undo voids the latest transaction that still counts; redo is offered while
+  // nothing newer has been recorded since
+  const undoIndex = transactions.findIndex(
+    (transaction) => !transaction.voided
+  );
+  const canRedo = transactions.length > 0 && !!transactions[0].voided;
+
   return (
     <div className="bg-neutral bg-opacity-90 rounded-lg shadow-md border border-neutral">
       <div className="p-4 border-b bg-primary rounded-t-lg flex justify-between items-center">
         <h2 className="text-xl font-semibold text-neutral">Transactions</h2>
-        <button
-          onClick={onRefresh}
-          disabled={refreshing}
-          className="bg-secondary-hover text-secondary p-2 rounded-md transition-colors disabled:opacity-50 disabled:cursor-not-allowed w-8 h-8 flex items-center justify-center"
-          title="Refresh Data"
-        >
-          <svg
-            className={`w-3 h-3 ${refreshing ? "animate-spin" : ""}`}
-            fill="none"
-            stroke="currentColor"
-            viewBox="0 0 24 24"
-            xmlns="http://www.w3.org/2000/svg"
+        <div className="flex gap-2">
+          {canRedo && (
+            <button
+              onClick={onRedo}
+              disabled={refreshing}
+              className="bg-secondary-hover text-secondary p-2 rounded-md transition-colors disabled:opacity-50 disabled:cursor-not-allowed w-8 h-8 flex items-center justify-center"
+              title="Redo"
+            >
+              <svg
+                className="w-3 h-3"
+                fill="none"
+                stroke="currentColor"
+                viewBox="0 0 24 24"
+                xmlns="http://www.w3.org/2000/svg"
+              >
+                <path
+                  strokeLinecap="round"
+                  strokeLinejoin="round"
+                  strokeWidth={2}
+                  d="M15 15l6-6m0 0l-6-6m6 6H9a6 6 0 000 12h3"
+                />
+              </svg>
+            </button>
+          )}
+          <button
+            onClick={onRefresh}
+            disabled={refreshing}
+            className="bg-secondary-hover text-secondary p-2 rounded-md transition-colors disabled:opacity-50 disabled:cursor-not-allowed w-8 h-8 flex items-center justify-center"
+            title="Refresh Data"
           >
-            <path
-              strokeLinecap="round"
-              strokeLinejoin="round"
-              strokeWidth={2}
-              d="M16.023 9.348h4.992v-.001M2.985 19.644v-4.992m0 0h4.992m-4.993 0l3.181 3.183a8.25 8.25 0 0013.803-3.7M4.031 9.865a8.25 8.25 0 0113.803-3.7l3.181 3.182m0-4.991v4.99"
-            />
-          </svg>
-        </button>
+            <svg
+              className={`w-3 h-3 ${refreshing ? "animate-spin" : ""}`}
+              fill="none"
+              stroke="currentColor"
+              viewBox="0 0 24 24"
+              xmlns="http://www.w3.org/2000/svg"
+            >
+              <path
+                strokeLinecap="round"
+                strokeLinejoin="round"
+                strokeWidth={2}
+                d="M16.023 9.348h4.992v-.001M2.985 19.644v-4.992m0 0h4.992m-4.993 0l3.181 3.183a8.25 8.25 0 0013.803-3.7M4.031 9.865a8.25 8.25 0 0113.803-3.7l3.181 3.182m0-4.991v4.99"
+              />
+            </svg>
+          </button>
+        </div>
       </div>
       <div className="divide-y divide-neutral divide-opacity-20">
-        {recentTransactions.length === 0 ? (
+        {transactions.length === 0 ? (
           <div className="p-4 text-center text-neutral">
             No transactions yet
           </div>
         ) : (
-          recentTransactions.map((transaction, index) => (
+          transactions.map((transaction, index) => (
             <div
-              key={index}
-              className="p-4 hover:bg-neutral-hover transition-colors"
+              key={transaction.id}
+              className={`p-4 hover:bg-neutral-hover transition-colors ${
+                transaction.voided ? "opacity-50" : ""
+              }`}
             >
               <div className="grid grid-cols-[4fr_2fr_4fr] gap-4 items-center">
                 {/* Transaction Details */}
                 <div>
                   <div className="flex items-center space-x-2">
                     <span className="font-semibold text-neutral">
-                      {transaction.debtor}
+                      {accountName(payer(transaction), players)}
                     </span>
                     <span className="text-neutral">→</span>
                     <span className="font-semibold text-neutral">
-                      {transaction.creditor}
+                      {accountName(payee(transaction), players)}
                     </span>
                   </div>
                   <div className="text-sm text-neutral mt-2">
                     <span className="bg-primary text-primary px-2 py-1 rounded-full text-xs font-medium">
-                      {transaction.split}
+                      {transaction.split ??
+                        TRANSACTION_KIND_LABELS[transaction.kind]}
                     </span>
+                    {transaction.stake && (
+                      <span className="ml-2 text-xs opacity-70">
+                        {describeStake(transaction.stake)}
+                      </span>
+                    )}
+                    {transaction.award && (
+                      <span className="ml-2 text-xs opacity-70">
+                        {describeAward(transaction.award)}
+                      </span>
+                    )}
                   </div>
                   <div className="text-sm text-neutral mt-1">
-                    {transaction.date}
+                    {formatTime(transaction.time, timeZone)}
                   </div>
+                  {transaction.voided && (
+                    <div className="text-xs text-neutral italic mt-1">
+                      {describeVoid(transaction.voided, timeZone)}
+                    </div>
+                  )}
                 </div>
 
                 {/* Action buttons */}
                 <div className="flex gap-4">
                   <button
                     onClick={() => onConvert(transaction)}
-                    disabled={!isSplit(transaction) || refreshing}
+                    disabled={!canConvert(transaction)}
                     className={`p-2 rounded-md transition-colors w-8 h-8 flex items-center justify-center ${
-                      isSplit(transaction) && !refreshing
+                      canConvert(transaction)
                         ? "bg-confirmation-hover text-confirmation"
                         : "bg-gray-200 text-gray-400 cursor-not-allowed"
                     }`}
@@ -108,7 +220,32 @@ export const TransactionList = ({
                       />
                     </svg>
                   </button>
-                  {index === 0 ? (
+                  <button
+                    onClick={() => onCorrect(transaction)}
+                    disabled={!canCorrect(transaction)}
+                    className={`p-2 rounded-md transition-colors w-8 h-8 flex items-center justify-center ${
+                      canCorrect(transaction)
+                        ? "bg-secondary-hover text-secondary"
+                        : "bg-gray-200 text-gray-400 cursor-not-allowed"
+                    }`}
+                    title="Correct"
+                  >
+                    <svg
+                      className="w-3 h-3"
+                      fill="none"
+                      stroke="currentColor"
+                      viewBox="0 0 24 24"
+                      xmlns="http://www.w3.org/2000/svg"
+                    >
+                      <path
+                        strokeLinecap="round"
+                        strokeLinejoin="round"
+                        strokeWidth={2}
+                        d="M16.862 4.487l1.687-1.688a1.875 1.875 0 112.652 2.652L6.832 19.82a4.5 4.5 0 01-1.897 1.13l-2.685.8.8-2.685a4.5 4.5 0 011.13-1.897L16.863 4.487z"
+                      />
+                    </svg>
+                  </button>
+                  {index === undoIndex ? (
                     <button
                       onClick={onUndo}
                       disabled={refreshing}
@@ -146,12 +283,12 @@ export const TransactionList = ({
                       isSplit(transaction)
                         ? "bg-negative text-negative"
                         : "bg-confirmation text-confirmation"
-                    }`}
+                    } ${transaction.voided ? "line-through" : ""}`}
                   >
-                    ${transaction.amount.toFixed(2)}
+                    ${transaction.amount}
                   </div>
                   <div className="text-sm text-neutral mt-1">
-                    Pot: ${transaction.pot_amount.toFixed(2)}
+                    Pot: ${transaction.pot_amount}
                   </div>
                 </div>
               </div>
@@ -159,6 +296,17 @@ export const TransactionList = ({
           ))
         )}
       </div>
+      {hasMore && (
+        <div className="p-4 text-center border-t border-neutral">
+          <button
+            onClick={onLoadMore}
+            disabled={loadingMore || refreshing}
+            className="bg-secondary-hover text-secondary px-4 py-2 rounded-md transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+          >
+            {loadingMore ? "Loading..." : "Load older"}
+          </button>
+        </div>
+      )}
     </div>
   );
 };
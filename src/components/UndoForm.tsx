@@ -0,0 +1,58 @@
+import { useState } from "react";
+import { describeError } from "../types";
+
+interface UndoFormProps {
+  onUndo: (reason: string) => Promise<void>;
+}
+
+export const UndoForm = ({ onUndo }: UndoFormProps) => {
+  const [reason, setReason] = useState("");
+  const [isUndoing, setIsUndoing] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const handleSubmit = async (e: React.FormEvent) => {
+    e.preventDefault();
+    setIsUndoing(true);
+    try {
+      await onUndo(reason.trim());
+    } catch (err) {
+      setError(describeError(err));
+    } finally {
+      setIsUndoing(false);
+    }
+  };
+
+  return (
+    <form onSubmit={handleSubmit} className="space-y-4">
+      <p className="text-sm text-neutral">
+        The latest transaction will be greyed out and stop counting toward
+        balances. It can be redone until something else is recorded.
+      </p>
+      <div>
+        <label
+          htmlFor="undo-reason"
+          className="block text-sm font-medium text-neutral mb-2"
+        >
+          Reason (optional)
+        </label>
+        <input
+          id="undo-reason"
+          type="text"
+          value={reason}
+          onChange={(e) => setReason(e.target.value)}
+          placeholder="e.g. Wrong player"
+          autoFocus
+          className="w-full px-3 py-2 border border-neutral rounded-md bg-neutral text-neutral focus:outline-none focus-ring focus:ring-2"
+        />
+      </div>
+      {error && <p className="text-sm text-negative">{error}</p>}
+      <button
+        type="submit"
+        disabled={isUndoing}
+        className="w-full bg-negative-hover text-negative py-2 px-4 rounded-md font-medium transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+      >
+        {isUndoing ? "Undoing..." : "Undo"}
+      </button>
+    </form>
+  );
+};
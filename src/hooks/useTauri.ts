@@ -1,31 +1,85 @@
 import { invoke } from "@tauri-apps/api/core";
-import { Balance, Transaction } from "../types";
+import {
+  Activity,
+  ActivityFilter,
+  AwardTable,
+  BackendConfig,
+  Balance,
+  GameSummary,
+  NewGame,
+  Payment,
+  Player,
+  PotPolicy,
+  Replay,
+  SplitGeometry,
+  SettlementPlan,
+  SplitStats,
+  StakePolicy,
+  Transaction,
+  TransactionPage,
+} from "../types";
 
 export const useTauri = () => {
+  const listGames = async (): Promise<GameSummary[]> => {
+    return await invoke("list_games");
+  };
+
+  const getCurrentGame = async (): Promise<GameSummary> => {
+    return await invoke("get_current_game");
+  };
+
+  const createGame = async (game: NewGame): Promise<GameSummary> => {
+    return await invoke("create_game", { game });
+  };
+
+  const selectGame = async (gameId: string): Promise<GameSummary> => {
+    return await invoke("select_game", { game_id: gameId });
+  };
+
   const getBalances = async (): Promise<Balance[]> => {
     return await invoke("balances");
   };
 
-  const getNames = async (): Promise<string[]> => {
-    return await invoke("names");
+  const getPlayers = async (): Promise<Player[]> => {
+    return await invoke("get_players");
+  };
+
+  // Resolves to the new (or returning) player's id
+  const addPlayer = async (name: string): Promise<string> => {
+    return await invoke("add_player", { name });
+  };
+
+  const renamePlayer = async (
+    player: string,
+    newName: string
+  ): Promise<void> => {
+    return await invoke("rename_player", { player, new_name: newName });
+  };
+
+  const retirePlayer = async (player: string): Promise<void> => {
+    return await invoke("retire_player", { player });
   };
 
   const getTransactions = async (count: number): Promise<Transaction[]> => {
     return await invoke("transactions", { count });
   };
 
-  const createSplit = async (
-    name: string,
-    splitString: string
-  ): Promise<void> => {
-    return await invoke("create_split", { name, split_string: splitString });
+  const getTransactionsPage = async (
+    before: number | null,
+    limit: number
+  ): Promise<TransactionPage> => {
+    return await invoke("transactions_page", { before, limit });
+  };
+
+  const createSplit = async (player: string, pins: number[]): Promise<void> => {
+    return await invoke("create_split", { player, pins });
   };
 
   const convertSplit = async (
-    name: string,
-    splitString: string
+    player: string,
+    pins: number[]
   ): Promise<void> => {
-    return await invoke("convert_split", { name, split_string: splitString });
+    return await invoke("convert_split", { player, pins });
   };
 
   const getValidSplits = async (): Promise<string[]> => {
@@ -33,8 +87,33 @@ export const useTauri = () => {
     return Array.from(splits as Set<string>);
   };
 
-  const removeLastTransaction = async (): Promise<void> => {
-    return await invoke("remove_last_transaction");
+  // Resolves to the transaction, now marked voided
+  const removeLastTransaction = async (
+    reason?: string
+  ): Promise<Transaction> => {
+    return await invoke("remove_last_transaction", { reason });
+  };
+
+  const redoLastUndo = async (): Promise<Transaction> => {
+    return await invoke("redo_last_undo");
+  };
+
+  // With preview set nothing is written; the result shows what would change
+  const voidTransaction = async (
+    id: number,
+    reason: string | undefined,
+    preview: boolean
+  ): Promise<Replay> => {
+    return await invoke("void_transaction", { id, reason, preview });
+  };
+
+  const amendTransaction = async (
+    id: number,
+    player: string,
+    pins: number[],
+    preview: boolean
+  ): Promise<Replay> => {
+    return await invoke("amend_transaction", { id, player, pins, preview });
   };
 
   const setSheetId = async (sheetId: string): Promise<void> => {
@@ -49,16 +128,122 @@ export const useTauri = () => {
     return await invoke("set_demo_sheet_id");
   };
 
+  const getSplitStats = async (): Promise<SplitStats> => {
+    return await invoke("split_stats");
+  };
+
+  const planSettlement = async (keepPot: boolean): Promise<SettlementPlan> => {
+    return await invoke("plan_settlement", { keep_pot: keepPot });
+  };
+
+  const recordSettlement = async (
+    payments: Payment[]
+  ): Promise<Transaction[]> => {
+    return await invoke("record_settlement", { payments });
+  };
+
+  // Newest first
+  const getActivityLog = async (
+    filter: ActivityFilter
+  ): Promise<Activity[]> => {
+    return await invoke("get_activity_log", { filter });
+  };
+
+  const classifySplit = async (pins: number[]): Promise<SplitGeometry> => {
+    return await invoke("classify_split", { pins });
+  };
+
+  const getSuggestedAwardTable = async (): Promise<AwardTable> => {
+    return await invoke("get_suggested_award_table");
+  };
+
+  const getAwardTable = async (): Promise<AwardTable> => {
+    return await invoke("get_award_table");
+  };
+
+  const setAwardTable = async (table: AwardTable): Promise<void> => {
+    return await invoke("set_award_table", { table });
+  };
+
+  const getStakePolicy = async (): Promise<StakePolicy> => {
+    return await invoke("get_stake_policy");
+  };
+
+  const setStakePolicy = async (policy: StakePolicy): Promise<void> => {
+    return await invoke("set_stake_policy", { policy });
+  };
+
+  const getPotPolicy = async (): Promise<PotPolicy> => {
+    return await invoke("get_pot_policy");
+  };
+
+  const setPotPolicy = async (policy: PotPolicy): Promise<void> => {
+    return await invoke("set_pot_policy", { policy });
+  };
+
+  const getDisplayTimezone = async (): Promise<string> => {
+    return await invoke("get_display_timezone");
+  };
+
+  const setDisplayTimezone = async (timezone: string): Promise<void> => {
+    return await invoke("set_display_timezone", { timezone });
+  };
+
+  const getDeviceName = async (): Promise<string> => {
+    return await invoke("get_device_name");
+  };
+
+  const setDeviceName = async (name: string): Promise<void> => {
+    return await invoke("set_device_name", { name });
+  };
+
+  const getBackendConfig = async (): Promise<BackendConfig> => {
+    return await invoke("get_backend_config");
+  };
+
+  const setBackendConfig = async (config: BackendConfig): Promise<void> => {
+    return await invoke("set_backend_config", { config });
+  };
+
   return {
+    listGames,
+    getCurrentGame,
+    selectGame,
+    createGame,
     getBalances,
-    getNames,
+    getPlayers,
+    addPlayer,
+    renamePlayer,
+    retirePlayer,
     getTransactions,
+    getTransactionsPage,
     createSplit,
     convertSplit,
     getValidSplits,
+    getSplitStats,
+    planSettlement,
+    recordSettlement,
+    getActivityLog,
+    classifySplit,
+    getSuggestedAwardTable,
     removeLastTransaction,
+    redoLastUndo,
+    voidTransaction,
+    amendTransaction,
     setSheetId,
     getSheetId,
     setDemoSheetId,
+    getAwardTable,
+    setAwardTable,
+    getStakePolicy,
+    setStakePolicy,
+    getPotPolicy,
+    setPotPolicy,
+    getDisplayTimezone,
+    setDisplayTimezone,
+    getDeviceName,
+    setDeviceName,
+    getBackendConfig,
+    setBackendConfig,
   };
 };
@@ -1,14 +1,339 @@
+// Accounts are "pot" or a player id; look names up with accountName
+export const POT_ACCOUNT = "pot";
+
+export type TransactionKind =
+  | "split"
+  | "conversion"
+  | "settlement"
+  | "manual_adjustment"
+  | "buy_in"
+  | "reversal";
+
+export const TRANSACTION_KIND_LABELS: Record<TransactionKind, string> = {
+  split: "Split",
+  conversion: "Conversion",
+  settlement: "Settled up",
+  manual_adjustment: "Adjustment",
+  buy_in: "Buy-in",
+  reversal: "Reversal",
+};
+
 export interface Transaction {
+  id: number;
+  kind: TransactionKind;
   creditor: string;
   debtor: string;
-  amount: number;
-  split: string;
+  // Exact decimal strings with two places, e.g. "12.30"
+  amount: string;
+  // Canonical pin list in ascending order, e.g. "2-3-10"; absent on settlements
+  split?: string;
+  // RFC 3339 UTC timestamp; format with the game's display time zone
   time: string;
-  pot_amount: number;
-  date: string;
+  pot_amount: string;
+  // Present on splits recorded since stakes could vary
+  stake?: StakeBreakdown;
+  // Present on conversions recorded since pots had a policy
+  award?: AwardBreakdown;
+  // Present once voided by an undo or correction; voided transactions don't count
+  // toward balances
+  voided?: Void;
+}
+
+export interface BalanceChange {
+  account: string;
+  name: string;
+  before: string;
+  after: string;
+}
+
+// What correcting a past transaction changes, as previewed or as committed
+export interface Replay {
+  // The corrected transaction, then later ones whose pot or award changed
+  changed: Transaction[];
+  // Only accounts whose balance moves, ordered by name
+  balances: BalanceChange[];
+}
+
+export type ActivityAction =
+  | "create_game"
+  | "add_player"
+  | "rename_player"
+  | "retire_player"
+  | "split"
+  | "conversion"
+  | "settlement"
+  | "undo"
+  | "redo"
+  | "void"
+  | "amend"
+  | "set_award_table"
+  | "set_stake_policy"
+  | "set_pot_policy"
+  | "set_display_timezone";
+
+export const ACTIVITY_ACTION_LABELS: Record<ActivityAction, string> = {
+  create_game: "Created game",
+  add_player: "Added player",
+  rename_player: "Renamed player",
+  retire_player: "Retired player",
+  split: "Split",
+  conversion: "Conversion",
+  settlement: "Settled up",
+  undo: "Undo",
+  redo: "Redo",
+  void: "Voided",
+  amend: "Amended",
+  set_award_table: "Changed award table",
+  set_stake_policy: "Changed stake",
+  set_pot_policy: "Changed pot rules",
+  set_display_timezone: "Changed time zone",
+};
+
+// One entry in a game's activity log
+export interface Activity {
+  // Position in the log, counting from 1
+  id: number;
+  // RFC 3339 UTC timestamp
+  at: string;
+  // The name of the device that made the change
+  actor: string;
+  action: ActivityAction;
+  transactions?: number[];
+  // e.g. "Ann 7-10", or the reason given for an undo
+  detail?: string;
+  // Absent for changes to players and settings
+  balances?: BalanceChange[];
+}
+
+// Every field set must match; an empty filter matches everything
+export interface ActivityFilter {
+  actions?: ActivityAction[];
+  actor?: string;
+  transaction?: number;
+  since?: string;
+  until?: string;
+  limit?: number;
+}
+
+export interface Void {
+  // The name of the device that voided the transaction
+  by: string;
+  // RFC 3339 UTC timestamp
+  at: string;
+  reason?: string;
+}
+
+export type Escalation =
+  | { kind: "add"; step: string }
+  | { kind: "multiply"; factor: number };
+
+export interface StakePolicy {
+  base: string;
+  // Keyed by player id; players not listed pay the full stake
+  player_multipliers?: Record<string, number>;
+  escalation?: Escalation;
+  max_stake?: string;
+}
+
+export interface StakeBreakdown {
+  base: string;
+  multiplier: number;
+  // 1 for the player's first split of the night
+  attempt: number;
+}
+
+// `from` hands `amount` to `to`; both are accounts
+export interface Payment {
+  from: string;
+  to: string;
+  amount: string;
+}
+
+export interface SettlementPlan {
+  payments: Payment[];
+  // What players still owe to or are owed by the pot when it is kept
+  remaining: Balance[];
+}
+
+export interface PotPolicy {
+  min_award?: string;
+  max_award?: string;
+  // Never paid out; "0.00" for none
+  reserve: string;
+  // Awards are whole multiples of this; to the cent when omitted
+  rounding?: string;
+}
+
+export type AwardLimit = "min_award" | "max_award" | "reserve";
+
+export interface AwardBreakdown {
+  percent: number;
+  // The percentage of the pot before the policy was applied
+  uncapped: string;
+  limited_by?: AwardLimit;
+}
+
+export const splitPins = (split: string): number[] =>
+  split.split("-").map(Number);
+
+export const formatTime = (time: string, timeZone: string): string => {
+  try {
+    return new Date(time).toLocaleString(undefined, {
+      timeZone,
+      dateStyle: "short",
+      timeStyle: "short",
+    });
+  } catch {
+    // Unknown time zone names throw; fall back to the device's zone
+    return new Date(time).toLocaleString(undefined, {
+      dateStyle: "short",
+      timeStyle: "short",
+    });
+  }
+};
+
+export interface GameSummary {
+  id: string;
+  name: string;
+}
+
+export interface NewGame {
+  name: string;
+  players: string[];
+  // A bare amount is a flat stake
+  stake: string | StakePolicy;
+  // Omit for no limits on awards
+  pot_policy?: PotPolicy;
+  // Omit for the standard table
+  award_table?: AwardTable;
+}
+
+export interface Player {
+  id: string;
+  name: string;
+  retired: boolean;
+  former_names: string[];
 }
 
+export interface TransactionPage {
+  transactions: Transaction[];
+  next_before: number | null;
+}
+
+export const accountName = (account: string, players: Player[]): string =>
+  account === POT_ACCOUNT
+    ? "Pot"
+    : players.find((player) => player.id === account)?.name ?? account;
+
+// The player a split or conversion is for, whichever side of the ledger they're on
+export const transactionPlayer = (transaction: Transaction): string =>
+  transaction.creditor === POT_ACCOUNT
+    ? transaction.debtor
+    : transaction.creditor;
+
 export interface Balance {
+  account: string;
   name: string;
   amount: string;
-}
\ No newline at end of file
+}
+export interface StorageError {
+  kind:
+    | "not_found"
+    | "invalid_split"
+    | "conflict"
+    | "unauthorized"
+    | "backend_unavailable"
+    | "corrupt_record"
+    | "invalid_config";
+  message: string;
+}
+
+export const describeError = (err: unknown): string => {
+  const storageError = err as Partial<StorageError> | null;
+  if (storageError && typeof storageError.message === "string") {
+    return storageError.message;
+  }
+  return String(err);
+};
+
+export interface ConversionRate {
+  attempts: number;
+  conversions: number;
+  rate: number | null;
+  // Shrunk toward a broader average so rarely seen splits aren't 0% or 100%
+  smoothed_rate: number;
+}
+
+export interface SplitRecord extends ConversionRate {
+  split: string;
+}
+
+export interface PlayerRecord extends ConversionRate {
+  player: string;
+  name: string;
+  splits: SplitRecord[];
+}
+
+export interface SplitStats {
+  overall: ConversionRate;
+  by_split: SplitRecord[];
+  by_player: PlayerRecord[];
+}
+
+export interface SplitGeometry {
+  pins: number[];
+  kind: "split" | "washout" | "cluster";
+  baby_split: boolean;
+  wide_split: boolean;
+  bucket: boolean;
+  sleepers: number[];
+  lateral_gap: number;
+  row_span: number;
+  // 0-10
+  difficulty: number;
+  suggested_percent: number | null;
+}
+
+export interface Bounds {
+  min?: number;
+  max?: number;
+}
+
+// Mirrors the backend's externally tagged SplitRule enum
+export type SplitRule =
+  | { contains_all: number[] }
+  | { contains_any: number[] }
+  | { within: number[] }
+  | { pin_count: Bounds }
+  | { lateral_gap: Bounds }
+  | { row_span: Bounds }
+  | { exact: string[] }
+  | "cluster"
+  | { all: SplitRule[] }
+  | { any: SplitRule[] }
+  | { not: SplitRule };
+
+export interface AwardTier {
+  percent: number;
+  // Canonical split strings, e.g. "7-10"
+  splits?: string[];
+  rule?: SplitRule;
+}
+
+export interface AwardTable {
+  name: string;
+  tiers: AwardTier[];
+}
+
+export interface DynamoDbConfig {
+  region: string;
+  games_table: string;
+  transactions_table: string;
+  activity_table: string;
+  endpoint?: string | null;
+}
+
+export type BackendConfig =
+  | { kind: "memory"; file_path?: string | null }
+  | ({ kind: "dynamo_db" } & DynamoDbConfig)
+  | { kind: "sheets"; sheet_id: string };